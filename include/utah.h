@@ -0,0 +1,54 @@
+/* Utah C API. See src/ffi.rs for the full documentation.
+ *
+ * Frames hold doubles and are passed around as opaque pointers, which must be released with
+ * utah_frame_free. Functions that build a frame return NULL on failure; the others return 0 or a
+ * count on success and -1 on failure, including when passed a NULL frame.
+ */
+
+#ifndef UTAH_H
+#define UTAH_H
+
+#include <stddef.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct utah_frame utah_frame;
+
+/* Aggregation codes for utah_frame_agg. */
+enum utah_agg {
+    UTAH_AGG_SUM = 0,
+    UTAH_AGG_MEAN = 1,
+    UTAH_AGG_MAX = 2,
+    UTAH_AGG_MIN = 3,
+    UTAH_AGG_COUNT = 4,
+    UTAH_AGG_EMPTY_COUNT = 5
+};
+
+/* A frame from nrows * ncols values laid out row by row, with ncols column names. */
+utah_frame *utah_frame_new(const double *data, size_t nrows, size_t ncols,
+                           const char *const *columns);
+void utah_frame_free(utah_frame *df);
+
+ptrdiff_t utah_frame_nrows(const utah_frame *df);
+ptrdiff_t utah_frame_ncols(const utah_frame *df);
+
+/* Copy a label to out, truncated to len - 1 bytes and nul-terminated, and return its full
+ * length. A NULL out only returns the length. */
+ptrdiff_t utah_frame_column_name(const utah_frame *df, size_t i, char *out, size_t len);
+ptrdiff_t utah_frame_index_label(const utah_frame *df, size_t i, char *out, size_t len);
+
+utah_frame *utah_frame_select(const utah_frame *df, const char *const *names, size_t n);
+utah_frame *utah_frame_filter(const utah_frame *df, const char *column, double lo, double hi);
+
+/* Write one aggregate per column to out. */
+int utah_frame_agg(const utah_frame *df, int agg, double *out);
+/* Copy every value to out, row by row. */
+int utah_frame_read(const utah_frame *df, double *out);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* UTAH_H */
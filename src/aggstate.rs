@@ -0,0 +1,194 @@
+//! Utah aggregation state
+//!
+//! An `AggState` holds the partial statistics of each column of a shard of a dataframe. Partials
+//! are plain data, so workers can each aggregate a shard, ship the encoded state to a coordinator,
+//! and the coordinator can `merge` them into the statistics of the whole frame.
+
+use dataframe::DataFrame;
+use ndarray::{Array, Axis};
+use util::error::*;
+use util::traits::UtahNum;
+use util::helpers::{count, DESCRIBE_STATS};
+use util::types::*;
+use rustc_serialize::{Decodable, Decoder, Encodable, Encoder};
+use std::result;
+
+/// The partial statistics of one column.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColumnState<T> {
+    pub count: usize,
+    pub empties: usize,
+    pub sum: T,
+    pub min: T,
+    pub max: T,
+}
+
+impl<T> ColumnState<T>
+    where T: UtahNum + PartialOrd
+{
+    pub fn new() -> ColumnState<T> {
+        ColumnState {
+            count: 0,
+            empties: 0,
+            sum: T::zero(),
+            min: T::empty(),
+            max: T::empty(),
+        }
+    }
+
+    /// Fold one more value into the state.
+    pub fn update(&mut self, x: &T) {
+        if x.is_empty() {
+            self.empties += 1;
+            return;
+        }
+        self.count += 1;
+        self.sum = self.sum.clone() + x.clone();
+        if self.min.is_empty() || *x < self.min {
+            self.min = x.clone();
+        }
+        if self.max.is_empty() || *x > self.max {
+            self.max = x.clone();
+        }
+    }
+
+    /// Combine with the state of another shard of the same column.
+    pub fn merge(&mut self, other: &ColumnState<T>) {
+        self.count += other.count;
+        self.empties += other.empties;
+        self.sum = self.sum.clone() + other.sum.clone();
+        if !other.min.is_empty() && (self.min.is_empty() || other.min < self.min) {
+            self.min = other.min.clone();
+        }
+        if !other.max.is_empty() && (self.max.is_empty() || other.max > self.max) {
+            self.max = other.max.clone();
+        }
+    }
+
+    /// The final value of a statistic. Only the statistics of `DESCRIBE_STATS` are kept.
+    pub fn finish(&self, agg: Agg) -> Option<T> {
+        match agg {
+            Agg::Sum => Some(self.sum.clone()),
+            Agg::Mean if self.count == 0 => Some(T::empty()),
+            Agg::Mean => Some(self.sum.clone() / count(self.count)),
+            Agg::Min => Some(self.min.clone()),
+            Agg::Max => Some(self.max.clone()),
+            Agg::Count => Some(count(self.count)),
+            Agg::EmptyCount => Some(count(self.empties)),
+            Agg::Prod | Agg::FirstValid | Agg::LastValid => None,
+        }
+    }
+}
+
+impl<T> Default for ColumnState<T>
+    where T: UtahNum + PartialOrd
+{
+    fn default() -> ColumnState<T> {
+        ColumnState::new()
+    }
+}
+
+/// The partial statistics of every column of a dataframe.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AggState<T> {
+    pub columns: Vec<String>,
+    pub states: Vec<ColumnState<T>>,
+}
+
+impl<T: Encodable> Encodable for ColumnState<T> {
+    fn encode<S: Encoder>(&self, s: &mut S) -> result::Result<(), S::Error> {
+        s.emit_struct("ColumnState", 5, |s| {
+            s.emit_struct_field("count", 0, |s| self.count.encode(s))?;
+            s.emit_struct_field("empties", 1, |s| self.empties.encode(s))?;
+            s.emit_struct_field("sum", 2, |s| self.sum.encode(s))?;
+            s.emit_struct_field("min", 3, |s| self.min.encode(s))?;
+            s.emit_struct_field("max", 4, |s| self.max.encode(s))
+        })
+    }
+}
+
+impl<T: Decodable> Decodable for ColumnState<T> {
+    fn decode<D: Decoder>(d: &mut D) -> result::Result<ColumnState<T>, D::Error> {
+        d.read_struct("ColumnState", 5, |d| {
+            Ok(ColumnState {
+                count: d.read_struct_field("count", 0, Decodable::decode)?,
+                empties: d.read_struct_field("empties", 1, Decodable::decode)?,
+                sum: d.read_struct_field("sum", 2, Decodable::decode)?,
+                min: d.read_struct_field("min", 3, Decodable::decode)?,
+                max: d.read_struct_field("max", 4, Decodable::decode)?,
+            })
+        })
+    }
+}
+
+impl<T: Encodable> Encodable for AggState<T> {
+    fn encode<S: Encoder>(&self, s: &mut S) -> result::Result<(), S::Error> {
+        s.emit_struct("AggState", 2, |s| {
+            s.emit_struct_field("columns", 0, |s| self.columns.encode(s))?;
+            s.emit_struct_field("states", 1, |s| self.states.encode(s))
+        })
+    }
+}
+
+impl<T: Decodable> Decodable for AggState<T> {
+    fn decode<D: Decoder>(d: &mut D) -> result::Result<AggState<T>, D::Error> {
+        d.read_struct("AggState", 2, |d| {
+            Ok(AggState {
+                columns: d.read_struct_field("columns", 0, Decodable::decode)?,
+                states: d.read_struct_field("states", 1, Decodable::decode)?,
+            })
+        })
+    }
+}
+
+impl<T> AggState<T>
+    where T: UtahNum + PartialOrd
+{
+    /// An empty state for the given columns.
+    pub fn new(columns: Vec<String>) -> AggState<T> {
+        AggState {
+            states: columns.iter().map(|_| ColumnState::new()).collect(),
+            columns: columns,
+        }
+    }
+
+    /// The state of a whole dataframe.
+    pub fn from_df(df: &DataFrame<T>) -> AggState<T> {
+        let mut state = AggState::new(df.columns.clone());
+        for (s, c) in state.states.iter_mut().zip(df.data.axis_iter(Axis(1))) {
+            for x in c.iter() {
+                s.update(x);
+            }
+        }
+        state
+    }
+
+    /// Combine with the state of another shard. Both states must cover the same columns, in the
+    /// same order.
+    pub fn merge(&mut self, other: &AggState<T>) -> Result<()> {
+        if self.columns != other.columns {
+            return Err(ErrorKind::MisalignedLabels(format!("{:?} and {:?}",
+                                                           self.columns,
+                                                           other.columns))
+                .into());
+        }
+        for (s, o) in self.states.iter_mut().zip(other.states.iter()) {
+            s.merge(o);
+        }
+        Ok(())
+    }
+
+    /// The final statistics, laid out like the output of `describe`.
+    pub fn finish(&self) -> DataFrame<T> {
+        let mut d = Vec::with_capacity(DESCRIBE_STATS.len() * self.states.len());
+        for &(_, agg) in DESCRIBE_STATS.iter() {
+            d.extend(self.states.iter().map(|s| s.finish(agg).unwrap()));
+        }
+        DataFrame {
+            columns: self.columns.clone(),
+            data: Array::from_shape_vec((DESCRIBE_STATS.len(), self.states.len()), d).unwrap(),
+            index: DESCRIBE_STATS.iter().map(|&(name, _)| name.to_string()).collect(),
+            index_sorted: false,
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use dataframe::*;
+#[cfg(nightly)]
+use test::Bencher;
+use ndarray::Array;
+use rand::distributions::Range;
+use ndarray_rand::RandomExt;
+use util::types::*;
+use util::traits::*;
+
+fn frame(rows: usize, cols: usize, offset: usize) -> DataFrame<f64> {
+    let a = Array::random((rows, cols), Range::new(0., 10.));
+    let columns: Vec<String> = (0..cols).map(|i| format!("{}_{}", offset, i)).collect();
+    let index: Vec<String> = (offset..offset + rows).map(|i| i.to_string()).collect();
+    DataFrame::new(a).columns(&columns[..]).unwrap().index(&index[..]).unwrap()
+}
+
+#[bench]
+fn bench_inner_join(b: &mut Bencher) {
+    let left = frame(20000, 10, 0);
+    let right = frame(20000, 10, 10000);
+    b.iter(|| {
+        let _ = left.inner_left_join(&right).as_df();
+    });
+}
+
+#[bench]
+fn bench_inner_join_small_left(b: &mut Bencher) {
+    let left = frame(200, 10, 0);
+    let right = frame(20000, 10, 100);
+    b.iter(|| {
+        let _ = left.inner_left_join(&right).as_df();
+    });
+}
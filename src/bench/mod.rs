@@ -5,3 +5,7 @@ pub mod bench_sum;
 #[allow(unused_imports)]
 #[cfg(nightly)]
 pub mod bench_creation;
+
+#[allow(unused_imports)]
+#[cfg(nightly)]
+pub mod bench_join;
@@ -0,0 +1,121 @@
+//! Utah statistics cache
+//!
+//! A `StatsCache` remembers the summary statistics of every column it has seen, along with a copy
+//! of the column to confirm a match. Share one cache (e.g. behind an `Arc`) across the frames of an
+//! interactive session and repeated `describe_cached` calls only compute the columns that
+//! changed.
+//!
+//! Every column cached is copied, so a cache holds as much memory as all the distinct columns it
+//! has seen. `StatsCache::with_limit` caps the number of columns kept; beyond it, the statistics of
+//! new columns are computed without being stored. `clear` releases everything.
+
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::{Mutex, MutexGuard};
+use util::error::*;
+use util::traits::UtahNum;
+use util::types::ColumnView;
+use util::helpers::{reduce, DESCRIBE_STATS};
+use util::keys::ReprKey;
+
+/// The number of values a fingerprint looks at.
+const SAMPLES: usize = 16;
+
+/// A fingerprint of a column, computed from its length and up to `SAMPLES` evenly spaced values.
+/// It is cheap to compute but only narrows down the candidates: columns with the same fingerprint
+/// can still differ.
+pub fn fingerprint<T>(values: ColumnView<T>) -> u64
+    where T: UtahNum
+{
+    let mut h = DefaultHasher::new();
+    values.len().hash(&mut h);
+    let step = (values.len() / SAMPLES).max(1);
+    for x in values.iter().step_by(step).take(SAMPLES) {
+        ReprKey(x).hash(&mut h);
+    }
+    h.finish()
+}
+
+/// Whether a column holds the same values as a cached one, counting empty values as equal.
+fn same_values<T>(values: ColumnView<T>, cached: &[T]) -> bool
+    where T: UtahNum
+{
+    values.len() == cached.len() &&
+    values.iter().zip(cached.iter()).all(|(a, b)| a == b || (a.is_empty() && b.is_empty()))
+}
+
+#[derive(Debug, Default)]
+pub struct StatsCache<T>
+    where T: UtahNum
+{
+    entries: Mutex<HashMap<u64, Vec<(Vec<T>, Vec<T>)>>>,
+    hits: Mutex<usize>,
+    limit: Option<usize>,
+}
+
+/// Lock a part of the cache, failing if a thread panicked while holding it.
+fn lock<'a, U>(m: &'a Mutex<U>) -> Result<MutexGuard<'a, U>> {
+    m.lock().map_err(|_| ErrorKind::PoisonedLock("stats cache".to_string()).into())
+}
+
+impl<T> StatsCache<T>
+    where T: UtahNum + PartialOrd
+{
+    /// A cache that keeps every column it sees.
+    pub fn new() -> StatsCache<T> {
+        StatsCache {
+            entries: Mutex::new(HashMap::new()),
+            hits: Mutex::new(0),
+            limit: None,
+        }
+    }
+
+    /// A cache that keeps at most `columns` columns.
+    pub fn with_limit(columns: usize) -> StatsCache<T> {
+        StatsCache { limit: Some(columns), ..StatsCache::new() }
+    }
+
+    /// The statistics of a column, in the order of the rows of `describe`, computed only if no
+    /// column with the same values was seen before.
+    pub fn column_stats(&self, values: ColumnView<T>) -> Result<Vec<T>> {
+        let key = fingerprint(values);
+        if let Some(candidates) = lock(&self.entries)?.get(&key) {
+            if let Some(&(_, ref stats)) = candidates.iter().find(|c| same_values(values, &c.0)) {
+                *lock(&self.hits)? += 1;
+                return Ok(stats.clone());
+            }
+        }
+        let stats: Vec<T> = DESCRIBE_STATS.iter()
+            .map(|&(_, agg)| reduce(values.iter(), agg))
+            .collect();
+        let mut entries = lock(&self.entries)?;
+        let stored: usize = entries.values().map(|c| c.len()).sum();
+        if self.limit.map_or(true, |n| stored < n) {
+            entries.entry(key)
+                .or_insert_with(Vec::new)
+                .push((values.iter().cloned().collect(), stats.clone()));
+        }
+        Ok(stats)
+    }
+
+    /// The number of columns whose statistics are cached.
+    pub fn len(&self) -> Result<usize> {
+        Ok(lock(&self.entries)?.values().map(|c| c.len()).sum())
+    }
+
+    pub fn is_empty(&self) -> Result<bool> {
+        Ok(self.len()? == 0)
+    }
+
+    /// The number of lookups answered from the cache.
+    pub fn hits(&self) -> Result<usize> {
+        Ok(*lock(&self.hits)?)
+    }
+
+    pub fn clear(&self) -> Result<()> {
+        lock(&self.entries)?.clear();
+        *lock(&self.hits)? = 0;
+        Ok(())
+    }
+}
@@ -4,8 +4,11 @@
 use util::types::*;
 use util::traits::*;
 use dataframe::*;
-use ndarray::Array;
+use ndarray::{Array, ArrayView1};
 use util::error::*;
+use util::helpers::{count, distinct, mode, reduce, windows_df};
+use util::rank::{median, sorted_positions};
+use std::cmp::Ordering;
 
 #[derive(Clone, Debug)]
 pub struct Sum<'a, I: 'a, T: 'a>
@@ -114,13 +117,13 @@ impl<'a, I, T> Max<'a, I, T>
 
 impl<'a, I, T> Iterator for Max<'a, I, T>
     where I: Iterator<Item = Window<'a, T>>,
-          T: UtahNum + Ord + 'a
+          T: UtahNum + PartialOrd + 'a
 {
     type Item = T;
     fn next(&mut self) -> Option<Self::Item> {
         match self.data.next() {
             None => return None,
-            Some((_, dat)) => return dat.iter().max().map(|x| x.clone()),
+            Some((_, dat)) => return extreme(&dat, Ordering::Greater),
         }
 
 
@@ -155,13 +158,13 @@ impl<'a, I, T> Min<'a, I, T>
 
 impl<'a, I, T> Iterator for Min<'a, I, T>
     where I: Iterator<Item = Window<'a, T>>,
-          T: UtahNum + Ord
+          T: UtahNum + PartialOrd
 {
     type Item = T;
     fn next(&mut self) -> Option<Self::Item> {
         match self.data.next() {
             None => return None,
-            Some((_, dat)) => return dat.iter().min().map(|x| x.clone()),
+            Some((_, dat)) => return extreme(&dat, Ordering::Less),
         }
 
 
@@ -248,7 +251,7 @@ impl<'a, I, T> ToDataFrame<'a, T, T> for Mean<'a, I, T>
 
 impl<'a, I, T> ToDataFrame<'a, T, T> for Max<'a, I, T>
     where I: Iterator<Item = Window<'a, T>>,
-          T: UtahNum + Ord
+          T: UtahNum + PartialOrd
 {
     fn as_df(self) -> Result<DataFrame<T>> {
         let other = self.other.clone();
@@ -299,7 +302,7 @@ impl<'a, I, T> ToDataFrame<'a, T, T> for Max<'a, I, T>
 
 impl<'a, I, T> ToDataFrame<'a, T, T> for Min<'a, I, T>
     where I: Iterator<Item = Window<'a, T>>,
-          T: UtahNum + Ord
+          T: UtahNum + PartialOrd
 {
     fn as_df(self) -> Result<DataFrame<T>> {
         let other = self.other.clone();
@@ -395,3 +398,715 @@ impl<'a, I, T> ToDataFrame<'a, T, T> for Sum<'a, I, T>
         Ok(Array::from_vec(c))
     }
 }
+
+
+/// The largest (`Ordering::Greater`) or smallest (`Ordering::Less`) value of a window. Values that
+/// are not ordered, such as `NAN`, are only returned if there is nothing else.
+fn extreme<T>(dat: &ArrayView1<T>, want: Ordering) -> Option<T>
+    where T: UtahNum + PartialOrd
+{
+    let best = dat.iter()
+        .filter(|x| x.partial_cmp(x).is_some())
+        .fold(None, |m: Option<&T>, x| match m {
+            Some(m) if x.partial_cmp(m) != Some(want) => Some(m),
+            _ => Some(x),
+        });
+    best.or_else(|| dat.iter().next()).cloned()
+}
+
+/// Lay out one aggregated value per window as a dataframe, along the specified `UtahAxis`.
+fn aggregate_df<T>(c: Vec<T>, other: Vec<String>, axis: UtahAxis) -> Result<DataFrame<T>>
+    where T: UtahNum
+{
+    let def = vec!["0".to_string()];
+    match axis {
+        UtahAxis::Row => {
+            Ok(DataFrame {
+                columns: def,
+                data: Array::from_shape_vec((other.len(), 1), c).unwrap(),
+                index: other,
+                index_sorted: false,
+            })
+        }
+        UtahAxis::Column => {
+            Ok(DataFrame {
+                columns: other,
+                data: Array::from_shape_vec((1, c.len()), c).unwrap(),
+                index: def,
+                index_sorted: false,
+            })
+        }
+    }
+}
+
+/// The non-empty values of a window, in ascending order.
+fn sorted_values<T>(dat: &ArrayView1<T>) -> Vec<T>
+    where T: UtahNum + PartialOrd
+{
+    let values: Vec<T> = dat.iter().cloned().collect();
+    sorted_positions(&values[..]).into_iter().map(|p| values[p].clone()).collect()
+}
+
+/// The number of values to cut from each end of `n` sorted values.
+fn trim_count(n: usize, frac: f64) -> usize {
+    let k = (frac.max(0.) * n as f64).floor() as usize;
+    if 2 * k >= n { n.saturating_sub(1) / 2 } else { k }
+}
+
+/// The mean of `values`, or empty if there are none.
+fn mean_of<T>(values: &[T]) -> T
+    where T: UtahNum
+{
+    if values.is_empty() {
+        return T::empty();
+    }
+    let size: T = count(values.len());
+    values.iter().fold(T::zero(), |acc, x| acc + x.clone()) / size
+}
+
+#[derive(Clone, Debug)]
+pub struct TrimmedMean<'a, I: 'a, T: 'a>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum
+{
+    data: I,
+    frac: f64,
+    other: Vec<String>,
+    axis: UtahAxis,
+}
+
+impl<'a, I, T> TrimmedMean<'a, I, T>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum + 'a
+{
+    pub fn new(df: I, frac: f64, other: Vec<String>, axis: UtahAxis) -> TrimmedMean<'a, I, T> {
+        TrimmedMean {
+            data: df,
+            frac: frac,
+            other: other,
+            axis: axis,
+        }
+    }
+}
+
+impl<'a, I, T> Iterator for TrimmedMean<'a, I, T>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum + PartialOrd + 'a
+{
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.data.next() {
+            None => None,
+            Some((_, dat)) => {
+                let values = sorted_values(&dat);
+                let k = trim_count(values.len(), self.frac);
+                Some(mean_of(&values[k..values.len() - k]))
+            }
+        }
+    }
+}
+
+impl<'a, I, T> ToDataFrame<'a, T, T> for TrimmedMean<'a, I, T>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum + PartialOrd
+{
+    fn as_df(self) -> Result<DataFrame<T>> {
+        let other = self.other.clone();
+        let axis = self.axis.clone();
+        aggregate_df(self.collect(), other, axis)
+    }
+
+    fn as_matrix(self) -> Result<Matrix<T>> {
+        Ok(self.as_df()?.data)
+    }
+
+    fn as_array(self) -> Result<Row<T>> {
+        let c: Vec<_> = self.collect();
+        Ok(Array::from_vec(c))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct WinsorizedMean<'a, I: 'a, T: 'a>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum
+{
+    data: I,
+    frac: f64,
+    other: Vec<String>,
+    axis: UtahAxis,
+}
+
+impl<'a, I, T> WinsorizedMean<'a, I, T>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum + 'a
+{
+    pub fn new(df: I, frac: f64, other: Vec<String>, axis: UtahAxis) -> WinsorizedMean<'a, I, T> {
+        WinsorizedMean {
+            data: df,
+            frac: frac,
+            other: other,
+            axis: axis,
+        }
+    }
+}
+
+impl<'a, I, T> Iterator for WinsorizedMean<'a, I, T>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum + PartialOrd + 'a
+{
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.data.next() {
+            None => None,
+            Some((_, dat)) => {
+                let mut values = sorted_values(&dat);
+                let n = values.len();
+                let k = trim_count(n, self.frac);
+                if k > 0 {
+                    let (lo, hi) = (values[k].clone(), values[n - 1 - k].clone());
+                    for x in values[..k].iter_mut() {
+                        *x = lo.clone();
+                    }
+                    for x in values[n - k..].iter_mut() {
+                        *x = hi.clone();
+                    }
+                }
+                Some(mean_of(&values[..]))
+            }
+        }
+    }
+}
+
+impl<'a, I, T> ToDataFrame<'a, T, T> for WinsorizedMean<'a, I, T>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum + PartialOrd
+{
+    fn as_df(self) -> Result<DataFrame<T>> {
+        let other = self.other.clone();
+        let axis = self.axis.clone();
+        aggregate_df(self.collect(), other, axis)
+    }
+
+    fn as_matrix(self) -> Result<Matrix<T>> {
+        Ok(self.as_df()?.data)
+    }
+
+    fn as_array(self) -> Result<Row<T>> {
+        let c: Vec<_> = self.collect();
+        Ok(Array::from_vec(c))
+    }
+}
+
+/// A running accumulation along each window. Empty entries stay empty and are skipped.
+#[derive(Clone, Debug)]
+pub struct Cumulative<'a, I: 'a, T: 'a>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum
+{
+    data: I,
+    op: ArithOp,
+    other: Vec<String>,
+    axis: UtahAxis,
+}
+
+impl<'a, I, T> Cumulative<'a, I, T>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum + 'a
+{
+    /// Accumulate with `op`, which should be `ArithOp::Add` or `ArithOp::Mul`.
+    pub fn new(df: I, op: ArithOp, other: Vec<String>, axis: UtahAxis) -> Cumulative<'a, I, T> {
+        Cumulative {
+            data: df,
+            op: op,
+            other: other,
+            axis: axis,
+        }
+    }
+}
+
+impl<'a, I, T> Iterator for Cumulative<'a, I, T>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum + 'a
+{
+    type Item = (String, Row<T>);
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.data.next() {
+            None => None,
+            Some((name, dat)) => {
+                let op = self.op;
+                let mut acc = match op {
+                    ArithOp::Mul | ArithOp::Div => T::one(),
+                    ArithOp::Add | ArithOp::Sub => T::zero(),
+                };
+                let v: Vec<T> = dat.iter()
+                    .map(|x| if x.is_empty() {
+                        x.clone()
+                    } else {
+                        acc = op.apply(acc.clone(), x.clone());
+                        acc.clone()
+                    })
+                    .collect();
+                Some((name, Array::from_vec(v)))
+            }
+        }
+    }
+}
+
+impl<'a, I, T> ToDataFrame<'a, (String, Row<T>), T> for Cumulative<'a, I, T>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum
+{
+    fn as_df(self) -> Result<DataFrame<T>> {
+        let other = self.other.clone();
+        let axis = self.axis.clone();
+        Ok(windows_df(self, other, axis))
+    }
+
+    fn as_matrix(self) -> Result<Matrix<T>> {
+        Ok(self.as_df()?.data)
+    }
+
+    fn as_array(self) -> Result<Row<T>> {
+        let mut c = Vec::new();
+        for (_, j) in self {
+            c.extend(j.iter().cloned());
+        }
+        Ok(Array::from_vec(c))
+    }
+}
+
+/// Reduce each window with an `Agg`.
+#[derive(Clone, Debug)]
+pub struct Reduce<'a, I: 'a, T: 'a>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum
+{
+    data: I,
+    agg: Agg,
+    other: Vec<String>,
+    axis: UtahAxis,
+}
+
+impl<'a, I, T> Reduce<'a, I, T>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum + 'a
+{
+    pub fn new(df: I, agg: Agg, other: Vec<String>, axis: UtahAxis) -> Reduce<'a, I, T> {
+        Reduce {
+            data: df,
+            agg: agg,
+            other: other,
+            axis: axis,
+        }
+    }
+}
+
+impl<'a, I, T> Iterator for Reduce<'a, I, T>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum + PartialOrd + 'a
+{
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.data.next() {
+            None => None,
+            Some((_, dat)) => Some(reduce(dat.iter(), self.agg)),
+        }
+    }
+}
+
+impl<'a, I, T> ToDataFrame<'a, T, T> for Reduce<'a, I, T>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum + PartialOrd
+{
+    fn as_df(self) -> Result<DataFrame<T>> {
+        let other = self.other.clone();
+        let axis = self.axis.clone();
+        aggregate_df(self.collect(), other, axis)
+    }
+
+    fn as_matrix(self) -> Result<Matrix<T>> {
+        Ok(self.as_df()?.data)
+    }
+
+    fn as_array(self) -> Result<Row<T>> {
+        let c: Vec<_> = self.collect();
+        Ok(Array::from_vec(c))
+    }
+}
+
+/// The median of each window, found by selection rather than a full sort. Empty values are
+/// skipped.
+#[derive(Clone, Debug)]
+pub struct Median<'a, I: 'a, T: 'a>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum
+{
+    data: I,
+    other: Vec<String>,
+    axis: UtahAxis,
+}
+
+impl<'a, I, T> Median<'a, I, T>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum + 'a
+{
+    pub fn new(df: I, other: Vec<String>, axis: UtahAxis) -> Median<'a, I, T> {
+        Median {
+            data: df,
+            other: other,
+            axis: axis,
+        }
+    }
+}
+
+impl<'a, I, T> Iterator for Median<'a, I, T>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum + PartialOrd + 'a
+{
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.data.next() {
+            None => None,
+            Some((_, dat)) => {
+                let values: Vec<T> = dat.iter().cloned().collect();
+                Some(median(&values[..]))
+            }
+        }
+    }
+}
+
+impl<'a, I, T> ToDataFrame<'a, T, T> for Median<'a, I, T>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum + PartialOrd
+{
+    fn as_df(self) -> Result<DataFrame<T>> {
+        let other = self.other.clone();
+        let axis = self.axis.clone();
+        aggregate_df(self.collect(), other, axis)
+    }
+
+    fn as_matrix(self) -> Result<Matrix<T>> {
+        Ok(self.as_df()?.data)
+    }
+
+    fn as_array(self) -> Result<Row<T>> {
+        let c: Vec<_> = self.collect();
+        Ok(Array::from_vec(c))
+    }
+}
+
+/// The most frequent value of each window. Empty values are skipped, and ties go to the value
+/// seen first.
+#[derive(Clone, Debug)]
+pub struct Mode<'a, I: 'a, T: 'a>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum
+{
+    data: I,
+    other: Vec<String>,
+    axis: UtahAxis,
+}
+
+impl<'a, I, T> Mode<'a, I, T>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum + 'a
+{
+    pub fn new(df: I, other: Vec<String>, axis: UtahAxis) -> Mode<'a, I, T> {
+        Mode {
+            data: df,
+            other: other,
+            axis: axis,
+        }
+    }
+}
+
+impl<'a, I, T> Iterator for Mode<'a, I, T>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum + 'a
+{
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.data.next() {
+            None => None,
+            Some((_, dat)) => Some(mode(dat.iter())),
+        }
+    }
+}
+
+impl<'a, I, T> ToDataFrame<'a, T, T> for Mode<'a, I, T>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum
+{
+    fn as_df(self) -> Result<DataFrame<T>> {
+        let other = self.other.clone();
+        let axis = self.axis.clone();
+        aggregate_df(self.collect(), other, axis)
+    }
+
+    fn as_matrix(self) -> Result<Matrix<T>> {
+        Ok(self.as_df()?.data)
+    }
+
+    fn as_array(self) -> Result<Row<T>> {
+        let c: Vec<_> = self.collect();
+        Ok(Array::from_vec(c))
+    }
+}
+
+/// The geometric mean of each window. Empty values are skipped.
+#[derive(Clone, Debug)]
+pub struct GMean<'a, I: 'a, T: 'a>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum
+{
+    data: I,
+    other: Vec<String>,
+    axis: UtahAxis,
+}
+
+impl<'a, I, T> GMean<'a, I, T>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum + 'a
+{
+    pub fn new(df: I, other: Vec<String>, axis: UtahAxis) -> GMean<'a, I, T> {
+        GMean {
+            data: df,
+            other: other,
+            axis: axis,
+        }
+    }
+}
+
+impl<'a, I, T> Iterator for GMean<'a, I, T>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum + Into<f64> + From<f64> + 'a
+{
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.data.next() {
+            None => None,
+            Some((_, dat)) => {
+                let logs: Vec<f64> = dat.iter()
+                    .filter(|x| !x.is_empty())
+                    .map(|x| x.clone().into().ln())
+                    .collect();
+                if logs.is_empty() {
+                    return Some(T::empty());
+                }
+                let n = logs.len() as f64;
+                Some(T::from((logs.into_iter().sum::<f64>() / n).exp()))
+            }
+        }
+    }
+}
+
+impl<'a, I, T> ToDataFrame<'a, T, T> for GMean<'a, I, T>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum + Into<f64> + From<f64>
+{
+    fn as_df(self) -> Result<DataFrame<T>> {
+        let other = self.other.clone();
+        let axis = self.axis.clone();
+        aggregate_df(self.collect(), other, axis)
+    }
+
+    fn as_matrix(self) -> Result<Matrix<T>> {
+        Ok(self.as_df()?.data)
+    }
+
+    fn as_array(self) -> Result<Row<T>> {
+        let c: Vec<_> = self.collect();
+        Ok(Array::from_vec(c))
+    }
+}
+
+/// The harmonic mean of each window. Empty values are skipped.
+#[derive(Clone, Debug)]
+pub struct HMean<'a, I: 'a, T: 'a>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum
+{
+    data: I,
+    other: Vec<String>,
+    axis: UtahAxis,
+}
+
+impl<'a, I, T> HMean<'a, I, T>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum + 'a
+{
+    pub fn new(df: I, other: Vec<String>, axis: UtahAxis) -> HMean<'a, I, T> {
+        HMean {
+            data: df,
+            other: other,
+            axis: axis,
+        }
+    }
+}
+
+impl<'a, I, T> Iterator for HMean<'a, I, T>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum + Into<f64> + From<f64> + 'a
+{
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.data.next() {
+            None => None,
+            Some((_, dat)) => {
+                // Reciprocals are taken in f64, since integer division would truncate them to zero.
+                let (inverse, n) = dat.iter()
+                    .filter(|x| !x.is_empty())
+                    .fold((0., 0), |(acc, n), x| (acc + 1. / x.clone().into(), n + 1));
+                if n == 0 { Some(T::empty()) } else { Some(T::from(n as f64 / inverse)) }
+            }
+        }
+    }
+}
+
+impl<'a, I, T> ToDataFrame<'a, T, T> for HMean<'a, I, T>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum + Into<f64> + From<f64>
+{
+    fn as_df(self) -> Result<DataFrame<T>> {
+        let other = self.other.clone();
+        let axis = self.axis.clone();
+        aggregate_df(self.collect(), other, axis)
+    }
+
+    fn as_matrix(self) -> Result<Matrix<T>> {
+        Ok(self.as_df()?.data)
+    }
+
+    fn as_array(self) -> Result<Row<T>> {
+        let c: Vec<_> = self.collect();
+        Ok(Array::from_vec(c))
+    }
+}
+
+/// The number of distinct non-empty values of each window, optionally treating values no
+/// further apart than a tolerance as equal.
+#[derive(Clone, Debug)]
+pub struct NUnique<'a, I: 'a, T: 'a>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum
+{
+    data: I,
+    tolerance: Option<T>,
+    other: Vec<String>,
+    axis: UtahAxis,
+}
+
+impl<'a, I, T> NUnique<'a, I, T>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum + 'a
+{
+    pub fn new(df: I,
+               tolerance: Option<T>,
+               other: Vec<String>,
+               axis: UtahAxis)
+               -> NUnique<'a, I, T> {
+        NUnique {
+            data: df,
+            tolerance: tolerance,
+            other: other,
+            axis: axis,
+        }
+    }
+}
+
+impl<'a, I, T> Iterator for NUnique<'a, I, T>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum + PartialOrd + 'a
+{
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.data.next() {
+            None => None,
+            Some((_, dat)) => Some(count(distinct(dat.iter(), self.tolerance.as_ref()))),
+        }
+    }
+}
+
+impl<'a, I, T> ToDataFrame<'a, T, T> for NUnique<'a, I, T>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum + PartialOrd
+{
+    fn as_df(self) -> Result<DataFrame<T>> {
+        let other = self.other.clone();
+        let axis = self.axis.clone();
+        aggregate_df(self.collect(), other, axis)
+    }
+
+    fn as_matrix(self) -> Result<Matrix<T>> {
+        Ok(self.as_df()?.data)
+    }
+
+    fn as_array(self) -> Result<Row<T>> {
+        let c: Vec<_> = self.collect();
+        Ok(Array::from_vec(c))
+    }
+}
+
+/// Each window reduced to a single value by a user-supplied function.
+#[derive(Clone)]
+pub struct AggWith<'a, I: 'a, T: 'a, F>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum,
+          F: Fn(ArrayView1<'a, T>) -> T
+{
+    data: I,
+    f: F,
+    other: Vec<String>,
+    axis: UtahAxis,
+}
+
+impl<'a, I, T, F> AggWith<'a, I, T, F>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum + 'a,
+          F: Fn(ArrayView1<'a, T>) -> T
+{
+    pub fn new(df: I, f: F, other: Vec<String>, axis: UtahAxis) -> AggWith<'a, I, T, F> {
+        AggWith {
+            data: df,
+            f: f,
+            other: other,
+            axis: axis,
+        }
+    }
+}
+
+impl<'a, I, T, F> Iterator for AggWith<'a, I, T, F>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum + 'a,
+          F: Fn(ArrayView1<'a, T>) -> T
+{
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.data.next() {
+            None => None,
+            Some((_, dat)) => Some((self.f)(dat)),
+        }
+    }
+}
+
+impl<'a, I, T, F> ToDataFrame<'a, T, T> for AggWith<'a, I, T, F>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum,
+          F: Fn(ArrayView1<'a, T>) -> T
+{
+    fn as_df(self) -> Result<DataFrame<T>> {
+        let other = self.other.clone();
+        let axis = self.axis.clone();
+        aggregate_df(self.collect(), other, axis)
+    }
+
+    fn as_matrix(self) -> Result<Matrix<T>> {
+        Ok(self.as_df()?.data)
+    }
+
+    fn as_array(self) -> Result<Row<T>> {
+        let c: Vec<_> = self.collect();
+        Ok(Array::from_vec(c))
+    }
+}
@@ -0,0 +1,307 @@
+//! Utah groupby combinators.
+
+use util::types::*;
+use util::traits::*;
+use util::helpers::{take, take_labels, reduce};
+use util::rank;
+use ndarray::Array;
+use mixedtypes::InnerType;
+use util::multiindex::{escape_level, join_levels, LEVEL_SEPARATOR};
+use dataframe::*;
+use util::error::*;
+
+/// The rows of a dataframe, split into groups of equal key values.
+#[derive(Clone, Debug)]
+pub struct GroupBy<'a, T: 'a>
+    where T: UtahNum
+{
+    pub df: &'a DataFrame<T>,
+    /// The first key column.
+    pub key: String,
+    /// The value of the first key column in each group.
+    pub keys: Vec<T>,
+    pub groups: Vec<Vec<usize>>,
+    /// All the key columns.
+    pub by: Vec<String>,
+    /// The label of each group: its key values, escaped and joined by `LEVEL_SEPARATOR` when
+    /// there are several key columns, so that the labels form a `MultiIndex`.
+    pub labels: Vec<String>,
+}
+
+impl<'a, T> GroupBy<'a, T>
+    where T: UtahNum
+{
+    pub fn new(df: &'a DataFrame<T>,
+               key: String,
+               groups: Vec<(T, Vec<usize>)>)
+               -> GroupBy<'a, T> {
+        let (keys, groups): (Vec<T>, _) = groups.into_iter().unzip();
+        GroupBy {
+            df: df,
+            by: vec![key.clone()],
+            key: key,
+            labels: keys.iter().map(|k| escape_level(&format!("{:?}", k))).collect(),
+            keys: keys,
+            groups: groups,
+        }
+    }
+
+    /// Groups over several key columns, given the key values of each group.
+    pub fn multi(df: &'a DataFrame<T>,
+                 by: Vec<String>,
+                 groups: Vec<(Vec<T>, Vec<usize>)>)
+                 -> GroupBy<'a, T> {
+        let labels = groups.iter()
+            .map(|&(ref k, _)| {
+                join_levels(&k.iter().map(|x| format!("{:?}", x)).collect::<Vec<_>>())
+            })
+            .collect();
+        let (keys, groups) = groups.into_iter()
+            .map(|(k, g)| (k.into_iter().next().unwrap_or_else(T::empty), g))
+            .unzip();
+        GroupBy {
+            df: df,
+            key: by.first().cloned().unwrap_or_default(),
+            by: by,
+            keys: keys,
+            groups: groups,
+            labels: labels,
+        }
+    }
+
+    /// Groups that are not defined by key columns, such as the time buckets of `resample`, with
+    /// the label of each group.
+    pub fn labelled(df: &'a DataFrame<T>, groups: Vec<(String, Vec<usize>)>) -> GroupBy<'a, T> {
+        let (labels, groups) = groups.into_iter().unzip();
+        GroupBy {
+            df: df,
+            key: String::new(),
+            keys: Vec::new(),
+            groups: groups,
+            by: Vec::new(),
+            labels: labels,
+        }
+    }
+
+    /// The number of groups.
+    pub fn ngroups(&self) -> usize {
+        self.groups.len()
+    }
+
+    /// Keep the rows selected from each group by `f`, in their original order.
+    fn filter_rows<F>(&self, f: F) -> DataFrame<T>
+        where F: Fn(&[usize]) -> &[usize]
+    {
+        let mut positions: Vec<usize> = self.groups
+            .iter()
+            .flat_map(|g| f(&g[..]).to_vec())
+            .collect();
+        positions.sort();
+        DataFrame {
+            columns: self.df.columns.clone(),
+            data: take(&self.df.data, UtahAxis::Row, &positions[..]),
+            index: take_labels(&self.df.index[..], &positions[..]),
+            index_sorted: false,
+        }
+    }
+
+    /// Apply `f` to the rows of each group, as a dataframe, and stack the results. Each row of
+    /// the result is labelled by its group label and its own label, as a `MultiIndex`. The
+    /// results must all have the same columns.
+    pub fn apply<F, R>(&self, mut f: F) -> Result<DataFrame<T>>
+        where F: FnMut(&DataFrame<T>) -> R,
+              R: Into<DataFrame<T>>
+    {
+        let mut columns: Option<Vec<String>> = None;
+        let mut index = Vec::new();
+        let mut c = Vec::new();
+        for (label, g) in self.labels.iter().zip(self.groups.iter()) {
+            let group = DataFrame {
+                columns: self.df.columns.clone(),
+                data: take(&self.df.data, UtahAxis::Row, &g[..]),
+                index: take_labels(&self.df.index[..], &g[..]),
+                index_sorted: false,
+            };
+            let res: DataFrame<T> = f(&group).into();
+            match columns {
+                Some(ref cols) if *cols != res.columns => {
+                    return Err(ErrorKind::MisalignedLabels(format!("{:?} and {:?}",
+                                                                   cols,
+                                                                   res.columns))
+                        .into())
+                }
+                Some(_) => {}
+                None => columns = Some(res.columns.clone()),
+            }
+            index.extend(res.index
+                .iter()
+                .map(|i| format!("{}{}{}", label, LEVEL_SEPARATOR, escape_level(i))));
+            c.extend(res.data.iter().cloned());
+        }
+        let columns = columns.unwrap_or_else(|| self.df.columns.clone());
+        Ok(DataFrame {
+            data: Array::from_shape_vec((index.len(), columns.len()), c).unwrap(),
+            columns: columns,
+            index: index,
+            index_sorted: false,
+        })
+    }
+
+    /// The first `n` rows of each group.
+    pub fn head(&self, n: usize) -> DataFrame<T> {
+        self.filter_rows(|g| &g[..n.min(g.len())])
+    }
+
+    /// The last `n` rows of each group.
+    pub fn tail(&self, n: usize) -> DataFrame<T> {
+        self.filter_rows(|g| &g[g.len() - n.min(g.len())..])
+    }
+
+    /// Reduce the values of each column within each group with `f`. The result has one row per
+    /// group, labelled by its key, and every column but the keys.
+    fn reduce_groups<F>(&self, f: F) -> DataFrame<T>
+        where F: Fn(&[T]) -> T
+    {
+        let columns: Vec<usize> = (0..self.df.columns.len())
+            .filter(|&i| !self.by.contains(&self.df.columns[i]))
+            .collect();
+        let mut c = Vec::with_capacity(self.groups.len() * columns.len());
+        let mut values = Vec::new();
+        for g in &self.groups {
+            for &i in &columns {
+                let column = self.df.data.column(i);
+                values.clear();
+                values.extend(g.iter().map(|&r| column[r].clone()));
+                c.push(f(&values[..]));
+            }
+        }
+        DataFrame {
+            columns: take_labels(&self.df.columns[..], &columns[..]),
+            data: Array::from_shape_vec((self.groups.len(), columns.len()), c).unwrap(),
+            index: self.labels.clone(),
+            index_sorted: false,
+        }
+    }
+
+    /// The median of each column within each group, ignoring empty values.
+    pub fn median(&self) -> DataFrame<T>
+        where T: PartialOrd
+    {
+        self.reduce_groups(|v| rank::median(v))
+    }
+
+    /// The `q`-th quantile of each column within each group, ignoring empty values and
+    /// interpolating linearly between ranks.
+    pub fn quantile(&self, q: f64) -> DataFrame<T>
+        where T: PartialOrd + From<f64>
+    {
+        self.reduce_groups(|v| rank::quantile(v, q))
+    }
+
+    /// Reduce each column within each group with `agg`.
+    pub fn agg(&self, agg: Agg) -> DataFrame<T>
+        where T: PartialOrd
+    {
+        self.reduce_groups(|v| reduce(v.iter(), agg))
+    }
+
+    /// Reduce each column within each group with `f`, which sees the group's values in row
+    /// order, empty ones included.
+    pub fn agg_with<F>(&self, f: F) -> DataFrame<T>
+        where F: Fn(&[T]) -> T
+    {
+        self.reduce_groups(f)
+    }
+
+    /// The sum of each column within each group, ignoring empty values.
+    pub fn sum(&self) -> DataFrame<T>
+        where T: PartialOrd
+    {
+        self.agg(Agg::Sum)
+    }
+
+    /// The mean of each column within each group, ignoring empty values.
+    pub fn mean(&self) -> DataFrame<T>
+        where T: PartialOrd
+    {
+        self.agg(Agg::Mean)
+    }
+
+    /// The number of non-empty values of each column within each group.
+    pub fn count(&self) -> DataFrame<T>
+        where T: PartialOrd
+    {
+        self.agg(Agg::Count)
+    }
+
+    /// The maximum of each column within each group, ignoring empty values.
+    pub fn max(&self) -> DataFrame<T>
+        where T: PartialOrd
+    {
+        self.agg(Agg::Max)
+    }
+
+    /// The minimum of each column within each group, ignoring empty values.
+    pub fn min(&self) -> DataFrame<T>
+        where T: PartialOrd
+    {
+        self.agg(Agg::Min)
+    }
+
+    /// The last non-empty value of each column within each group.
+    pub fn last(&self) -> DataFrame<T>
+        where T: PartialOrd
+    {
+        self.agg(Agg::LastValid)
+    }
+
+    /// The first, highest, lowest and last non-empty values of each column within each group.
+    /// Each column becomes four, labelled `open`, `high`, `low` and `close` under its name, as a
+    /// `MultiIndex`.
+    pub fn ohlc(&self) -> DataFrame<T>
+        where T: PartialOrd
+    {
+        let stats = [("open", Agg::FirstValid),
+                     ("high", Agg::Max),
+                     ("low", Agg::Min),
+                     ("close", Agg::LastValid)];
+        let frames: Vec<DataFrame<T>> = stats.iter().map(|&(_, agg)| self.agg(agg)).collect();
+        let names = &frames[0].columns;
+        let mut columns = Vec::with_capacity(names.len() * stats.len());
+        for name in names {
+            columns.extend(stats.iter().map(|&(s, _)| join_levels(&[&name[..], s])));
+        }
+        let mut c = Vec::with_capacity(self.groups.len() * columns.len());
+        for r in 0..self.groups.len() {
+            for i in 0..names.len() {
+                c.extend(frames.iter().map(|f| f.data[[r, i]].clone()));
+            }
+        }
+        DataFrame {
+            data: Array::from_shape_vec((self.groups.len(), columns.len()), c).unwrap(),
+            columns: columns,
+            index: self.labels.clone(),
+            index_sorted: false,
+        }
+    }
+}
+
+impl<'a> GroupBy<'a, InnerType> {
+    /// Join the string values of each column within each group with `separator`, in row order.
+    /// Other values are skipped, and groups with no strings get an empty value.
+    pub fn str_join(&self, separator: &str) -> DataFrame<InnerType> {
+        self.reduce_groups(|v| {
+            let strings: Vec<&str> = v.iter()
+                .filter_map(|x| match *x {
+                    InnerType::Str(ref s) => Some(&s[..]),
+                    _ => None,
+                })
+                .collect();
+            if strings.is_empty() {
+                InnerType::Empty
+            } else {
+                InnerType::Str(strings.join(separator))
+            }
+        })
+    }
+}
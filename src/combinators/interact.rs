@@ -4,11 +4,17 @@ use util::types::*;
 use std::iter::Iterator;
 use std::iter::repeat;
 use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::hash::Hash;
+use std::cmp::Ordering;
 use ndarray::{ArrayView1, Array};
 use dataframe::*;
 use std::iter::Chain;
 use util::error::*;
 use util::traits::*;
+use util::helpers::{suffix_labels, windows_df};
+use util::keys::RowKey;
+use util::rank::compare_labels;
 
 #[derive(Clone, Debug)]
 pub struct Concat<'a, I, T: 'a>
@@ -17,6 +23,8 @@ pub struct Concat<'a, I, T: 'a>
     pub concat_data: I,
     pub concat_other: Vec<String>,
     pub axis: UtahAxis,
+    pub left_len: usize,
+    pub suffixes: Option<(String, String)>,
 }
 
 
@@ -29,16 +37,26 @@ impl<'a, I, T> Concat<'a, I, T>
                right_df: I,
                left_other: Vec<String>,
                axis: UtahAxis)
-               -> Concat<'a, Chain<I, I>, T> {
-
+               -> Concat<'a, Chain<I, I>, T>
+        where I: Clone
+    {
+        let left_len = left_df.clone().count();
         let it = left_df.chain(right_df);
 
         Concat {
             concat_data: it,
             concat_other: left_other,
             axis: axis,
+            left_len: left_len,
+            suffixes: None,
         }
     }
+
+    /// Append `left` and `right` to the labels that appear on both sides.
+    pub fn suffixes(mut self, left: &str, right: &str) -> Self {
+        self.suffixes = Some((left.to_string(), right.to_string()));
+        self
+    }
 }
 
 impl<'a, I, T> Iterator for Concat<'a, I, T>
@@ -50,175 +68,468 @@ impl<'a, I, T> Iterator for Concat<'a, I, T>
     }
 }
 
-#[derive(Clone)]
-pub struct InnerJoin<'a, L, T>
-    where L: Iterator<Item = Window<'a, T>> + Clone,
-          T: UtahNum + 'a
-{
-    pub left: L,
-    pub right: HashMap<String, ArrayView1<'a, T>>,
-    pub left_columns: Vec<String>,
-    pub right_columns: Vec<String>,
+/// The right rows matching each left row of a join, in right order: those of left row `i` are
+/// `targets[offsets[i]..offsets[i + 1]]`.
+#[derive(Clone, Debug)]
+struct Matches {
+    offsets: Vec<usize>,
+    targets: Vec<usize>,
 }
 
-impl<'a, L, T> InnerJoin<'a, L, T>
-    where L: Iterator<Item = Window<'a, T>> + Clone,
-          T: UtahNum + 'a
-{
-    pub fn new<RI>(left: L,
-                   right: RI,
-                   left_columns: Vec<String>,
-                   right_columns: Vec<String>)
-                   -> Self
-        where RI: Iterator<Item = Window<'a, T>>
-    {
-        InnerJoin {
-            left: left,
-            right: right.collect(),
-            left_columns: left_columns,
-            right_columns: right_columns,
+impl Matches {
+    fn with_capacity(left_len: usize) -> Matches {
+        let mut offsets = Vec::with_capacity(left_len + 1);
+        offsets.push(0);
+        Matches {
+            offsets: offsets,
+            targets: Vec::new(),
         }
     }
-}
 
+    /// Record the matches of the next left row.
+    fn push<I: IntoIterator<Item = usize>>(&mut self, targets: I) {
+        self.targets.extend(targets);
+        self.offsets.push(self.targets.len());
+    }
 
+    /// Group `(left, right)` pairs, found in right order, by their left row.
+    fn from_pairs(left_len: usize, pairs: &[(usize, usize)]) -> Matches {
+        let mut offsets = vec![0; left_len + 1];
+        for &(i, _) in pairs {
+            offsets[i + 1] += 1;
+        }
+        for i in 0..left_len {
+            offsets[i + 1] += offsets[i];
+        }
+        let mut next = offsets.clone();
+        let mut targets = vec![0; pairs.len()];
+        for &(i, j) in pairs {
+            targets[next[i]] = j;
+            next[i] += 1;
+        }
+        Matches {
+            offsets: offsets,
+            targets: targets,
+        }
+    }
 
-impl<'a, L, T> Iterator for InnerJoin<'a, L, T>
-    where L: Iterator<Item = Window<'a, T>> + Clone,
-          T: UtahNum + 'a
-{
-    type Item = (String, ArrayView1<'a, T>, ArrayView1<'a, T>);
+    /// The number of left rows.
+    fn left_len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+}
 
-    fn next(&mut self) -> Option<Self::Item> {
-        loop {
-            match self.left.next() {
-                Some((k, lv)) => {
-                    let rv = self.right.get(&k);
-
-                    match rv {
-                        Some(v) => {
-                            return Some((k, lv, *v));
-                        }
-                        None => continue,
-                    }
+/// Marks the end of a chain of positions.
+const NO_POSITION: usize = ::std::usize::MAX;
+
+/// Chain the positions of equal keys: the first position of each key, and for each position the
+/// next one with the same key, or `NO_POSITION`. Missing keys are left out.
+fn chain<K: Hash + Eq>(keys: Vec<Option<K>>) -> (HashMap<K, usize>, Vec<usize>) {
+    let mut first = HashMap::with_capacity(keys.len());
+    let mut next = vec![NO_POSITION; keys.len()];
+    for (i, k) in keys.into_iter().enumerate().rev() {
+        if let Some(k) = k {
+            match first.entry(k) {
+                Entry::Occupied(mut e) => next[i] = e.insert(i),
+                Entry::Vacant(e) => {
+                    e.insert(i);
                 }
-                None => return None,
             }
-
         }
     }
+    (first, next)
 }
 
-#[derive(Clone)]
-pub struct OuterJoin<'a, L, T>
-    where L: Iterator<Item = Window<'a, T>> + Clone,
-          T: UtahNum + 'a
-{
-    left: L,
-    right: HashMap<String, ArrayView1<'a, T>>,
-    left_columns: Vec<String>,
-    right_columns: Vec<String>,
+/// The positions of a chain, in order.
+struct Walk<'c> {
+    at: usize,
+    next: &'c [usize],
 }
 
+impl<'c> Iterator for Walk<'c> {
+    type Item = usize;
+    fn next(&mut self) -> Option<usize> {
+        if self.at == NO_POSITION {
+            return None;
+        }
+        let i = self.at;
+        self.at = self.next[i];
+        Some(i)
+    }
+}
 
-impl<'a, L, T> OuterJoin<'a, L, T>
-    where L: Iterator<Item = Window<'a, T>> + Clone,
-          T: UtahNum + 'a
-{
-    pub fn new<RI>(left: L,
-                   right: RI,
-                   left_columns: Vec<String>,
-                   right_columns: Vec<String>)
-                   -> Self
-        where RI: Iterator<Item = Window<'a, T>>
-    {
-        OuterJoin {
-            left: left,
-            right: right.collect(),
-            left_columns: left_columns,
-            right_columns: right_columns,
+fn walk<'c>(start: Option<&usize>, next: &'c [usize]) -> Walk<'c> {
+    Walk {
+        at: start.cloned().unwrap_or(NO_POSITION),
+        next: next,
+    }
+}
+
+/// Match keys by hashing the smaller side and probing it with the other. Missing keys match
+/// nothing.
+fn hash_matches<K: Hash + Eq>(left: Vec<Option<K>>, right: Vec<Option<K>>) -> Matches {
+    let left_len = left.len();
+    if left_len >= right.len() {
+        let (first, next) = chain(right);
+        let mut matches = Matches::with_capacity(left_len);
+        for k in &left {
+            matches.push(walk(k.as_ref().and_then(|k| first.get(k)), &next[..]));
         }
+        return matches;
     }
+    let (first, next) = chain(left);
+    let mut pairs = Vec::new();
+    for (j, k) in right.iter().enumerate() {
+        pairs.extend(walk(k.as_ref().and_then(|k| first.get(k)), &next[..]).map(|i| (i, j)));
+    }
+    Matches::from_pairs(left_len, &pairs[..])
 }
 
+/// Match labels sorted by `compare_labels` by merging them, in one pass over each side.
+fn merge_labels(left: &[String], right: &[String]) -> Matches {
+    let cmp = |a: &String, b: &String| compare_labels(a, b);
+    let mut matches = Matches::with_capacity(left.len());
+    let mut start = 0;
+    for l in left {
+        while start < right.len() && cmp(&right[start], l) == Ordering::Less {
+            start += 1;
+        }
+        // Labels like "1" and "1.0" compare equal without being the same label.
+        let run = right[start..].iter().take_while(|r| cmp(r, l) == Ordering::Equal);
+        matches.push(run.enumerate().filter(|&(_, r)| r == l).map(|(j, _)| start + j));
+    }
+    matches
+}
 
-impl<'a, L, T> Iterator for OuterJoin<'a, L, T>
-    where L: Iterator<Item = Window<'a, T>> + Clone,
-          T: UtahNum + 'a
+/// The keys of the rows of a frame: the values at `keys` (see `util::keys`), missing if any of
+/// them is empty.
+fn row_keys<'a, 'k, T>(df: &'a DataFrame<T>,
+                        keys: &'k [usize])
+                        -> Vec<Option<RowKey<'a, 'k, T>>>
+    where T: UtahNum
 {
-    type Item = (String, ArrayView1<'a, T>, Option<ArrayView1<'a, T>>);
+    df.data
+        .outer_iter()
+        .map(|row| {
+            if keys.iter().any(|&k| row[k].is_empty()) {
+                None
+            } else {
+                Some(RowKey {
+                    row: row,
+                    keys: keys,
+                })
+            }
+        })
+        .collect()
+}
 
-    fn next(&mut self) -> Option<Self::Item> {
+/// Find the matches of the rows of a join: on the labels, or on the values at `left_keys` and
+/// `right_keys` if there are any. Label joins of frames whose indices are both flagged sorted
+/// (see `DataFrame::index_sorted`) are merged; otherwise the smaller side is hashed. A left row
+/// matches every right row with the same key. Keys are borrowed from the frames, never copied.
+fn build<T>(left: &DataFrame<T>,
+            right: &DataFrame<T>,
+            left_keys: &[usize],
+            right_keys: &[usize])
+            -> Matches
+    where T: UtahNum
+{
+    if !left_keys.is_empty() {
+        return hash_matches(row_keys(left, left_keys), row_keys(right, right_keys));
+    }
+    if left.index_sorted && right.index_sorted {
+        return merge_labels(&left.index[..], &right.index[..]);
+    }
+    hash_matches(left.index.iter().map(|x| Some(&x[..])).collect(),
+                 right.index.iter().map(|x| Some(&x[..])).collect())
+}
 
-        match self.left.next() {
-            Some((k, lv)) => {
-                let rv = self.right.get(&k);
-                match rv {
-                    Some(v) => return Some((k, lv, Some(*v))),
-                    None => Some((k, lv, None)),
-                }
+/// The row positions of an inner join on labels, in the order `InnerJoin` emits the rows.
+pub fn inner_join_positions<T>(left: &DataFrame<T>, right: &DataFrame<T>) -> Vec<(usize, usize)>
+    where T: UtahNum
+{
+    let matches = build(left, right, &[], &[]);
+    let mut positions = Vec::with_capacity(matches.targets.len());
+    for (i, w) in matches.offsets.windows(2).enumerate() {
+        positions.extend(matches.targets[w[0]..w[1]].iter().map(|&j| (i, j)));
+    }
+    positions
+}
 
-            }
-            None => None,
+/// The row positions of a full outer join on labels: each left row with each of its matches, or
+/// alone if it has none, followed by the right rows no left row matched. Rows match as in
+/// `InnerJoin`, so a label repeated on both sides yields every pairing.
+pub fn full_join_positions<T>(left: &DataFrame<T>,
+                              right: &DataFrame<T>)
+                              -> Vec<(Option<usize>, Option<usize>)>
+    where T: UtahNum
+{
+    let matches = build(left, right, &[], &[]);
+    let mut paired = vec![false; right.index.len()];
+    let mut positions = Vec::with_capacity(matches.targets.len() + matches.offsets.len());
+    for (i, w) in matches.offsets.windows(2).enumerate() {
+        if w[0] == w[1] {
+            positions.push((Some(i), None));
+        }
+        for &j in &matches.targets[w[0]..w[1]] {
+            paired[j] = true;
+            positions.push((Some(i), Some(j)));
         }
+    }
+    positions.extend((0..paired.len()).filter(|&j| !paired[j]).map(|j| (None, Some(j))));
+    positions
+}
 
+/// Lay out joined rows as a dataframe, filling the right columns of unmatched rows with empty
+/// values. An `indicator` names an extra column and the values it takes for matched and
+/// unmatched rows. `capacity` is an upper bound on the number of rows.
+fn join_df<'a, I, T>(rows: I,
+                     left_columns: Vec<String>,
+                     right_columns: Vec<String>,
+                     suffixes: Option<(String, String)>,
+                     indicator: Option<(String, T, T)>,
+                     capacity: usize)
+                     -> DataFrame<T>
+    where I: Iterator<Item = (String, ArrayView1<'a, T>, Option<ArrayView1<'a, T>>)>,
+          T: UtahNum + 'a
+{
+    let width = left_columns.len() + right_columns.len() + indicator.iter().count();
+    let mut c = Vec::with_capacity(capacity * width);
+    let mut n = Vec::with_capacity(capacity);
+    for (i, j, k) in rows {
+        c.extend(j.iter().cloned());
+        let matched = k.is_some();
+        match k {
+            Some(z) => c.extend(z.iter().cloned()),
+            None => c.extend(repeat(T::empty()).take(right_columns.len())),
+        }
+        if let Some((_, ref both, ref unmatched)) = indicator {
+            c.push(if matched { both.clone() } else { unmatched.clone() });
+        }
+        n.push(i);
     }
+    let mut columns = match suffixes {
+        Some(ref s) => suffix_labels(&left_columns[..], &right_columns[..], s),
+        None => left_columns.into_iter().chain(right_columns.into_iter()).collect(),
+    };
+    columns.extend(indicator.map(|(name, _, _)| name));
+    DataFrame {
+        data: Array::from_shape_vec((n.len(), width), c).unwrap(),
+        columns: columns,
+        index: n,
+        index_sorted: false,
+    }
+}
+
+#[derive(Clone)]
+pub struct InnerJoin<'a, T>
+    where T: UtahNum + 'a
+{
+    pub left: &'a DataFrame<T>,
+    pub right: &'a DataFrame<T>,
+    matches: Matches,
+    position: usize,
+    cursor: usize,
+    suffixes: Option<(String, String)>,
 }
 
+impl<'a, T> InnerJoin<'a, T>
+    where T: UtahNum + 'a
+{
+    pub fn new(left: &'a DataFrame<T>, right: &'a DataFrame<T>) -> Self {
+        InnerJoin::on(left, right, Vec::new(), Vec::new())
+    }
 
-impl<'a, L, T> ToDataFrame<'a, (String, ArrayView1<'a, T>, ArrayView1<'a, T>), T>
-    for InnerJoin<'a, L, T>
-    where L: Iterator<Item = Window<'a, T>> + Clone,
-          T: UtahNum{
-    fn as_df(self) -> Result<DataFrame<T>> {
+    /// Join on the values of the `left_keys` columns of the left rows and the `right_keys`
+    /// columns of the right rows, instead of on the labels. Rows keep their left labels, and rows
+    /// with an empty key value match nothing.
+    pub fn on(left: &'a DataFrame<T>,
+              right: &'a DataFrame<T>,
+              left_keys: Vec<usize>,
+              right_keys: Vec<usize>)
+              -> Self {
+        InnerJoin {
+            left: left,
+            right: right,
+            matches: build(left, right, &left_keys[..], &right_keys[..]),
+            position: 0,
+            cursor: 0,
+            suffixes: None,
+        }
+    }
 
-        let s = self.clone();
-        let right_columns = self.right_columns.clone();
-        let left_columns = self.left_columns.clone();
-        let mut c = Vec::new();
-        let mut n = Vec::new();
-        let res_dim = (s.fold(0, |acc, _| acc + 1), left_columns.len() + right_columns.len());
+    /// Append `left` and `right` to the names of the columns that appear on both sides.
+    pub fn suffixes(mut self, left: &str, right: &str) -> Self {
+        self.suffixes = Some((left.to_string(), right.to_string()));
+        self
+    }
 
+    /// The number of rows left to emit.
+    fn remaining(&self) -> usize {
+        self.matches.targets.len() - self.cursor
+    }
 
-        for (i, j, k) in self {
-            let p = j.iter().chain(k.iter()).map(|x| x.to_owned());
-            c.extend(p);
+    /// Collect the join into a dataframe with an extra column `name`, which is `both` on every
+    /// row, for symmetry with `OuterJoin::as_df_with_indicator`.
+    pub fn as_df_with_indicator(self, name: &str) -> Result<DataFrame<T>>
+        where T: From<String>
+    {
+        let left_columns = self.left.columns.clone();
+        let right_columns = self.right.columns.clone();
+        let suffixes = self.suffixes.clone();
+        let capacity = self.remaining();
+        let both = T::from("both".to_string());
+        Ok(join_df(self.map(|(i, j, k)| (i, j, Some(k))),
+                   left_columns,
+                   right_columns,
+                   suffixes,
+                   Some((name.to_string(), both.clone(), both)),
+                   capacity))
+    }
+}
 
-            n.push(i.to_owned());
+impl<'a, T> Iterator for InnerJoin<'a, T>
+    where T: UtahNum + 'a
+{
+    type Item = (String, ArrayView1<'a, T>, ArrayView1<'a, T>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (left, right) = (self.left, self.right);
+        while self.position < self.matches.left_len() {
+            let i = self.position;
+            if self.cursor < self.matches.offsets[i + 1] {
+                let j = self.matches.targets[self.cursor];
+                self.cursor += 1;
+                return Some((left.index[i].clone(), left.data.row(i), right.data.row(j)));
+            }
+            self.position += 1;
         }
+        None
+    }
+}
 
-        let columns: Vec<_> = left_columns.iter()
-            .chain(right_columns.iter())
-            .map(|x| x.to_owned())
-            .collect();
+#[derive(Clone)]
+pub struct OuterJoin<'a, T>
+    where T: UtahNum + 'a
+{
+    left: &'a DataFrame<T>,
+    right: &'a DataFrame<T>,
+    matches: Matches,
+    position: usize,
+    cursor: usize,
+    suffixes: Option<(String, String)>,
+    swapped: bool,
+}
 
-        let d = Array::from_shape_vec(res_dim, c).unwrap().mapv(|x| x.to_owned());
-        let df = DataFrame::new(d).columns(&columns[..])?.index(&n[..])?;
 
-        Ok(df)
+impl<'a, T> OuterJoin<'a, T>
+    where T: UtahNum + 'a
+{
+    pub fn new(left: &'a DataFrame<T>, right: &'a DataFrame<T>) -> Self {
+        OuterJoin::on(left, right, Vec::new(), Vec::new())
+    }
 
+    /// Join on the values of the `left_keys` columns of the left rows and the `right_keys`
+    /// columns of the right rows, instead of on the labels. Rows keep their left labels, and rows
+    /// with an empty key value match nothing.
+    pub fn on(left: &'a DataFrame<T>,
+              right: &'a DataFrame<T>,
+              left_keys: Vec<usize>,
+              right_keys: Vec<usize>)
+              -> Self {
+        OuterJoin {
+            left: left,
+            right: right,
+            matches: build(left, right, &left_keys[..], &right_keys[..]),
+            position: 0,
+            cursor: 0,
+            suffixes: None,
+            swapped: false,
+        }
+    }
 
+    /// Append `left` and `right` to the names of the columns that appear on both sides.
+    pub fn suffixes(mut self, left: &str, right: &str) -> Self {
+        self.suffixes = Some((left.to_string(), right.to_string()));
+        self
+    }
 
+    /// Mark the sides as swapped, as in a right join, so the indicator reports unmatched rows as
+    /// `right_only`.
+    pub fn swapped(mut self) -> Self {
+        self.swapped = true;
+        self
     }
 
-    fn as_matrix(self) -> Result<Matrix<T>> {
-        let s = self.clone();
-        let right_columns = self.right_columns.clone();
-        let left_columns = self.left_columns.clone();
-        let mut c = Vec::new();
-        let mut n = Vec::new();
-        let res_dim = (s.fold(0, |acc, _| acc + 1), left_columns.len() + right_columns.len());
+    /// An upper bound on the number of rows left to emit: the matches left, plus one for each
+    /// left row not yet reached, in case it matches nothing.
+    fn remaining(&self) -> usize {
+        self.matches.targets.len() - self.cursor + self.matches.left_len() - self.position
+    }
 
+    /// Collect the join into a dataframe with an extra column `name` telling whether each row
+    /// matched (`both`) or not (`left_only`, or `right_only` if the sides were swapped).
+    pub fn as_df_with_indicator(self, name: &str) -> Result<DataFrame<T>>
+        where T: From<String>
+    {
+        let left_columns = self.left.columns.clone();
+        let right_columns = self.right.columns.clone();
+        let suffixes = self.suffixes.clone();
+        let capacity = self.remaining();
+        let unmatched = if self.swapped { "right_only" } else { "left_only" };
+        let indicator = (name.to_string(),
+                         T::from("both".to_string()),
+                         T::from(unmatched.to_string()));
+        Ok(join_df(self, left_columns, right_columns, suffixes, Some(indicator), capacity))
+    }
+}
 
-        for (i, j, k) in self {
-            let p = j.iter().chain(k.iter()).map(|x| x.to_owned());
-            c.extend(p);
 
-            n.push(i.to_owned());
+impl<'a, T> Iterator for OuterJoin<'a, T>
+    where T: UtahNum + 'a
+{
+    type Item = (String, ArrayView1<'a, T>, Option<ArrayView1<'a, T>>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (left, right) = (self.left, self.right);
+        while self.position < self.matches.left_len() {
+            let i = self.position;
+            let (start, end) = (self.matches.offsets[i], self.matches.offsets[i + 1]);
+            if start == end {
+                self.position += 1;
+                return Some((left.index[i].clone(), left.data.row(i), None));
+            }
+            if self.cursor < end {
+                let j = self.matches.targets[self.cursor];
+                self.cursor += 1;
+                return Some((left.index[i].clone(), left.data.row(i), Some(right.data.row(j))));
+            }
+            self.position += 1;
         }
+        None
+    }
+}
+
 
+impl<'a, T> ToDataFrame<'a, (String, ArrayView1<'a, T>, ArrayView1<'a, T>), T>
+    for InnerJoin<'a, T>
+    where T: UtahNum
+{
+    fn as_df(self) -> Result<DataFrame<T>> {
+        let left_columns = self.left.columns.clone();
+        let right_columns = self.right.columns.clone();
+        let suffixes = self.suffixes.clone();
+        let capacity = self.remaining();
+        Ok(join_df(self.map(|(i, j, k)| (i, j, Some(k))),
+                   left_columns,
+                   right_columns,
+                   suffixes,
+                   None,
+                   capacity))
+    }
 
-        Ok(Array::from_shape_vec(res_dim, c).unwrap())
+    fn as_matrix(self) -> Result<Matrix<T>> {
+        Ok(self.as_df()?.data)
     }
 
     fn as_array(self) -> Result<Row<T>> {
@@ -232,73 +543,26 @@ impl<'a, L, T> ToDataFrame<'a, (String, ArrayView1<'a, T>, ArrayView1<'a, T>), T
 }
 
 
-impl<'a, L,T> ToDataFrame<'a, (String, ArrayView1<'a, T>, Option<ArrayView1<'a, T>>), T>
-    for OuterJoin<'a, L, T>
-    where L: Iterator<Item = Window<'a, T>> + Clone,
-          T: UtahNum{
+impl<'a, T> ToDataFrame<'a, (String, ArrayView1<'a, T>, Option<ArrayView1<'a, T>>), T>
+    for OuterJoin<'a, T>
+    where T: UtahNum
+{
     fn as_df(self) -> Result<DataFrame<T>> {
-
-        let s = self.clone();
-        let right_columns = self.right_columns.clone();
-        let left_columns = self.left_columns.clone();
-        let mut c = Vec::new();
-        let mut n = Vec::new();
-        let res_dim = (s.fold(0, |acc, _| acc + 1), left_columns.len() + right_columns.len());
-
-        let r = repeat(T::empty()).take(right_columns.len());
-        for (i, j, k) in self {
-            c.extend(j.iter().map(|x| x.to_owned()));
-            match k {
-                Some(z) => c.extend(z.iter().map(|x| x.to_owned())),
-                None => c.extend(r.clone()),
-            }
-
-
-            n.push(i.to_owned());
-        }
-
-        let columns: Vec<_> = left_columns.iter()
-            .chain(right_columns.iter())
-            .map(|x| x.to_owned())
-            .collect();
-
-        let d = Array::from_shape_vec(res_dim, c).unwrap().mapv(|x| x.to_owned());
-
-        let df = DataFrame::new(d).columns(&columns[..])?.index(&n[..])?;
-        Ok(df)
-
-
-
+        let left_columns = self.left.columns.clone();
+        let right_columns = self.right.columns.clone();
+        let suffixes = self.suffixes.clone();
+        let capacity = self.remaining();
+        Ok(join_df(self, left_columns, right_columns, suffixes, None, capacity))
     }
-    fn as_matrix(self) -> Result<Matrix<T>> {
-        let s = self.clone();
-        let right_columns = self.right_columns.clone();
-        let left_columns = self.left_columns.clone();
-        let mut c = Vec::new();
-        let mut n = Vec::new();
-        let res_dim = (s.fold(0, |acc, _| acc + 1), left_columns.len() + right_columns.len());
-
-        let r = repeat(T::empty()).take(right_columns.len());
-        for (i, j, k) in self {
-            c.extend(j.iter().map(|x| x.to_owned()));
-            match k {
-                Some(z) => c.extend(z.iter().map(|x| x.to_owned())),
-                None => c.extend(r.clone()),
-            }
-
-
-            n.push(i.to_owned());
-        }
-
-        Ok(Array::from_shape_vec(res_dim, c).unwrap())
-
 
+    fn as_matrix(self) -> Result<Matrix<T>> {
+        Ok(self.as_df()?.data)
     }
 
     fn as_array(self) -> Result<Row<T>> {
-        let right_columns = self.right_columns.clone();
+        let width = self.right.columns.len();
         let mut c = Vec::new();
-        let r = repeat(T::empty()).take(right_columns.len());
+        let r = repeat(T::empty()).take(width);
         for (_, j, k) in self {
             c.extend(j.iter().map(|x| x.to_owned()));
             match k {
@@ -317,57 +581,28 @@ impl<'a, I, T> ToDataFrame<'a, Window<'a, T>, T> for Concat<'a, I, T>
           T: UtahNum
 {
     fn as_df(self) -> Result<DataFrame<T>> {
-
-        let s = self.clone();
-        let axis = self.axis.clone();
         let other = self.concat_other.clone();
-        let mut c = Vec::new();
-        let mut n = Vec::new();
-        let res_dim = match axis {
-            UtahAxis::Row => (s.fold(0, |acc, _| acc + 1), other.len()),
-            UtahAxis::Column => (other.len(), s.fold(0, |acc, _| acc + 1)),
-
-        };
-
-        for (i, j) in self {
-            c.extend(j.iter().map(|x| x.to_owned()));
-            n.push(i.to_owned());
-        }
-
-        let d = Array::from_shape_vec(res_dim, c).unwrap().mapv(|x| x.to_owned());
-
-        match axis {
-            UtahAxis::Row => {
-                let df = DataFrame::new(d).columns(&other[..])?.index(&n[..])?;
-                Ok(df)
-            }
-            UtahAxis::Column => {
-                let df = DataFrame::new(d).columns(&n[..])?.index(&other[..])?;
-                Ok(df)
+        let axis = self.axis.clone();
+        let left_len = self.left_len;
+        let suffixes = self.suffixes.clone();
+        let (labels, windows): (Vec<String>, Vec<_>) = self.map(|(i, j)| (i, j.to_owned())).unzip();
+        if let Some(w) = windows.iter().find(|w| w.len() != other.len()) {
+            let (expected, actual) = (other.len().to_string(), w.len().to_string());
+            return Err(match axis {
+                UtahAxis::Row => ErrorKind::ColumnShapeMismatch(expected, actual),
+                UtahAxis::Column => ErrorKind::IndexShapeMismatch(expected, actual),
             }
+                .into());
         }
-
-
-    }
-    fn as_matrix(self) -> Result<Matrix<T>> {
-        let s = self.clone();
-        let other = self.concat_other.clone();
-        let mut c = Vec::new();
-        let mut n = Vec::new();
-        let res_dim = match self.axis {
-            UtahAxis::Row => (other.len(), s.fold(0, |acc, _| acc + 1)),
-            UtahAxis::Column => (s.fold(0, |acc, _| acc + 1), other.len()),
-
+        let labels = match suffixes {
+            Some(ref s) => suffix_labels(&labels[..left_len], &labels[left_len..], s),
+            None => labels,
         };
+        Ok(windows_df(labels.into_iter().zip(windows.into_iter()), other, axis))
+    }
 
-        for (i, j) in self {
-            c.extend(j.iter().map(|x| x.to_owned()));
-            n.push(i.to_owned());
-        }
-
-        Ok(Array::from_shape_vec(res_dim, c).unwrap())
-
-
+    fn as_matrix(self) -> Result<Matrix<T>> {
+        Ok(self.as_df()?.data)
     }
 
     fn as_array(self) -> Result<Row<T>> {
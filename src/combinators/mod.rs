@@ -1,6 +1,8 @@
 //! Utah combinators
 
 pub mod aggregate;
+pub mod groupby;
 pub mod interact;
 pub mod process;
 pub mod transform;
+pub mod window;
@@ -4,8 +4,10 @@ use util::types::*;
 use std::iter::Iterator;
 use dataframe::{DataFrame, DataFrameMut, DataFrameMutIterator};
 use util::traits::*;
-use ndarray::Array;
+use ndarray::{Array, ArrayViewMut1};
 use util::error::*;
+use util::helpers::{count, mode, reduce};
+use util::rank;
 
 
 #[derive(Clone, Debug)]
@@ -61,7 +63,7 @@ pub struct Impute<'a, I, T: 'a>
           T: UtahNum
 {
     pub data: I,
-    pub strategy: ImputeStrategy,
+    pub strategy: ImputeStrategy<T>,
     pub other: Vec<String>,
     pub axis: UtahAxis,
 }
@@ -70,7 +72,7 @@ impl<'a, I, T> Impute<'a, I, T>
     where I: Iterator<Item = WindowMut<'a, T>>,
           T: UtahNum
 {
-    pub fn new(df: I, s: ImputeStrategy, other: Vec<String>, axis: UtahAxis) -> Impute<'a, I, T>
+    pub fn new(df: I, s: ImputeStrategy<T>, other: Vec<String>, axis: UtahAxis) -> Impute<'a, I, T>
         where I: Iterator<Item = WindowMut<'a, T>>
     {
 
@@ -83,6 +85,46 @@ impl<'a, I, T> Impute<'a, I, T>
     }
 }
 
+/// Fill each empty value with the last non-empty value before it, in iteration order.
+fn fill_forward<'b, I, T>(values: I)
+    where I: Iterator<Item = &'b mut T>,
+          T: UtahNum + 'b
+{
+    let mut last: Option<T> = None;
+    for x in values {
+        if !x.is_empty() {
+            last = Some(x.clone());
+        } else if let Some(ref l) = last {
+            *x = l.clone();
+        }
+    }
+}
+
+/// Fill the gaps between non-empty values by linear interpolation, and those at the ends as
+/// `edge` says.
+fn interpolate<T>(values: &mut ArrayViewMut1<T>, edge: InterpolateEdge)
+    where T: UtahNum
+{
+    let valid: Vec<usize> = (0..values.len()).filter(|&i| !values[i].is_empty()).collect();
+    for w in valid.windows(2) {
+        let (a, b) = (w[0], w[1]);
+        let (lo, run): (T, T) = (values[a].clone(), count(b - a));
+        let rise = values[b].clone() - lo.clone();
+        for i in a + 1..b {
+            values[i] = lo.clone() + rise.clone() * count(i - a) / run.clone();
+        }
+    }
+    if let (InterpolateEdge::Nearest, Some(&first), Some(&last)) =
+           (edge, valid.first(), valid.last()) {
+        for i in 0..first {
+            values[i] = values[first].clone();
+        }
+        for i in last + 1..values.len() {
+            values[i] = values[last].clone();
+        }
+    }
+}
+
 impl<'a, I, T> Iterator for Impute<'a, I, T>
     where I: Iterator<Item = WindowMut<'a, T>>,
           T: UtahNum
@@ -114,7 +156,31 @@ impl<'a, I, T> Iterator for Impute<'a, I, T>
 
                         Some((val, dat))
                     }
-
+                    ImputeStrategy::Mode => {
+                        let mode = mode(dat.iter());
+                        for x in dat.iter_mut().filter(|x| x.is_empty()) {
+                            *x = mode.clone();
+                        }
+                        Some((val, dat))
+                    }
+                    ImputeStrategy::ForwardFill => {
+                        fill_forward(dat.iter_mut());
+                        Some((val, dat))
+                    }
+                    ImputeStrategy::BackFill => {
+                        fill_forward(dat.iter_mut().rev());
+                        Some((val, dat))
+                    }
+                    ImputeStrategy::Interpolate(edge) => {
+                        interpolate(&mut dat, edge);
+                        Some((val, dat))
+                    }
+                    ImputeStrategy::Constant(ref c) => {
+                        for x in dat.iter_mut().filter(|x| x.is_empty()) {
+                            *x = c.clone();
+                        }
+                        Some((val, dat))
+                    }
                 }
             }
         }
@@ -127,7 +193,7 @@ impl<'a, I, T, F> Process<'a, T, F> for MapDF<'a, T, I, F>
           T: UtahNum,
           F: Fn(T) -> T
 {
-    fn impute(self, strategy: ImputeStrategy) -> Impute<'a, Self, T>
+    fn impute(self, strategy: ImputeStrategy<T>) -> Impute<'a, Self, T>
         where Self: Sized + Iterator<Item = WindowMut<'a, T>>
     {
         let other = self.other.clone();
@@ -165,6 +231,7 @@ impl<'a, I, T, F> Process<'a, T, F> for MapDF<'a, T, I, F>
                     columns: other,
                     data: Array::from_shape_vec((nrows, ncols), c).unwrap(),
                     index: n,
+                    index_sorted: false,
                 }
             }
             UtahAxis::Column => {
@@ -172,6 +239,7 @@ impl<'a, I, T, F> Process<'a, T, F> for MapDF<'a, T, I, F>
                     columns: n,
                     data: Array::from_shape_vec((nrows, ncols), c).unwrap(),
                     index: other,
+                    index_sorted: false,
                 }
             }
 
@@ -194,7 +262,7 @@ impl<'a, I, T, F> Process<'a, T, F> for Impute<'a, I, T>
           T: UtahNum,
           F: Fn(T) -> T
 {
-    fn impute(self, strategy: ImputeStrategy) -> Impute<'a, Self, T>
+    fn impute(self, strategy: ImputeStrategy<T>) -> Impute<'a, Self, T>
         where Self: Sized + Iterator<Item = WindowMut<'a, T>>
     {
         let other = self.other.clone();
@@ -232,6 +300,7 @@ impl<'a, I, T, F> Process<'a, T, F> for Impute<'a, I, T>
                     columns: other,
                     data: Array::from_shape_vec((nrows, ncols), c).unwrap(),
                     index: n,
+                    index_sorted: false,
                 }
             }
             UtahAxis::Column => {
@@ -239,6 +308,7 @@ impl<'a, I, T, F> Process<'a, T, F> for Impute<'a, I, T>
                     columns: n,
                     data: Array::from_shape_vec((nrows, ncols), c).unwrap(),
                     index: other,
+                    index_sorted: false,
                 }
             }
 
@@ -257,7 +327,7 @@ impl<'a, T, F> Process<'a, T, F> for DataFrameMutIterator<'a, T>
     where T: UtahNum,
           F: Fn(T) -> T
 {
-    fn impute(self, strategy: ImputeStrategy) -> Impute<'a, Self, T>
+    fn impute(self, strategy: ImputeStrategy<T>) -> Impute<'a, Self, T>
         where Self: Sized + Iterator<Item = WindowMut<'a, T>>
     {
 
@@ -297,6 +367,7 @@ impl<'a, T, F> Process<'a, T, F> for DataFrameMutIterator<'a, T>
                     columns: other,
                     data: Array::from_shape_vec((nrows, ncols), c).unwrap(),
                     index: n,
+                    index_sorted: false,
                 }
             }
             UtahAxis::Column => {
@@ -304,6 +375,7 @@ impl<'a, T, F> Process<'a, T, F> for DataFrameMutIterator<'a, T>
                     columns: n,
                     data: Array::from_shape_vec((nrows, ncols), c).unwrap(),
                     index: other,
+                    index_sorted: false,
                 }
             }
 
@@ -321,61 +393,13 @@ impl<'a, T> ToDataFrame<'a, WindowMut<'a, T>, T> for DataFrameMutIterator<'a, T>
     where T: UtahNum
 {
     fn as_df(self) -> Result<DataFrame<T>> {
-        let axis = self.axis.clone();
         let other = self.other.clone();
-        let mut c = Vec::new();
-        let mut n = Vec::new();
-        let (mut ncols, mut nrows) = match axis {
-            UtahAxis::Row => (other.len(), 0),
-            UtahAxis::Column => (0, other.len()),
-        };
-
-        for (i, j) in self {
-            match axis {
-                UtahAxis::Row => nrows += 1,
-                UtahAxis::Column => ncols += 1,
-            };
-            c.extend(j);
-            n.push(i.to_owned());
-        }
-
-
-        let d = Array::from_shape_vec((nrows, ncols), c).unwrap().map(|x| ((*x).clone()));
-        match axis {
-            UtahAxis::Row => {
-                let df = DataFrame::new(d).columns(&other[..])?.index(&n[..])?;
-                Ok(df)
-            }
-            UtahAxis::Column => {
-                let df = DataFrame::new(d).columns(&other[..])?.index(&n[..])?;
-                Ok(df)
-            }
-
-        }
-    }
-    fn as_matrix(self) -> Result<Matrix<T>> {
         let axis = self.axis.clone();
-        let other = self.other.clone();
-        let mut c = Vec::new();
-        let mut n = Vec::new();
-        let (mut ncols, mut nrows) = match axis {
-            UtahAxis::Row => (other.len(), 0),
-            UtahAxis::Column => (0, other.len()),
-        };
-
-        for (i, j) in self {
-            match axis {
-                UtahAxis::Row => nrows += 1,
-                UtahAxis::Column => ncols += 1,
-            };
-
-            c.extend(j);
-            n.push(i.to_owned());
-        }
-
-        Ok(Array::from_shape_vec((nrows, ncols), c).unwrap().map(|x| ((*x).clone())))
-
+        Ok(collect_windows(self, other, axis))
+    }
 
+    fn as_matrix(self) -> Result<Matrix<T>> {
+        Ok(self.as_df()?.data)
     }
 
     fn as_array(self) -> Result<Row<T>> {
@@ -392,60 +416,13 @@ impl<'a, I, T> ToDataFrame<'a, WindowMut<'a, T>, T> for Impute<'a, I, T>
           T: UtahNum
 {
     fn as_df(self) -> Result<DataFrame<T>> {
-        let axis = self.axis.clone();
         let other = self.other.clone();
-        let mut c = Vec::new();
-        let mut n = Vec::new();
-        let (mut ncols, mut nrows) = match axis {
-            UtahAxis::Row => (other.len(), 0),
-            UtahAxis::Column => (0, other.len()),
-        };
-
-        for (i, j) in self {
-            match axis {
-                UtahAxis::Row => nrows += 1,
-                UtahAxis::Column => ncols += 1,
-            };
-            c.extend(j);
-            n.push(i.to_owned());
-        }
-
-
-        let d = Array::from_shape_vec((nrows, ncols), c).unwrap().map(|x| ((*x).clone()));
-        match axis {
-            UtahAxis::Row => {
-                let df = DataFrame::new(d).columns(&other[..])?.index(&n[..])?;
-                Ok(df)
-            }
-            UtahAxis::Column => {
-                let df = DataFrame::new(d).columns(&other[..])?.index(&n[..])?;
-                Ok(df)
-            }
-
-        }
+        let axis = self.axis.clone();
+        Ok(collect_windows(self, other, axis))
     }
 
     fn as_matrix(self) -> Result<Matrix<T>> {
-        let axis = self.axis.clone();
-        let other = self.other.clone();
-        let mut c = Vec::new();
-        let mut n = Vec::new();
-        let (mut ncols, mut nrows) = match axis {
-            UtahAxis::Row => (other.len(), 0),
-            UtahAxis::Column => (0, other.len()),
-        };
-
-        for (i, j) in self {
-            match axis {
-                UtahAxis::Row => nrows += 1,
-                UtahAxis::Column => ncols += 1,
-            };
-
-            c.extend(j);
-            n.push(i.to_owned());
-        }
-
-        Ok(Array::from_shape_vec((nrows, ncols), c).unwrap().map(|x| ((*x).clone())))
+        Ok(self.as_df()?.data)
     }
 
     fn as_array(self) -> Result<Row<T>> {
@@ -465,67 +442,933 @@ impl<'a, I, T, F> ToDataFrame<'a, WindowMut<'a, T>, T> for MapDF<'a, T, I, F>
           F: Fn(T) -> T
 {
     fn as_df(self) -> Result<DataFrame<T>> {
-        let axis = self.axis.clone();
         let other = self.other.clone();
-        let mut c = Vec::new();
-        let mut n = Vec::new();
-        let (mut ncols, mut nrows) = match axis {
-            UtahAxis::Row => (other.len(), 0),
-            UtahAxis::Column => (0, other.len()),
-        };
+        let axis = self.axis.clone();
+        Ok(collect_windows(self, other, axis))
+    }
 
-        for (i, j) in self {
-            match axis {
-                UtahAxis::Row => nrows += 1,
-                UtahAxis::Column => ncols += 1,
-            };
+    fn as_matrix(self) -> Result<Matrix<T>> {
+        Ok(self.as_df()?.data)
+    }
+
+    fn as_array(self) -> Result<Row<T>> {
+        let mut c = Vec::new();
+        for (_, j) in self {
             c.extend(j);
-            n.push(i.to_owned());
         }
+        Ok(Array::from_vec(c).map(|x| ((*x).clone())))
+    }
+}
 
 
-        let d = Array::from_shape_vec((nrows, ncols), c).unwrap().map(|x| ((*x).clone()));
-        match axis {
-            UtahAxis::Row => {
-                let df = DataFrame::new(d).columns(&other[..])?.index(&n[..])?;
-                Ok(df)
+/// Collect the windows of a mutable combinator into an owned dataframe.
+fn collect_windows<'a, I, T>(windows: I, other: Vec<String>, axis: UtahAxis) -> DataFrame<T>
+    where I: Iterator<Item = WindowMut<'a, T>>,
+          T: UtahNum + 'a
+{
+    let mut c = Vec::new();
+    let mut n = Vec::new();
+    for (i, j) in windows {
+        c.extend(j.iter().cloned());
+        n.push(i);
+    }
+    let d = Array::from_shape_vec((n.len(), other.len()), c).unwrap();
+    match axis {
+        UtahAxis::Row => {
+            DataFrame {
+                columns: other,
+                data: d,
+                index: n,
+                index_sorted: false,
             }
-            UtahAxis::Column => {
-                let df = DataFrame::new(d).columns(&other[..])?.index(&n[..])?;
-                Ok(df)
+        }
+        UtahAxis::Column => {
+            DataFrame {
+                columns: n,
+                data: d.reversed_axes(),
+                index: other,
+                index_sorted: false,
             }
+        }
+    }
+}
 
+/// Collect the windows of a mutable combinator into a mutable dataframe.
+fn collect_windows_mut<'a, I, T>(windows: I,
+                                 other: Vec<String>,
+                                 axis: UtahAxis)
+                                 -> DataFrameMut<'a, T>
+    where I: Iterator<Item = WindowMut<'a, T>>,
+          T: UtahNum + 'a
+{
+    let mut c = Vec::new();
+    let mut n = Vec::new();
+    for (i, j) in windows {
+        c.extend(j);
+        n.push(i);
+    }
+    let d = Array::from_shape_vec((n.len(), other.len()), c).unwrap();
+    match axis {
+        UtahAxis::Row => {
+            DataFrameMut {
+                columns: other,
+                data: d,
+                index: n,
+                index_sorted: false,
+            }
+        }
+        UtahAxis::Column => {
+            DataFrameMut {
+                columns: n,
+                data: d.reversed_axes(),
+                index: other,
+                index_sorted: false,
+            }
         }
     }
+}
 
-    fn as_matrix(self) -> Result<Matrix<T>> {
+
+#[derive(Clone)]
+pub struct Rank<'a, I, T: 'a>
+    where I: Iterator<Item = WindowMut<'a, T>> + 'a,
+          T: UtahNum
+{
+    pub data: I,
+    pub method: RankMethod,
+    pub empties: Option<EmptyPosition>,
+    pub other: Vec<String>,
+    pub axis: UtahAxis,
+}
+
+impl<'a, I, T> Rank<'a, I, T>
+    where I: Iterator<Item = WindowMut<'a, T>>,
+          T: UtahNum
+{
+    pub fn new(df: I,
+               method: RankMethod,
+               empties: Option<EmptyPosition>,
+               other: Vec<String>,
+               axis: UtahAxis)
+               -> Rank<'a, I, T> {
+        Rank {
+            data: df,
+            method: method,
+            empties: empties,
+            other: other,
+            axis: axis,
+        }
+    }
+}
+
+impl<'a, I, T> Iterator for Rank<'a, I, T>
+    where I: Iterator<Item = WindowMut<'a, T>>,
+          T: UtahNum + PartialOrd
+{
+    type Item = WindowMut<'a, T>;
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.data.next() {
+            None => None,
+            Some((val, mut dat)) => {
+                let values: Vec<T> = dat.iter().cloned().collect();
+                let ranks = rank::rank(&values[..], self.method, self.empties);
+                for (x, r) in dat.iter_mut().zip(ranks) {
+                    *x = r;
+                }
+                Some((val, dat))
+            }
+        }
+    }
+}
+
+impl<'a, I, T, F> Process<'a, T, F> for Rank<'a, I, T>
+    where I: Iterator<Item = WindowMut<'a, T>>,
+          T: UtahNum + PartialOrd,
+          F: Fn(T) -> T
+{
+    fn impute(self, strategy: ImputeStrategy<T>) -> Impute<'a, Self, T>
+        where Self: Sized + Iterator<Item = WindowMut<'a, T>>
+    {
+        let other = self.other.clone();
         let axis = self.axis.clone();
+        Impute::new(self, strategy, other, axis)
+    }
+
+    fn to_mut_df(self) -> DataFrameMut<'a, T>
+        where Self: Sized + Iterator<Item = WindowMut<'a, T>>
+    {
         let other = self.other.clone();
-        let mut c = Vec::new();
-        let mut n = Vec::new();
-        let (mut ncols, mut nrows) = match axis {
-            UtahAxis::Row => (other.len(), 0),
-            UtahAxis::Column => (0, other.len()),
-        };
+        let axis = self.axis.clone();
+        collect_windows_mut(self, other, axis)
+    }
 
-        for (i, j) in self {
-            match axis {
-                UtahAxis::Row => nrows += 1,
-                UtahAxis::Column => ncols += 1,
-            };
+    fn mapdf(self, f: F) -> MapDF<'a, T, Self, F> {
+        let other = self.other.clone();
+        let axis = self.axis.clone();
+        MapDF::new(self, f, other, axis)
+    }
+}
 
-            c.extend(j);
-            n.push(i.to_owned());
-        }
+impl<'a, I, T> ToDataFrame<'a, WindowMut<'a, T>, T> for Rank<'a, I, T>
+    where I: Iterator<Item = WindowMut<'a, T>>,
+          T: UtahNum + PartialOrd
+{
+    fn as_df(self) -> Result<DataFrame<T>> {
+        let other = self.other.clone();
+        let axis = self.axis.clone();
+        Ok(collect_windows(self, other, axis))
+    }
 
-        Ok(Array::from_shape_vec((nrows, ncols), c).unwrap().map(|x| ((*x).clone())))
+    fn as_matrix(self) -> Result<Matrix<T>> {
+        Ok(self.as_df()?.data)
     }
 
     fn as_array(self) -> Result<Row<T>> {
         let mut c = Vec::new();
         for (_, j) in self {
-            c.extend(j);
+            c.extend(j.iter().cloned());
         }
-        Ok(Array::from_vec(c).map(|x| ((*x).clone())))
+        Ok(Array::from_vec(c))
+    }
+}
+
+
+#[derive(Clone)]
+pub struct Demean<'a, I, T: 'a>
+    where I: Iterator<Item = WindowMut<'a, T>> + 'a,
+          T: UtahNum
+{
+    pub data: I,
+    pub other: Vec<String>,
+    pub axis: UtahAxis,
+}
+
+impl<'a, I, T> Demean<'a, I, T>
+    where I: Iterator<Item = WindowMut<'a, T>>,
+          T: UtahNum
+{
+    pub fn new(df: I, other: Vec<String>, axis: UtahAxis) -> Demean<'a, I, T> {
+        Demean {
+            data: df,
+            other: other,
+            axis: axis,
+        }
+    }
+}
+
+impl<'a, I, T> Iterator for Demean<'a, I, T>
+    where I: Iterator<Item = WindowMut<'a, T>>,
+          T: UtahNum
+{
+    type Item = WindowMut<'a, T>;
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.data.next() {
+            None => None,
+            Some((val, mut dat)) => {
+                let size = dat.iter().filter(|x| !x.is_empty()).count();
+                // A lane with no values has no mean to remove.
+                if size == 0 {
+                    return Some((val, dat));
+                }
+                let total = dat.iter()
+                    .filter(|x| !x.is_empty())
+                    .fold(T::zero(), |acc, x| acc + x.clone());
+                let mean = total / count(size);
+                dat.mapv_inplace(|x| if x.is_empty() { x } else { x - mean.clone() });
+                Some((val, dat))
+            }
+        }
+    }
+}
+
+impl<'a, I, T, F> Process<'a, T, F> for Demean<'a, I, T>
+    where I: Iterator<Item = WindowMut<'a, T>>,
+          T: UtahNum,
+          F: Fn(T) -> T
+{
+    fn impute(self, strategy: ImputeStrategy<T>) -> Impute<'a, Self, T>
+        where Self: Sized + Iterator<Item = WindowMut<'a, T>>
+    {
+        let other = self.other.clone();
+        let axis = self.axis.clone();
+        Impute::new(self, strategy, other, axis)
+    }
+
+    fn to_mut_df(self) -> DataFrameMut<'a, T>
+        where Self: Sized + Iterator<Item = WindowMut<'a, T>>
+    {
+        let other = self.other.clone();
+        let axis = self.axis.clone();
+        collect_windows_mut(self, other, axis)
+    }
+
+    fn mapdf(self, f: F) -> MapDF<'a, T, Self, F> {
+        let other = self.other.clone();
+        let axis = self.axis.clone();
+        MapDF::new(self, f, other, axis)
+    }
+}
+
+impl<'a, I, T> ToDataFrame<'a, WindowMut<'a, T>, T> for Demean<'a, I, T>
+    where I: Iterator<Item = WindowMut<'a, T>>,
+          T: UtahNum
+{
+    fn as_df(self) -> Result<DataFrame<T>> {
+        let other = self.other.clone();
+        let axis = self.axis.clone();
+        Ok(collect_windows(self, other, axis))
+    }
+
+    fn as_matrix(self) -> Result<Matrix<T>> {
+        Ok(self.as_df()?.data)
+    }
+
+    fn as_array(self) -> Result<Row<T>> {
+        let mut c = Vec::new();
+        for (_, j) in self {
+            c.extend(j.iter().cloned());
+        }
+        Ok(Array::from_vec(c))
+    }
+}
+
+
+/// Scale each window to zero mean and unit (sample) standard deviation, in place. Empty values
+/// stay empty, and windows with no spread are only centered.
+#[derive(Clone)]
+pub struct Standardize<'a, I, T: 'a>
+    where I: Iterator<Item = WindowMut<'a, T>> + 'a,
+          T: UtahNum
+{
+    pub data: I,
+    pub other: Vec<String>,
+    pub axis: UtahAxis,
+}
+
+impl<'a, I, T> Standardize<'a, I, T>
+    where I: Iterator<Item = WindowMut<'a, T>>,
+          T: UtahNum
+{
+    pub fn new(df: I, other: Vec<String>, axis: UtahAxis) -> Standardize<'a, I, T> {
+        Standardize {
+            data: df,
+            other: other,
+            axis: axis,
+        }
+    }
+}
+
+impl<'a, I, T> Iterator for Standardize<'a, I, T>
+    where I: Iterator<Item = WindowMut<'a, T>>,
+          T: UtahNum + From<f64> + Into<f64>
+{
+    type Item = WindowMut<'a, T>;
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.data.next() {
+            None => None,
+            Some((val, mut dat)) => {
+                let values: Vec<f64> = dat.iter()
+                    .filter(|x| !x.is_empty())
+                    .map(|x| x.clone().into())
+                    .collect();
+                let n = values.len() as f64;
+                let mean = values.iter().sum::<f64>() / n;
+                let var = values.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / (n - 1.);
+                let std = if var > 0. { var.sqrt() } else { 1. };
+                dat.mapv_inplace(|x| if x.is_empty() {
+                    x
+                } else {
+                    T::from((x.into() - mean) / std)
+                });
+                Some((val, dat))
+            }
+        }
+    }
+}
+
+impl<'a, I, T, F> Process<'a, T, F> for Standardize<'a, I, T>
+    where I: Iterator<Item = WindowMut<'a, T>>,
+          T: UtahNum + From<f64> + Into<f64>,
+          F: Fn(T) -> T
+{
+    fn impute(self, strategy: ImputeStrategy<T>) -> Impute<'a, Self, T>
+        where Self: Sized + Iterator<Item = WindowMut<'a, T>>
+    {
+        let other = self.other.clone();
+        let axis = self.axis.clone();
+        Impute::new(self, strategy, other, axis)
+    }
+
+    fn to_mut_df(self) -> DataFrameMut<'a, T>
+        where Self: Sized + Iterator<Item = WindowMut<'a, T>>
+    {
+        let other = self.other.clone();
+        let axis = self.axis.clone();
+        collect_windows_mut(self, other, axis)
+    }
+
+    fn mapdf(self, f: F) -> MapDF<'a, T, Self, F> {
+        let other = self.other.clone();
+        let axis = self.axis.clone();
+        MapDF::new(self, f, other, axis)
+    }
+}
+
+impl<'a, I, T> ToDataFrame<'a, WindowMut<'a, T>, T> for Standardize<'a, I, T>
+    where I: Iterator<Item = WindowMut<'a, T>>,
+          T: UtahNum + From<f64> + Into<f64>
+{
+    fn as_df(self) -> Result<DataFrame<T>> {
+        let other = self.other.clone();
+        let axis = self.axis.clone();
+        Ok(collect_windows(self, other, axis))
+    }
+
+    fn as_matrix(self) -> Result<Matrix<T>> {
+        Ok(self.as_df()?.data)
+    }
+
+    fn as_array(self) -> Result<Row<T>> {
+        let mut c = Vec::new();
+        for (_, j) in self {
+            c.extend(j.iter().cloned());
+        }
+        Ok(Array::from_vec(c))
+    }
+}
+
+
+/// Rescale each window linearly onto a range, in place. Empty values stay empty, and windows with
+/// no spread map to the low end of the range.
+#[derive(Clone)]
+pub struct MinMaxScale<'a, I, T: 'a>
+    where I: Iterator<Item = WindowMut<'a, T>> + 'a,
+          T: UtahNum
+{
+    pub data: I,
+    pub range: (T, T),
+    pub other: Vec<String>,
+    pub axis: UtahAxis,
+}
+
+impl<'a, I, T> MinMaxScale<'a, I, T>
+    where I: Iterator<Item = WindowMut<'a, T>>,
+          T: UtahNum
+{
+    pub fn new(df: I, range: (T, T), other: Vec<String>, axis: UtahAxis) -> MinMaxScale<'a, I, T> {
+        MinMaxScale {
+            data: df,
+            range: range,
+            other: other,
+            axis: axis,
+        }
+    }
+}
+
+impl<'a, I, T> Iterator for MinMaxScale<'a, I, T>
+    where I: Iterator<Item = WindowMut<'a, T>>,
+          T: UtahNum + PartialOrd
+{
+    type Item = WindowMut<'a, T>;
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.data.next() {
+            None => None,
+            Some((val, mut dat)) => {
+                let lo = reduce(dat.iter(), Agg::Min);
+                let spread = reduce(dat.iter(), Agg::Max) - lo.clone();
+                let (ref a, ref b) = self.range;
+                let scale = b.clone() - a.clone();
+                dat.mapv_inplace(|x| if x.is_empty() {
+                    x
+                } else if spread == T::zero() {
+                    a.clone()
+                } else {
+                    (x - lo.clone()) * scale.clone() / spread.clone() + a.clone()
+                });
+                Some((val, dat))
+            }
+        }
+    }
+}
+
+impl<'a, I, T, F> Process<'a, T, F> for MinMaxScale<'a, I, T>
+    where I: Iterator<Item = WindowMut<'a, T>>,
+          T: UtahNum + PartialOrd,
+          F: Fn(T) -> T
+{
+    fn impute(self, strategy: ImputeStrategy<T>) -> Impute<'a, Self, T>
+        where Self: Sized + Iterator<Item = WindowMut<'a, T>>
+    {
+        let other = self.other.clone();
+        let axis = self.axis.clone();
+        Impute::new(self, strategy, other, axis)
+    }
+
+    fn to_mut_df(self) -> DataFrameMut<'a, T>
+        where Self: Sized + Iterator<Item = WindowMut<'a, T>>
+    {
+        let other = self.other.clone();
+        let axis = self.axis.clone();
+        collect_windows_mut(self, other, axis)
+    }
+
+    fn mapdf(self, f: F) -> MapDF<'a, T, Self, F> {
+        let other = self.other.clone();
+        let axis = self.axis.clone();
+        MapDF::new(self, f, other, axis)
+    }
+}
+
+impl<'a, I, T> ToDataFrame<'a, WindowMut<'a, T>, T> for MinMaxScale<'a, I, T>
+    where I: Iterator<Item = WindowMut<'a, T>>,
+          T: UtahNum + PartialOrd
+{
+    fn as_df(self) -> Result<DataFrame<T>> {
+        let other = self.other.clone();
+        let axis = self.axis.clone();
+        Ok(collect_windows(self, other, axis))
+    }
+
+    fn as_matrix(self) -> Result<Matrix<T>> {
+        Ok(self.as_df()?.data)
+    }
+
+    fn as_array(self) -> Result<Row<T>> {
+        let mut c = Vec::new();
+        for (_, j) in self {
+            c.extend(j.iter().cloned());
+        }
+        Ok(Array::from_vec(c))
+    }
+}
+
+
+/// Combine every entry with a scalar, in place.
+#[derive(Clone)]
+pub struct ScalarOp<'a, I, T: 'a>
+    where I: Iterator<Item = WindowMut<'a, T>> + 'a,
+          T: UtahNum
+{
+    pub data: I,
+    pub op: ArithOp,
+    pub value: T,
+    pub other: Vec<String>,
+    pub axis: UtahAxis,
+}
+
+impl<'a, I, T> ScalarOp<'a, I, T>
+    where I: Iterator<Item = WindowMut<'a, T>>,
+          T: UtahNum
+{
+    pub fn new(df: I,
+               op: ArithOp,
+               value: T,
+               other: Vec<String>,
+               axis: UtahAxis)
+               -> ScalarOp<'a, I, T> {
+        ScalarOp {
+            data: df,
+            op: op,
+            value: value,
+            other: other,
+            axis: axis,
+        }
+    }
+}
+
+impl<'a, I, T> Iterator for ScalarOp<'a, I, T>
+    where I: Iterator<Item = WindowMut<'a, T>>,
+          T: UtahNum
+{
+    type Item = WindowMut<'a, T>;
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.data.next() {
+            None => None,
+            Some((val, mut dat)) => {
+                let (op, value) = (self.op, &self.value);
+                dat.mapv_inplace(|x| op.apply(x, value.clone()));
+                Some((val, dat))
+            }
+        }
+    }
+}
+
+impl<'a, I, T, F> Process<'a, T, F> for ScalarOp<'a, I, T>
+    where I: Iterator<Item = WindowMut<'a, T>>,
+          T: UtahNum,
+          F: Fn(T) -> T
+{
+    fn impute(self, strategy: ImputeStrategy<T>) -> Impute<'a, Self, T>
+        where Self: Sized + Iterator<Item = WindowMut<'a, T>>
+    {
+        let other = self.other.clone();
+        let axis = self.axis.clone();
+        Impute::new(self, strategy, other, axis)
+    }
+
+    fn to_mut_df(self) -> DataFrameMut<'a, T>
+        where Self: Sized + Iterator<Item = WindowMut<'a, T>>
+    {
+        let other = self.other.clone();
+        let axis = self.axis.clone();
+        collect_windows_mut(self, other, axis)
+    }
+
+    fn mapdf(self, f: F) -> MapDF<'a, T, Self, F> {
+        let other = self.other.clone();
+        let axis = self.axis.clone();
+        MapDF::new(self, f, other, axis)
+    }
+}
+
+impl<'a, I, T> ToDataFrame<'a, WindowMut<'a, T>, T> for ScalarOp<'a, I, T>
+    where I: Iterator<Item = WindowMut<'a, T>>,
+          T: UtahNum
+{
+    fn as_df(self) -> Result<DataFrame<T>> {
+        let other = self.other.clone();
+        let axis = self.axis.clone();
+        Ok(collect_windows(self, other, axis))
+    }
+
+    fn as_matrix(self) -> Result<Matrix<T>> {
+        Ok(self.as_df()?.data)
+    }
+
+    fn as_array(self) -> Result<Row<T>> {
+        let mut c = Vec::new();
+        for (_, j) in self {
+            c.extend(j.iter().cloned());
+        }
+        Ok(Array::from_vec(c))
+    }
+}
+
+
+#[derive(Clone)]
+pub struct Clip<'a, I, T: 'a>
+    where I: Iterator<Item = WindowMut<'a, T>> + 'a,
+          T: UtahNum
+{
+    pub data: I,
+    pub lower: Option<T>,
+    pub upper: Option<T>,
+    pub other: Vec<String>,
+    pub axis: UtahAxis,
+}
+
+impl<'a, I, T> Clip<'a, I, T>
+    where I: Iterator<Item = WindowMut<'a, T>>,
+          T: UtahNum
+{
+    pub fn new(df: I,
+               lower: Option<T>,
+               upper: Option<T>,
+               other: Vec<String>,
+               axis: UtahAxis)
+               -> Clip<'a, I, T> {
+        Clip {
+            data: df,
+            lower: lower,
+            upper: upper,
+            other: other,
+            axis: axis,
+        }
+    }
+}
+
+impl<'a, I, T> Iterator for Clip<'a, I, T>
+    where I: Iterator<Item = WindowMut<'a, T>>,
+          T: UtahNum + PartialOrd
+{
+    type Item = WindowMut<'a, T>;
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.data.next() {
+            None => None,
+            Some((val, mut dat)) => {
+                for x in dat.iter_mut().filter(|x| !x.is_empty()) {
+                    match self.lower {
+                        Some(ref lo) if *x < *lo => *x = lo.clone(),
+                        _ => {}
+                    }
+                    match self.upper {
+                        Some(ref hi) if *x > *hi => *x = hi.clone(),
+                        _ => {}
+                    }
+                }
+                Some((val, dat))
+            }
+        }
+    }
+}
+
+impl<'a, I, T, F> Process<'a, T, F> for Clip<'a, I, T>
+    where I: Iterator<Item = WindowMut<'a, T>>,
+          T: UtahNum + PartialOrd,
+          F: Fn(T) -> T
+{
+    fn impute(self, strategy: ImputeStrategy<T>) -> Impute<'a, Self, T>
+        where Self: Sized + Iterator<Item = WindowMut<'a, T>>
+    {
+        let other = self.other.clone();
+        let axis = self.axis.clone();
+        Impute::new(self, strategy, other, axis)
+    }
+
+    fn to_mut_df(self) -> DataFrameMut<'a, T>
+        where Self: Sized + Iterator<Item = WindowMut<'a, T>>
+    {
+        let other = self.other.clone();
+        let axis = self.axis.clone();
+        collect_windows_mut(self, other, axis)
+    }
+
+    fn mapdf(self, f: F) -> MapDF<'a, T, Self, F> {
+        let other = self.other.clone();
+        let axis = self.axis.clone();
+        MapDF::new(self, f, other, axis)
+    }
+}
+
+impl<'a, I, T> ToDataFrame<'a, WindowMut<'a, T>, T> for Clip<'a, I, T>
+    where I: Iterator<Item = WindowMut<'a, T>>,
+          T: UtahNum + PartialOrd
+{
+    fn as_df(self) -> Result<DataFrame<T>> {
+        let other = self.other.clone();
+        let axis = self.axis.clone();
+        Ok(collect_windows(self, other, axis))
+    }
+
+    fn as_matrix(self) -> Result<Matrix<T>> {
+        Ok(self.as_df()?.data)
+    }
+
+    fn as_array(self) -> Result<Row<T>> {
+        let mut c = Vec::new();
+        for (_, j) in self {
+            c.extend(j.iter().cloned());
+        }
+        Ok(Array::from_vec(c))
+    }
+}
+
+
+#[derive(Clone)]
+pub struct Replace<'a, I, T: 'a>
+    where I: Iterator<Item = WindowMut<'a, T>> + 'a,
+          T: UtahNum
+{
+    pub data: I,
+    pub pairs: Vec<(T, T)>,
+    pub only: Option<Vec<String>>,
+    pub other: Vec<String>,
+    pub axis: UtahAxis,
+}
+
+impl<'a, I, T> Replace<'a, I, T>
+    where I: Iterator<Item = WindowMut<'a, T>>,
+          T: UtahNum
+{
+    pub fn new(df: I,
+               pairs: Vec<(T, T)>,
+               only: Option<Vec<String>>,
+               other: Vec<String>,
+               axis: UtahAxis)
+               -> Replace<'a, I, T> {
+        Replace {
+            data: df,
+            pairs: pairs,
+            only: only,
+            other: other,
+            axis: axis,
+        }
+    }
+}
+
+impl<'a, I, T> Iterator for Replace<'a, I, T>
+    where I: Iterator<Item = WindowMut<'a, T>>,
+          T: UtahNum
+{
+    type Item = WindowMut<'a, T>;
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.data.next() {
+            None => None,
+            Some((val, mut dat)) => {
+                if self.only.as_ref().map_or(true, |only| only.contains(&val)) {
+                    for x in dat.iter_mut() {
+                        let found = self.pairs
+                            .iter()
+                            .find(|&&(ref old, _)| *old == *x || (old.is_empty() && x.is_empty()));
+                        if let Some(&(_, ref new)) = found {
+                            *x = new.clone();
+                        }
+                    }
+                }
+                Some((val, dat))
+            }
+        }
+    }
+}
+
+impl<'a, I, T, F> Process<'a, T, F> for Replace<'a, I, T>
+    where I: Iterator<Item = WindowMut<'a, T>>,
+          T: UtahNum,
+          F: Fn(T) -> T
+{
+    fn impute(self, strategy: ImputeStrategy<T>) -> Impute<'a, Self, T>
+        where Self: Sized + Iterator<Item = WindowMut<'a, T>>
+    {
+        let other = self.other.clone();
+        let axis = self.axis.clone();
+        Impute::new(self, strategy, other, axis)
+    }
+
+    fn to_mut_df(self) -> DataFrameMut<'a, T>
+        where Self: Sized + Iterator<Item = WindowMut<'a, T>>
+    {
+        let other = self.other.clone();
+        let axis = self.axis.clone();
+        collect_windows_mut(self, other, axis)
+    }
+
+    fn mapdf(self, f: F) -> MapDF<'a, T, Self, F> {
+        let other = self.other.clone();
+        let axis = self.axis.clone();
+        MapDF::new(self, f, other, axis)
+    }
+}
+
+impl<'a, I, T> ToDataFrame<'a, WindowMut<'a, T>, T> for Replace<'a, I, T>
+    where I: Iterator<Item = WindowMut<'a, T>>,
+          T: UtahNum
+{
+    fn as_df(self) -> Result<DataFrame<T>> {
+        let other = self.other.clone();
+        let axis = self.axis.clone();
+        Ok(collect_windows(self, other, axis))
+    }
+
+    fn as_matrix(self) -> Result<Matrix<T>> {
+        Ok(self.as_df()?.data)
+    }
+
+    fn as_array(self) -> Result<Row<T>> {
+        let mut c = Vec::new();
+        for (_, j) in self {
+            c.extend(j.iter().cloned());
+        }
+        Ok(Array::from_vec(c))
+    }
+}
+
+
+#[derive(Clone)]
+pub struct ReplaceWith<'a, I, T: 'a, G>
+    where I: Iterator<Item = WindowMut<'a, T>> + 'a,
+          T: UtahNum,
+          G: Fn(&T) -> Option<T>
+{
+    pub data: I,
+    pub func: G,
+    pub only: Option<Vec<String>>,
+    pub other: Vec<String>,
+    pub axis: UtahAxis,
+}
+
+impl<'a, I, T, G> ReplaceWith<'a, I, T, G>
+    where I: Iterator<Item = WindowMut<'a, T>>,
+          T: UtahNum,
+          G: Fn(&T) -> Option<T>
+{
+    pub fn new(df: I,
+               f: G,
+               only: Option<Vec<String>>,
+               other: Vec<String>,
+               axis: UtahAxis)
+               -> ReplaceWith<'a, I, T, G> {
+        ReplaceWith {
+            data: df,
+            func: f,
+            only: only,
+            other: other,
+            axis: axis,
+        }
+    }
+}
+
+impl<'a, I, T, G> Iterator for ReplaceWith<'a, I, T, G>
+    where I: Iterator<Item = WindowMut<'a, T>>,
+          T: UtahNum,
+          G: Fn(&T) -> Option<T>
+{
+    type Item = WindowMut<'a, T>;
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.data.next() {
+            None => None,
+            Some((val, mut dat)) => {
+                if self.only.as_ref().map_or(true, |only| only.contains(&val)) {
+                    for x in dat.iter_mut() {
+                        if let Some(new) = (self.func)(x) {
+                            *x = new;
+                        }
+                    }
+                }
+                Some((val, dat))
+            }
+        }
+    }
+}
+
+impl<'a, I, T, G, F> Process<'a, T, F> for ReplaceWith<'a, I, T, G>
+    where I: Iterator<Item = WindowMut<'a, T>>,
+          T: UtahNum,
+          G: Fn(&T) -> Option<T>,
+          F: Fn(T) -> T
+{
+    fn impute(self, strategy: ImputeStrategy<T>) -> Impute<'a, Self, T>
+        where Self: Sized + Iterator<Item = WindowMut<'a, T>>
+    {
+        let other = self.other.clone();
+        let axis = self.axis.clone();
+        Impute::new(self, strategy, other, axis)
+    }
+
+    fn to_mut_df(self) -> DataFrameMut<'a, T>
+        where Self: Sized + Iterator<Item = WindowMut<'a, T>>
+    {
+        let other = self.other.clone();
+        let axis = self.axis.clone();
+        collect_windows_mut(self, other, axis)
+    }
+
+    fn mapdf(self, f: F) -> MapDF<'a, T, Self, F> {
+        let other = self.other.clone();
+        let axis = self.axis.clone();
+        MapDF::new(self, f, other, axis)
+    }
+}
+
+impl<'a, I, T, G> ToDataFrame<'a, WindowMut<'a, T>, T> for ReplaceWith<'a, I, T, G>
+    where I: Iterator<Item = WindowMut<'a, T>>,
+          T: UtahNum,
+          G: Fn(&T) -> Option<T>
+{
+    fn as_df(self) -> Result<DataFrame<T>> {
+        let other = self.other.clone();
+        let axis = self.axis.clone();
+        Ok(collect_windows(self, other, axis))
+    }
+
+    fn as_matrix(self) -> Result<Matrix<T>> {
+        Ok(self.as_df()?.data)
+    }
+
+    fn as_array(self) -> Result<Row<T>> {
+        let mut c = Vec::new();
+        for (_, j) in self {
+            c.extend(j.iter().cloned());
+        }
+        Ok(Array::from_vec(c))
     }
 }
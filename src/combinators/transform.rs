@@ -3,12 +3,14 @@
 use util::types::*;
 use std::iter::Iterator;
 use itertools::{put_back, PutBack};
-use ndarray::{Array, ArrayView1};
+use ndarray::{Array, ArrayView1, ArrayViewMut1};
 use combinators::aggregate::*;
 use util::traits::*;
 use dataframe::*;
 use std::fmt::Debug;
+use std::ops::{Index, IndexMut};
 use util::error::*;
+use util::helpers::windows_df;
 
 
 #[derive(Clone, Debug)]
@@ -151,10 +153,360 @@ impl<'a, I, T> Iterator for Append<'a, I, T>
     fn next(&mut self) -> Option<Self::Item> {
         self.new_data.next()
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.new_data.size_hint()
+    }
+}
+
+impl<'a, I, T> ExactSizeIterator for Append<'a, I, T>
+    where I: ExactSizeIterator<Item = Window<'a, T>>,
+          T: UtahNum
+{
+}
+
+
+
+
+
+/// Insert a new row or column at a given position. Positions past the end insert at the end.
+#[derive(Clone)]
+pub struct Insert<'a, I, T: 'a>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum
+{
+    pub data: I,
+    pub pos: usize,
+    pub new_data: Option<Window<'a, T>>,
+    pub other: Vec<String>,
+    pub axis: UtahAxis,
+}
+
+impl<'a, I, T> Insert<'a, I, T>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum
+{
+    pub fn new(df: I,
+               pos: usize,
+               name: String,
+               data: ArrayView1<'a, T>,
+               other: Vec<String>,
+               axis: UtahAxis)
+               -> Insert<'a, I, T> {
+        Insert {
+            data: df,
+            pos: pos,
+            new_data: Some((name, data)),
+            other: other,
+            axis: axis,
+        }
+    }
+}
+
+impl<'a, I, T> Iterator for Insert<'a, I, T>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum
+{
+    type Item = Window<'a, T>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos == 0 && self.new_data.is_some() {
+            return self.new_data.take();
+        }
+        match self.data.next() {
+            Some(x) => {
+                self.pos = self.pos.saturating_sub(1);
+                Some(x)
+            }
+            None => self.new_data.take(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let extra = if self.new_data.is_some() { 1 } else { 0 };
+        let (lo, hi) = self.data.size_hint();
+        (lo + extra, hi.map(|x| x + extra))
+    }
+}
+
+impl<'a, I, T> ExactSizeIterator for Insert<'a, I, T>
+    where I: ExactSizeIterator<Item = Window<'a, T>>,
+          T: UtahNum
+{
+}
+
+
+/// A row of a dataframe whose entries can be looked up by column name.
+#[derive(Clone, Debug)]
+pub struct RowView<'a, T: 'a>
+    where T: UtahNum
+{
+    pub columns: &'a [String],
+    pub values: ArrayView1<'a, T>,
+}
+
+impl<'a, T> RowView<'a, T>
+    where T: UtahNum
+{
+    /// The entry of the row in a column, if the column exists.
+    pub fn get(&self, name: &str) -> Option<&T> {
+        self.columns.iter().position(|x| x == name).map(|i| &self.values[i])
+    }
+}
+
+impl<'a, 'b, T> Index<&'b str> for RowView<'a, T>
+    where T: UtahNum
+{
+    type Output = T;
+
+    /// The entry of the row in a column. Panics if the column does not exist.
+    fn index(&self, name: &'b str) -> &T {
+        match self.get(name) {
+            Some(x) => x,
+            None => panic!("invalid column name: '{}'", name),
+        }
+    }
+}
+
+/// A mutable view of a single row or column, with its entries addressed by label.
+pub struct RowViewMut<'a, T: 'a>
+    where T: UtahNum
+{
+    pub labels: &'a [String],
+    pub values: ArrayViewMut1<'a, T>,
+}
+
+impl<'a, T> RowViewMut<'a, T>
+    where T: UtahNum
+{
+    /// The entry at a label, if the label exists.
+    pub fn get(&self, label: &str) -> Option<&T> {
+        self.labels.iter().position(|x| x == label).map(|i| &self.values[i])
+    }
+
+    /// The entry at a label, for editing in place, if the label exists.
+    pub fn get_mut(&mut self, label: &str) -> Option<&mut T> {
+        match self.labels.iter().position(|x| x == label) {
+            Some(i) => Some(&mut self.values[i]),
+            None => None,
+        }
+    }
+}
+
+impl<'a, 'b, T> Index<&'b str> for RowViewMut<'a, T>
+    where T: UtahNum
+{
+    type Output = T;
+
+    /// The entry at a label. Panics if the label does not exist.
+    fn index(&self, label: &'b str) -> &T {
+        match self.get(label) {
+            Some(x) => x,
+            None => panic!("invalid label: '{}'", label),
+        }
+    }
+}
+
+impl<'a, 'b, T> IndexMut<&'b str> for RowViewMut<'a, T>
+    where T: UtahNum
+{
+    /// The entry at a label, for editing in place. Panics if the label does not exist.
+    fn index_mut(&mut self, label: &'b str) -> &mut T {
+        match self.get_mut(label) {
+            Some(x) => x,
+            None => panic!("invalid label: '{}'", label),
+        }
+    }
 }
 
+/// Extend each row with a new entry computed from the rest of the row.
+#[derive(Clone)]
+pub struct WithColumn<'a, I, T: 'a, F>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum,
+          F: Fn(&RowView<'a, T>) -> T
+{
+    pub data: I,
+    pub columns: &'a [String],
+    pub name: String,
+    pub func: F,
+}
+
+impl<'a, I, T, F> WithColumn<'a, I, T, F>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum,
+          F: Fn(&RowView<'a, T>) -> T
+{
+    pub fn new(df: I, columns: &'a [String], name: String, f: F) -> WithColumn<'a, I, T, F> {
+        WithColumn {
+            data: df,
+            columns: columns,
+            name: name,
+            func: f,
+        }
+    }
+}
+
+impl<'a, I, T, F> Iterator for WithColumn<'a, I, T, F>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum,
+          F: Fn(&RowView<'a, T>) -> T
+{
+    type Item = (String, Row<T>);
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.data.next() {
+            None => None,
+            Some((name, dat)) => {
+                let row = RowView {
+                    columns: self.columns,
+                    values: dat,
+                };
+                let x = (self.func)(&row);
+                let mut v: Vec<T> = dat.iter().cloned().collect();
+                v.push(x);
+                Some((name, Array::from_vec(v)))
+            }
+        }
+    }
+}
+
+impl<'a, I, T, F> ToDataFrame<'a, (String, Row<T>), T> for WithColumn<'a, I, T, F>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum,
+          F: Fn(&RowView<'a, T>) -> T
+{
+    fn as_df(self) -> Result<DataFrame<T>> {
+        let mut columns = self.columns.to_vec();
+        columns.push(self.name.clone());
+        let ncols = columns.len();
+        let mut c = Vec::new();
+        let mut n = Vec::new();
+        for (i, j) in self {
+            c.extend(j.iter().cloned());
+            n.push(i);
+        }
+        Ok(DataFrame {
+            columns: columns,
+            data: Array::from_shape_vec((n.len(), ncols), c).unwrap(),
+            index: n,
+            index_sorted: false,
+        })
+    }
+
+    fn as_matrix(self) -> Result<Matrix<T>> {
+        Ok(self.as_df()?.data)
+    }
+
+    fn as_array(self) -> Result<Row<T>> {
+        let mut c = Vec::new();
+        for (_, j) in self {
+            c.extend(j.iter().cloned());
+        }
+        Ok(Array::from_vec(c))
+    }
+}
+
+
+/// Map each row or column to a new row or column.
+#[derive(Clone)]
+pub struct Apply<'a, I, T: 'a, F>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum,
+          F: Fn(ArrayView1<'a, T>) -> Vec<T>
+{
+    pub data: I,
+    pub func: F,
+    pub other: Vec<String>,
+    pub axis: UtahAxis,
+}
 
+impl<'a, I, T, F> Apply<'a, I, T, F>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum,
+          F: Fn(ArrayView1<'a, T>) -> Vec<T>
+{
+    pub fn new(df: I, f: F, other: Vec<String>, axis: UtahAxis) -> Apply<'a, I, T, F> {
+        Apply {
+            data: df,
+            func: f,
+            other: other,
+            axis: axis,
+        }
+    }
+}
 
+impl<'a, I, T, F> Iterator for Apply<'a, I, T, F>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum,
+          F: Fn(ArrayView1<'a, T>) -> Vec<T>
+{
+    type Item = (String, Row<T>);
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.data.next() {
+            None => None,
+            Some((name, dat)) => Some((name, Array::from_vec((self.func)(dat)))),
+        }
+    }
+}
+
+impl<'a, I, T, F> ToDataFrame<'a, (String, Row<T>), T> for Apply<'a, I, T, F>
+    where I: Iterator<Item = Window<'a, T>>,
+          T: UtahNum,
+          F: Fn(ArrayView1<'a, T>) -> Vec<T>
+{
+    /// Reassemble the results into a dataframe. They must all have the same length, and keep
+    /// the original labels if that length is unchanged.
+    fn as_df(self) -> Result<DataFrame<T>> {
+        let other = self.other.clone();
+        let axis = self.axis.clone();
+        let mut c = Vec::new();
+        let mut n = Vec::new();
+        let mut len = None;
+        for (i, j) in self {
+            if *len.get_or_insert(j.len()) != j.len() {
+                return Err(ErrorKind::RowShapeMismatch.into());
+            }
+            c.extend(j.iter().cloned());
+            n.push(i);
+        }
+        let len = len.unwrap_or(other.len());
+        let other = if len == other.len() {
+            other
+        } else {
+            (0..len).map(|x| x.to_string()).collect()
+        };
+        let d = Array::from_shape_vec((n.len(), len), c).unwrap();
+        match axis {
+            UtahAxis::Row => {
+                Ok(DataFrame {
+                    columns: other,
+                    data: d,
+                    index: n,
+                    index_sorted: false,
+                })
+            }
+            UtahAxis::Column => {
+                Ok(DataFrame {
+                    columns: n,
+                    data: d.reversed_axes(),
+                    index: other,
+                    index_sorted: false,
+                })
+            }
+        }
+    }
+
+    fn as_matrix(self) -> Result<Matrix<T>> {
+        Ok(self.as_df()?.data)
+    }
+
+    fn as_array(self) -> Result<Row<T>> {
+        let mut c = Vec::new();
+        for (_, j) in self {
+            c.extend(j.iter().cloned());
+        }
+        Ok(Array::from_vec(c))
+    }
+}
 
 
 
@@ -484,52 +836,13 @@ impl<'a, I, T> ToDataFrame<'a, Window<'a, T>, T> for Remove<'a, I, T>
           T: UtahNum
 {
     fn as_df(self) -> Result<DataFrame<T>> {
-        let s = self.clone();
         let other = self.other.clone();
         let axis = self.axis.clone();
-        let mut c = Vec::new();
-        let mut n = Vec::new();
-        let res_dim = match axis {
-            UtahAxis::Row => (s.fold(0, |acc, _| acc + 1), other.len()),
-            UtahAxis::Column => (other.len(), s.fold(0, |acc, _| acc + 1)),
-        };
-
-        for (i, j) in self {
-            c.extend(j.iter().map(|x| x.to_owned()));
-            n.push(i.to_owned());
-        }
-
-        let d = Array::from_shape_vec(res_dim, c).unwrap();
-        match axis {
-            UtahAxis::Row => {
-                let df = DataFrame::new(d).columns(&other[..])?.index(&n[..])?;
-                Ok(df)
-            }
-            UtahAxis::Column => {
-                let df = DataFrame::new(d).columns(&n[..])?.index(&other[..])?;
-                Ok(df)
-            }
-
-        }
+        Ok(windows_df(self.map(|(i, j)| (i, j.to_owned())), other, axis))
     }
-    fn as_matrix(self) -> Result<Matrix<T>> {
-        let s = self.clone();
-        let other = self.other.clone();
-        let axis = self.axis.clone();
-
-        let mut c = Vec::new();
-        let mut n = Vec::new();
-        let res_dim = match axis {
-            UtahAxis::Row => (s.fold(0, |acc, _| acc + 1), other.len()),
-            UtahAxis::Column => (other.len(), s.fold(0, |acc, _| acc + 1)),
-        };
 
-        for (i, j) in self {
-            c.extend(j.iter().map(|x| x.to_owned()));
-            n.push(i.to_owned());
-        }
-
-        Ok(Array::from_shape_vec(res_dim, c).unwrap())
+    fn as_matrix(self) -> Result<Matrix<T>> {
+        Ok(self.as_df()?.data)
     }
 
     fn as_array(self) -> Result<Row<T>> {
@@ -548,53 +861,13 @@ impl<'a, I, T> ToDataFrame<'a, Window<'a, T>, T> for Append<'a, I, T>
           T: UtahNum
 {
     fn as_df(self) -> Result<DataFrame<T>> {
-        let s = self.clone();
         let other = self.other.clone();
         let axis = self.axis.clone();
-        let mut c = Vec::new();
-        let mut n = Vec::new();
-        let res_dim = match axis {
-            UtahAxis::Row => (s.fold(0, |acc, _| acc + 1), other.len()),
-            UtahAxis::Column => (other.len(), s.fold(0, |acc, _| acc + 1)),
-        };
-
-        for (i, j) in self {
-            c.extend(j.iter().map(|x| x.to_owned()));
-            n.push(i.to_owned());
-        }
-
-
-        let d = Array::from_shape_vec(res_dim, c).unwrap();
-        match axis {
-            UtahAxis::Row => {
-                let df = DataFrame::new(d).columns(&other[..])?.index(&n[..])?;
-                Ok(df)
-            }
-            UtahAxis::Column => {
-                let df = DataFrame::new(d).columns(&n[..])?.index(&other[..])?;
-                Ok(df)
-            }
-
-        }
+        Ok(windows_df(self.map(|(i, j)| (i, j.to_owned())), other, axis))
     }
-    fn as_matrix(self) -> Result<Matrix<T>> {
-        let s = self.clone();
-        let other = self.other.clone();
-        let axis = self.axis.clone();
-
-        let mut c = Vec::new();
-        let mut n = Vec::new();
-        let res_dim = match axis {
-            UtahAxis::Row => (s.fold(0, |acc, _| acc + 1), other.len()),
-            UtahAxis::Column => (other.len(), s.fold(0, |acc, _| acc + 1)),
-        };
-
-        for (i, j) in self {
-            c.extend(j.iter().map(|x| x.to_owned()));
-            n.push(i.to_owned());
-        }
 
-        Ok(Array::from_shape_vec(res_dim, c).unwrap())
+    fn as_matrix(self) -> Result<Matrix<T>> {
+        Ok(self.as_df()?.data)
     }
 
     fn as_array(self) -> Result<Row<T>> {
@@ -616,57 +889,40 @@ impl<'a, I, T> ToDataFrame<'a, Window<'a, T>, T> for Select<'a, I, T>
           T: UtahNum
 {
     fn as_df(self) -> Result<DataFrame<T>> {
-        let s = self.clone();
         let other = self.other.clone();
         let axis = self.axis.clone();
-        let mut c = Vec::new();
-        let mut n = Vec::new();
-        let res_dim = match axis {
-            UtahAxis::Row => (s.fold(0, |acc, _| acc + 1), other.len()),
-            UtahAxis::Column => (other.len(), s.fold(0, |acc, _| acc + 1)),
-        };
+        Ok(windows_df(self.map(|(i, j)| (i, j.to_owned())), other, axis))
+    }
 
-        for (i, j) in self {
+    fn as_matrix(self) -> Result<Matrix<T>> {
+        Ok(self.as_df()?.data)
+    }
+
+    fn as_array(self) -> Result<Row<T>> {
 
+        let mut c = Vec::new();
+        for (_, j) in self {
             c.extend(j.iter().map(|x| x.to_owned()));
-            n.push(i.to_owned());
         }
+        Ok(Array::from_vec(c))
+    }
+}
 
-        let d = Array::from_shape_vec(res_dim, c).unwrap();
-        match axis {
-            UtahAxis::Row => {
-                let df = DataFrame::new(d).columns(&other[..])?.index(&n[..])?;
-                Ok(df)
-            }
-            UtahAxis::Column => {
-                let df = DataFrame::new(d).columns(&n[..])?.index(&other[..])?;
-                Ok(df)
-            }
 
-        }
-    }
-    fn as_matrix(self) -> Result<Matrix<T>> {
-        let s = self.clone();
+impl<'a, T> ToDataFrame<'a, Window<'a, T>, T> for DataFrameIterator<'a, T>
+    where T: UtahNum
+{
+    fn as_df(self) -> Result<DataFrame<T>> {
         let other = self.other.clone();
         let axis = self.axis.clone();
+        Ok(windows_df(self.map(|(i, j)| (i, j.to_owned())), other, axis))
+    }
 
-        let mut c = Vec::new();
-        let mut n = Vec::new();
-        let res_dim = match axis {
-            UtahAxis::Row => (s.fold(0, |acc, _| acc + 1), other.len()),
-            UtahAxis::Column => (other.len(), s.fold(0, |acc, _| acc + 1)),
-        };
-
-        for (i, j) in self {
-            c.extend(j.iter().map(|x| x.to_owned()));
-            n.push(i.to_owned());
-        }
-
-        Ok(Array::from_shape_vec(res_dim, c).unwrap())
+    fn as_matrix(self) -> Result<Matrix<T>> {
+        Ok(self.as_df()?.data)
     }
 
     fn as_array(self) -> Result<Row<T>> {
-
         let mut c = Vec::new();
         for (_, j) in self {
             c.extend(j.iter().map(|x| x.to_owned()));
@@ -675,64 +931,124 @@ impl<'a, I, T> ToDataFrame<'a, Window<'a, T>, T> for Select<'a, I, T>
     }
 }
 
+impl<'a, I, T> Aggregate<'a, T> for Insert<'a, I, T>
+    where I: Iterator<Item = Window<'a, T>> + Clone,
+          T: UtahNum
+{
+    fn sumdf(self) -> Sum<'a, Self, T>
+        where Self: Sized + Iterator<Item = Window<'a, T>>
+    {
+        let other = self.other.clone();
+        let axis = self.axis.clone();
+        Sum::new(self, other, axis)
+    }
 
-impl<'a, T> ToDataFrame<'a, Window<'a, T>, T> for DataFrameIterator<'a, T>
-    where T: UtahNum
+    fn maxdf(self) -> Max<'a, Self, T>
+        where Self: Sized + Iterator<Item = Window<'a, T>>
+    {
+        let other = self.other.clone();
+        let axis = self.axis.clone();
+        Max::new(self, other, axis)
+    }
+
+    fn mindf(self) -> Min<'a, Self, T>
+        where Self: Sized + Iterator<Item = Window<'a, T>>
+    {
+        let other = self.other.clone();
+        let axis = self.axis.clone();
+        Min::new(self, other, axis)
+    }
+
+    fn mean(self) -> Mean<'a, Self, T>
+        where Self: Sized + Iterator<Item = Window<'a, T>>
+    {
+        let other = self.other.clone();
+        let axis = self.axis.clone();
+        Mean::new(self, other, axis)
+    }
+}
+
+impl<'a, I, T> Transform<'a, T> for Insert<'a, I, T>
+    where I: Iterator<Item = Window<'a, T>> + Clone,
+          T: UtahNum
+{
+    fn select<U: ?Sized>(self, names: &'a [&'a U]) -> Select<'a, Self, T>
+        where Self: Sized + Iterator<Item = Window<'a, T>> + Clone,
+              String: From<&'a U>
+    {
+        let other = self.other.clone();
+        let axis = self.axis.clone();
+        let names = names.iter()
+            .map(|x| String::from(*x))
+            .collect();
+        Select::new(self, names, other, axis)
+    }
+
+    fn remove<U: ?Sized>(self, names: &'a [&'a U]) -> Remove<'a, Self, T>
+        where Self: Sized + Iterator<Item = Window<'a, T>> + Clone,
+              String: From<&'a U>
+    {
+        let other = self.other.clone();
+        let axis = self.axis.clone();
+        let names = names.iter()
+            .map(|x| String::from(*x))
+            .collect();
+        Remove::new(self, names, other, axis)
+    }
+
+    fn append<U: ?Sized>(self, name: &'a U, data: ArrayView1<'a, T>) -> Append<'a, Self, T>
+        where Self: Sized + Iterator<Item = Window<'a, T>> + Clone,
+              String: From<&'a U>
+    {
+        let other = self.other.clone();
+        let axis = self.axis.clone();
+        let name = String::from(name);
+        Append::new(self, name, data, other, axis)
+    }
+}
+
+impl<'a, I, T> ToDataFrame<'a, Window<'a, T>, T> for Insert<'a, I, T>
+    where I: Iterator<Item = Window<'a, T>> + Clone,
+          T: UtahNum
 {
     fn as_df(self) -> Result<DataFrame<T>> {
-        let s = self.clone();
         let other = self.other.clone();
         let axis = self.axis.clone();
         let mut c = Vec::new();
         let mut n = Vec::new();
-        let res_dim = match axis {
-            UtahAxis::Row => (s.fold(0, |acc, _| acc + 1), other.len()),
-            UtahAxis::Column => (other.len(), s.fold(0, |acc, _| acc + 1)),
-        };
-
         for (i, j) in self {
-            c.extend(j.iter().map(|x| x.to_owned()));
-            n.push(i.to_owned());
+            c.extend(j.iter().cloned());
+            n.push(i);
         }
-
-        let d = Array::from_shape_vec(res_dim, c).unwrap();
+        let d = Array::from_shape_vec((n.len(), other.len()), c).unwrap();
         match axis {
             UtahAxis::Row => {
-                let df = DataFrame::new(d).columns(&other[..])?.index(&n[..])?;
-                Ok(df)
+                Ok(DataFrame {
+                    columns: other,
+                    data: d,
+                    index: n,
+                    index_sorted: false,
+                })
             }
             UtahAxis::Column => {
-                let df = DataFrame::new(d).columns(&n[..])?.index(&other[..])?;
-                Ok(df)
+                Ok(DataFrame {
+                    columns: n,
+                    data: d.reversed_axes(),
+                    index: other,
+                    index_sorted: false,
+                })
             }
-
         }
     }
-    fn as_matrix(self) -> Result<Matrix<T>> {
-        let s = self.clone();
-        let other = self.other.clone();
-        let axis = self.axis.clone();
-
-        let mut c = Vec::new();
-        let mut n = Vec::new();
-        let res_dim = match axis {
-            UtahAxis::Row => (s.fold(0, |acc, _| acc + 1), other.len()),
-            UtahAxis::Column => (other.len(), s.fold(0, |acc, _| acc + 1)),
-        };
-
-        for (i, j) in self {
-            c.extend(j.iter().map(|x| x.to_owned()));
-            n.push(i.to_owned());
-        }
-
-        Ok(Array::from_shape_vec(res_dim, c).unwrap())
 
+    fn as_matrix(self) -> Result<Matrix<T>> {
+        Ok(self.as_df()?.data)
     }
 
     fn as_array(self) -> Result<Row<T>> {
         let mut c = Vec::new();
         for (_, j) in self {
-            c.extend(j.iter().map(|x| x.to_owned()));
+            c.extend(j.iter().cloned());
         }
         Ok(Array::from_vec(c))
     }
@@ -0,0 +1,603 @@
+//! Utah window combinators.
+
+use util::types::*;
+use util::traits::*;
+use util::error::*;
+use std::iter::Iterator;
+use std::collections::VecDeque;
+use dataframe::*;
+use ndarray::Array;
+use util::helpers::{count, windows_df};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WindowStat {
+    Min,
+    Max,
+    Sum,
+    Mean,
+    /// Sample variance.
+    Var,
+}
+
+/// A rolling window over the rows of a dataframe, or an expanding one that grows from the first
+/// row, waiting for a statistic to compute.
+#[derive(Clone)]
+pub struct Rolling<'a, T: 'a>
+    where T: UtahNum
+{
+    data: DFIter<'a, T>,
+    window: Option<usize>,
+    min_periods: usize,
+    other: Vec<String>,
+}
+
+impl<'a, T> Rolling<'a, T>
+    where T: UtahNum
+{
+    pub fn new(df: DFIter<'a, T>, window: usize, other: Vec<String>) -> Rolling<'a, T> {
+        Rolling {
+            data: df,
+            window: Some(window),
+            min_periods: 1,
+            other: other,
+        }
+    }
+
+    /// An expanding window, whose statistics are empty until it holds `min_periods` non-empty
+    /// values.
+    pub fn expanding(df: DFIter<'a, T>, min_periods: usize, other: Vec<String>) -> Rolling<'a, T> {
+        Rolling {
+            data: df,
+            window: None,
+            min_periods: min_periods,
+            other: other,
+        }
+    }
+
+    /// Rolling minimum of each column.
+    pub fn min(self) -> RollingIter<'a, T> {
+        RollingIter::new(self, WindowStat::Min)
+    }
+
+    /// Rolling maximum of each column.
+    pub fn max(self) -> RollingIter<'a, T> {
+        RollingIter::new(self, WindowStat::Max)
+    }
+
+    /// Rolling sum of each column.
+    pub fn sum(self) -> RollingIter<'a, T> {
+        RollingIter::new(self, WindowStat::Sum)
+    }
+
+    /// Rolling mean of each column.
+    pub fn mean(self) -> RollingIter<'a, T> {
+        RollingIter::new(self, WindowStat::Mean)
+    }
+
+    /// Rolling sample variance of each column.
+    pub fn var(self) -> RollingIter<'a, T> {
+        RollingIter::new(self, WindowStat::Var)
+    }
+
+    /// Rolling sample standard deviation of each column.
+    pub fn std(self) -> RollingIter<'a, T>
+        where T: From<f64> + Into<f64>
+    {
+        let mut iter = RollingIter::new(self, WindowStat::Var);
+        iter.finish = Some(sqrt::<T>);
+        iter
+    }
+}
+
+fn sqrt<T>(x: T) -> T
+    where T: From<f64> + Into<f64>
+{
+    T::from(x.into().sqrt())
+}
+
+/// Yields each column of a dataframe with a rolling statistic applied. For a bounded window, the
+/// first `window - 1` entries of each column are empty.
+#[derive(Clone)]
+pub struct RollingIter<'a, T: 'a>
+    where T: UtahNum
+{
+    data: DFIter<'a, T>,
+    window: Option<usize>,
+    min_periods: usize,
+    stat: WindowStat,
+    finish: Option<fn(T) -> T>,
+    other: Vec<String>,
+}
+
+impl<'a, T> RollingIter<'a, T>
+    where T: UtahNum
+{
+    pub fn new(rolling: Rolling<'a, T>, stat: WindowStat) -> RollingIter<'a, T> {
+        RollingIter {
+            data: rolling.data,
+            window: rolling.window,
+            min_periods: rolling.min_periods,
+            stat: stat,
+            finish: None,
+            other: rolling.other,
+        }
+    }
+}
+
+/// Whether the value at `i` has left a window ending at `j`, if the window is bounded.
+fn left(window: Option<usize>, i: usize, j: usize) -> bool {
+    window.map_or(false, |w| j - i >= w)
+}
+
+/// Whether the window ending at `i` is not yet full, if the window is bounded.
+fn filling(window: Option<usize>, i: usize) -> bool {
+    window.map_or(false, |w| i + 1 < w)
+}
+
+/// Rolling extremum with a monotonic deque, in O(n) for the whole column. `keep(a, b)` is true
+/// when a value `a` earlier in the window dominates a later value `b`. Empty values are skipped,
+/// and windows with no values are empty.
+fn rolling_extremum<T, F>(values: &[T], window: Option<usize>, keep: F) -> Vec<T>
+    where T: UtahNum,
+          F: Fn(&T, &T) -> bool
+{
+    let mut out = vec![T::empty(); values.len()];
+    let mut deque: VecDeque<usize> = VecDeque::new();
+    if window == Some(0) {
+        return out;
+    }
+    for (i, x) in values.iter().enumerate() {
+        if !x.is_empty() {
+            while deque.back().map_or(false, |&b| !keep(&values[b], x)) {
+                deque.pop_back();
+            }
+            deque.push_back(i);
+        }
+        while deque.front().map_or(false, |&f| left(window, f, i)) {
+            deque.pop_front();
+        }
+        if !filling(window, i) {
+            if let Some(&f) = deque.front() {
+                out[i] = values[f].clone();
+            }
+        }
+    }
+    out
+}
+
+/// Running moments of the non-empty values of a window. Deviations are summed from `shift`, a
+/// value of the window, so that the variance does not cancel when the values are large compared
+/// to their spread.
+#[derive(Clone)]
+struct Moments<T> {
+    n: usize,
+    total: T,
+    shift: T,
+    sum: T,
+    squares: T,
+}
+
+impl<T> Moments<T>
+    where T: UtahNum + PartialOrd
+{
+    fn new(shift: T) -> Moments<T> {
+        Moments {
+            n: 0,
+            total: T::zero(),
+            shift: shift,
+            sum: T::zero(),
+            squares: T::zero(),
+        }
+    }
+
+    /// The moments of `values` from scratch, shifted by the last non-empty one.
+    fn of(values: &[T]) -> Moments<T> {
+        let shift = values.iter().rev().find(|x| !x.is_empty()).cloned().unwrap_or_else(T::zero);
+        let mut m = Moments::new(shift);
+        for x in values.iter().filter(|x| !x.is_empty()) {
+            m.add(x.clone());
+        }
+        m
+    }
+
+    fn add(&mut self, x: T) {
+        let d = x.clone() - self.shift.clone();
+        self.n += 1;
+        self.total = self.total.clone() + x;
+        self.sum = self.sum.clone() + d.clone();
+        self.squares = self.squares.clone() + d.clone() * d;
+    }
+
+    fn remove(&mut self, x: T) {
+        let d = x.clone() - self.shift.clone();
+        self.n -= 1;
+        self.total = self.total.clone() - x;
+        self.sum = self.sum.clone() - d.clone();
+        self.squares = self.squares.clone() - d.clone() * d;
+    }
+
+    /// Whether the running sums are still numbers. An infinite value leaving the window leaves
+    /// them NaN.
+    fn is_number(&self) -> bool {
+        self.total == self.total && self.sum == self.sum && self.squares == self.squares
+    }
+
+    fn stat(&self, stat: WindowStat) -> T {
+        match stat {
+            WindowStat::Sum if self.n > 0 => self.total.clone(),
+            WindowStat::Mean if self.n > 0 => self.total.clone() / count(self.n),
+            WindowStat::Var if self.n > 1 => {
+                let n = count(self.n);
+                let v = (self.squares.clone() - self.sum.clone() * self.sum.clone() / n) /
+                        count(self.n - 1);
+                // Rounding can leave the variance of equal values slightly below zero.
+                if v < T::zero() { T::zero() } else { v }
+            }
+            _ => T::empty(),
+        }
+    }
+}
+
+/// Rolling sum, mean or variance, updating running sums as values enter and leave the window.
+/// The sums are recomputed, with a fresh shift, whenever as many values have entered as they
+/// last covered, which keeps the cost linear overall, and as soon as a bounded window's sums stop
+/// being numbers, so an infinite value only affects the windows that hold it. Empty values are
+/// skipped, and windows with too few values are empty.
+fn rolling_moments<T>(values: &[T], window: Option<usize>, stat: WindowStat) -> Vec<T>
+    where T: UtahNum + PartialOrd
+{
+    let mut out = vec![T::empty(); values.len()];
+    let mut m = Moments::new(T::zero());
+    for (i, x) in values.iter().enumerate() {
+        if !x.is_empty() {
+            m.add(x.clone());
+        }
+        match window {
+            Some(w) if w > 0 && i >= w => {
+                let y = &values[i - w];
+                if !y.is_empty() {
+                    m.remove(y.clone());
+                }
+            }
+            _ => {}
+        }
+        let rebase = match window {
+            Some(w) => w > 0 && (i % w == 0 || !m.is_number()),
+            None => (i + 1).is_power_of_two(),
+        };
+        if rebase {
+            let start = window.map_or(0, |w| (i + 1).saturating_sub(w));
+            m = Moments::of(&values[start..i + 1]);
+        }
+        if filling(window, i) || window == Some(0) {
+            continue;
+        }
+        out[i] = m.stat(stat);
+    }
+    out
+}
+
+/// Empty the statistics of the windows holding fewer than `min_periods` non-empty values.
+fn too_few<T>(values: &[T], window: Option<usize>, min_periods: usize, mut out: Vec<T>) -> Vec<T>
+    where T: UtahNum
+{
+    let mut n = 0;
+    for (i, x) in values.iter().enumerate() {
+        if !x.is_empty() {
+            n += 1;
+        }
+        match window {
+            Some(w) if i >= w && !values[i - w].is_empty() => n -= 1,
+            _ => {}
+        }
+        if n < min_periods {
+            out[i] = T::empty();
+        }
+    }
+    out
+}
+
+impl<'a, T> Iterator for RollingIter<'a, T>
+    where T: UtahNum + PartialOrd
+{
+    type Item = (String, Row<T>);
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.data.next() {
+            None => None,
+            Some((name, dat)) => {
+                let values: Vec<T> = dat.iter().cloned().collect();
+                let res = match self.stat {
+                    WindowStat::Min => rolling_extremum(&values[..], self.window, |a, b| a < b),
+                    WindowStat::Max => rolling_extremum(&values[..], self.window, |a, b| a > b),
+                    stat => rolling_moments(&values[..], self.window, stat),
+                };
+                let res = if self.min_periods > 1 {
+                    too_few(&values[..], self.window, self.min_periods, res)
+                } else {
+                    res
+                };
+                let res = match self.finish {
+                    Some(f) => {
+                        res.into_iter().map(|x| if x.is_empty() { x } else { f(x) }).collect()
+                    }
+                    None => res,
+                };
+                Some((name, Array::from_vec(res)))
+            }
+        }
+    }
+}
+
+/// Collect owned columns into a matrix, along with their names.
+fn collect_columns<I, T>(columns: I, nrows: usize) -> (Vec<String>, Matrix<T>)
+    where I: Iterator<Item = (String, Row<T>)>,
+          T: UtahNum
+{
+    let (names, cols): (Vec<String>, Vec<Row<T>>) = columns.unzip();
+    let mut c = Vec::with_capacity(nrows * cols.len());
+    for r in 0..nrows {
+        c.extend(cols.iter().map(|col| col[r].clone()));
+    }
+    (names, Array::from_shape_vec((nrows, cols.len()), c).unwrap())
+}
+
+impl<'a, T> ToDataFrame<'a, (String, Row<T>), T> for RollingIter<'a, T>
+    where T: UtahNum + PartialOrd
+{
+    fn as_df(self) -> Result<DataFrame<T>> {
+        let other = self.other.clone();
+        let (n, d) = collect_columns(self, other.len());
+        Ok(DataFrame {
+            columns: n,
+            data: d,
+            index: other,
+            index_sorted: false,
+        })
+    }
+
+    fn as_matrix(self) -> Result<Matrix<T>> {
+        let nrows = self.other.len();
+        Ok(collect_columns(self, nrows).1)
+    }
+
+    fn as_array(self) -> Result<Row<T>> {
+        let mut c = Vec::new();
+        for (_, j) in self {
+            c.extend(j.iter().cloned());
+        }
+        Ok(Array::from_vec(c))
+    }
+}
+
+/// An exponentially weighted window over the rows of a dataframe, waiting for a statistic to
+/// compute.
+#[derive(Clone)]
+pub struct Ewm<'a, T: 'a>
+    where T: UtahNum
+{
+    data: DFIter<'a, T>,
+    alpha: f64,
+    other: Vec<String>,
+}
+
+impl<'a, T> Ewm<'a, T>
+    where T: UtahNum
+{
+    /// Fails unless the smoothing factor of `decay` lies in `(0, 1]`: a span below one gives a
+    /// factor above one, and a factor of zero never moves from the first value.
+    pub fn new(df: DFIter<'a, T>, decay: Decay, other: Vec<String>) -> Result<Ewm<'a, T>> {
+        let alpha = decay.alpha();
+        if !(alpha > 0. && alpha <= 1.) {
+            return Err(ErrorKind::InvalidDecay(format!("{:?}", decay)).into());
+        }
+        Ok(Ewm {
+            data: df,
+            alpha: alpha,
+            other: other,
+        })
+    }
+
+    /// Exponentially weighted mean of each column.
+    pub fn mean(self) -> EwmIter<'a, T> {
+        EwmIter::new(self, false, None)
+    }
+
+    /// Exponentially weighted (biased) variance of each column.
+    pub fn var(self) -> EwmIter<'a, T> {
+        EwmIter::new(self, true, None)
+    }
+
+    /// Exponentially weighted (biased) standard deviation of each column.
+    pub fn std(self) -> EwmIter<'a, T>
+        where T: From<f64> + Into<f64>
+    {
+        EwmIter::new(self, true, Some(sqrt::<T>))
+    }
+}
+
+/// Yields each column of a dataframe with an exponentially weighted statistic applied. Each entry
+/// updates the statistics of the previous ones; empty entries stay empty and leave the
+/// statistics unchanged.
+#[derive(Clone)]
+pub struct EwmIter<'a, T: 'a>
+    where T: UtahNum
+{
+    data: DFIter<'a, T>,
+    alpha: f64,
+    variance: bool,
+    finish: Option<fn(T) -> T>,
+    other: Vec<String>,
+}
+
+impl<'a, T> EwmIter<'a, T>
+    where T: UtahNum
+{
+    pub fn new(ewm: Ewm<'a, T>, variance: bool, finish: Option<fn(T) -> T>) -> EwmIter<'a, T> {
+        EwmIter {
+            data: ewm.data,
+            alpha: ewm.alpha,
+            variance: variance,
+            finish: finish,
+            other: ewm.other,
+        }
+    }
+}
+
+impl<'a, T> Iterator for EwmIter<'a, T>
+    where T: UtahNum + From<f64>
+{
+    type Item = (String, Row<T>);
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.data.next() {
+            None => None,
+            Some((name, dat)) => {
+                let (a, b) = (T::from(self.alpha), T::from(1. - self.alpha));
+                let mut state: Option<(T, T)> = None;
+                let res = dat.iter().map(|x| {
+                    if x.is_empty() {
+                        return x.clone();
+                    }
+                    let (mean, var) = match state.take() {
+                        None => (x.clone(), T::zero()),
+                        Some((m, v)) => {
+                            let d = x.clone() - m.clone();
+                            (m + a.clone() * d.clone(), b.clone() * (v + a.clone() * d.clone() * d))
+                        }
+                    };
+                    state = Some((mean.clone(), var.clone()));
+                    match (self.variance, self.finish) {
+                        (false, _) => mean,
+                        (true, Some(f)) => f(var),
+                        (true, None) => var,
+                    }
+                });
+                Some((name, res.collect()))
+            }
+        }
+    }
+}
+
+impl<'a, T> ToDataFrame<'a, (String, Row<T>), T> for EwmIter<'a, T>
+    where T: UtahNum + From<f64>
+{
+    fn as_df(self) -> Result<DataFrame<T>> {
+        let other = self.other.clone();
+        let (n, d) = collect_columns(self, other.len());
+        Ok(DataFrame {
+            columns: n,
+            data: d,
+            index: other,
+            index_sorted: false,
+        })
+    }
+
+    fn as_matrix(self) -> Result<Matrix<T>> {
+        let nrows = self.other.len();
+        Ok(collect_columns(self, nrows).1)
+    }
+
+    fn as_array(self) -> Result<Row<T>> {
+        let mut c = Vec::new();
+        for (_, j) in self {
+            c.extend(j.iter().cloned());
+        }
+        Ok(Array::from_vec(c))
+    }
+}
+
+/// What a `Shift` does with the lagged values.
+#[derive(Clone, Debug, PartialEq)]
+pub enum LagOp<T> {
+    /// Move the values, filling the vacated entries with the given value.
+    Shift(T),
+    /// Subtract the lagged values from the current ones, leaving the first entries empty.
+    Diff,
+}
+
+/// Yields each row or column lagged by a number of periods. Positive periods move values
+/// forward, negative periods move them back.
+#[derive(Clone)]
+pub struct Shift<'a, T: 'a>
+    where T: UtahNum
+{
+    data: DFIter<'a, T>,
+    periods: isize,
+    op: LagOp<T>,
+    other: Vec<String>,
+    axis: UtahAxis,
+}
+
+impl<'a, T> Shift<'a, T>
+    where T: UtahNum
+{
+    pub fn new(df: DFIter<'a, T>,
+               periods: isize,
+               op: LagOp<T>,
+               other: Vec<String>,
+               axis: UtahAxis)
+               -> Shift<'a, T> {
+        Shift {
+            data: df,
+            periods: periods,
+            op: op,
+            other: other,
+            axis: axis,
+        }
+    }
+}
+
+impl<'a, T> Iterator for Shift<'a, T>
+    where T: UtahNum
+{
+    type Item = (String, Row<T>);
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.data.next() {
+            None => None,
+            Some((name, dat)) => {
+                let n = dat.len() as isize;
+                let lagged = |i: isize| {
+                    let j = i - self.periods;
+                    if j >= 0 && j < n { Some(&dat[j as usize]) } else { None }
+                };
+                let res: Vec<T> = (0..n)
+                    .map(|i| match (&self.op, lagged(i)) {
+                        (&LagOp::Shift(_), Some(x)) => x.clone(),
+                        (&LagOp::Shift(ref fill), None) => fill.clone(),
+                        (&LagOp::Diff, Some(x)) => {
+                            let y = &dat[i as usize];
+                            if x.is_empty() || y.is_empty() {
+                                T::empty()
+                            } else {
+                                y.clone() - x.clone()
+                            }
+                        }
+                        (&LagOp::Diff, None) => T::empty(),
+                    })
+                    .collect();
+                Some((name, Array::from_vec(res)))
+            }
+        }
+    }
+}
+
+impl<'a, T> ToDataFrame<'a, (String, Row<T>), T> for Shift<'a, T>
+    where T: UtahNum
+{
+    fn as_df(self) -> Result<DataFrame<T>> {
+        let other = self.other.clone();
+        let axis = self.axis.clone();
+        Ok(windows_df(self, other, axis))
+    }
+
+    fn as_matrix(self) -> Result<Matrix<T>> {
+        Ok(self.as_df()?.data)
+    }
+
+    fn as_array(self) -> Result<Row<T>> {
+        let mut c = Vec::new();
+        for (_, j) in self {
+            c.extend(j.iter().cloned());
+        }
+        Ok(Array::from_vec(c))
+    }
+}
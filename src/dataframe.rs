@@ -7,25 +7,54 @@ use ndarray::{AxisIter, AxisIterMut};
 use util::traits::*;
 use std::slice::Iter;
 use ndarray::{ArrayView1, ArrayViewMut1, Dim, Ix};
+use util::helpers::position;
+use util::rank;
+use std::cmp::Ordering;
+use std::ops::{Index, IndexMut};
+use std::mem;
+use ndarray::Array;
 
 /// A read-only dataframe.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone)]
 pub struct DataFrame<T>
     where T: UtahNum
 {
     pub columns: Vec<String>,
     pub data: Matrix<T>,
     pub index: Vec<String>,
+    /// Whether `index` is known to be in ascending `rank::compare_labels` order. Only
+    /// `sort_index` sets it; anything that builds or changes the index leaves it `false`.
+    pub index_sorted: bool,
 }
 
 /// A read-write dataframe
-#[derive(Debug, PartialEq)]
+#[derive(Debug)]
 pub struct DataFrameMut<'a, T: 'a>
     where T: UtahNum
 {
     pub columns: Vec<String>,
     pub data: MatrixMut<'a, T>,
     pub index: Vec<String>,
+    /// As `DataFrame::index_sorted`; set by `sort_index_in_place`.
+    pub index_sorted: bool,
+}
+
+/// Frames are equal when their labels and entries are; whether the index is known to be sorted
+/// does not matter.
+impl<T> PartialEq for DataFrame<T>
+    where T: UtahNum
+{
+    fn eq(&self, other: &DataFrame<T>) -> bool {
+        self.columns == other.columns && self.index == other.index && self.data == other.data
+    }
+}
+
+impl<'a, T> PartialEq for DataFrameMut<'a, T>
+    where T: 'a + UtahNum
+{
+    fn eq(&self, other: &DataFrameMut<'a, T>) -> bool {
+        self.columns == other.columns && self.index == other.index && self.data == other.data
+    }
 }
 
 
@@ -58,8 +87,15 @@ impl<'a, T> Iterator for DataFrameIterator<'a, T>
             None => None,
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.names.len().min(self.data.len());
+        (n, Some(n))
+    }
 }
 
+impl<'a, T> ExactSizeIterator for DataFrameIterator<'a, T> where T: UtahNum {}
+
 /// The read-write dataframe iterator
 pub struct DataFrameMutIterator<'a, T>
     where T: UtahNum + 'a
@@ -88,8 +124,124 @@ impl<'a, T> Iterator for DataFrameMutIterator<'a, T>
             None => None,
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.names.len().min(self.data.len());
+        (n, Some(n))
+    }
 }
 
+impl<'a, T> ExactSizeIterator for DataFrameMutIterator<'a, T> where T: UtahNum {}
+
+
+impl<T> DataFrame<T>
+    where T: UtahNum
+{
+    /// A view of a column, if the column exists.
+    ///
+    /// This stands in for `df["column"]`: `Index::index` must return a reference to something
+    /// stored in the frame, while a column of the row-major matrix is a strided view made on
+    /// demand, so the frame has no `Index<&str>`.
+    pub fn get<'a>(&'a self, column: &str) -> Option<ColumnView<'a, T>> {
+        self.columns.iter().position(|x| x == column).map(|j| self.data.column(j))
+    }
+
+    /// The entry at a row and column, if both exist.
+    pub fn get_cell(&self, row: &str, column: &str) -> Option<&T> {
+        match (self.index.iter().position(|x| x == row),
+               self.columns.iter().position(|x| x == column)) {
+            (Some(i), Some(j)) => Some(&self.data[[i, j]]),
+            _ => None,
+        }
+    }
+
+    /// The entry at a row and column, for editing in place, if both exist.
+    pub fn get_cell_mut(&mut self, row: &str, column: &str) -> Option<&mut T> {
+        match (self.index.iter().position(|x| x == row),
+               self.columns.iter().position(|x| x == column)) {
+            (Some(i), Some(j)) => Some(&mut self.data[[i, j]]),
+            _ => None,
+        }
+    }
+
+    /// Add a row at the bottom, in place. The entries are kept in a growable buffer, so pushing
+    /// many rows one at a time takes amortized constant time per entry.
+    pub fn push_row(&mut self, label: &str, values: &[T]) -> Result<()> {
+        if values.len() != self.columns.len() {
+            return Err(ErrorKind::ColumnShapeMismatch(self.columns.len().to_string(),
+                                                      values.len().to_string())
+                .into());
+        }
+        let mut d = self.take_row_major();
+        d.extend_from_slice(values);
+        self.data = Array::from_shape_vec((self.index.len() + 1, self.columns.len()), d).unwrap();
+        self.index.push(label.to_string());
+        self.index_sorted = false;
+        Ok(())
+    }
+
+    /// Add the rows of `other` at the bottom, in place. Both dataframes must have the same
+    /// columns, in the same order.
+    pub fn extend_rows(&mut self, other: &DataFrame<T>) -> Result<()> {
+        if self.columns != other.columns {
+            return Err(ErrorKind::MisalignedLabels(format!("{:?} and {:?}",
+                                                           self.columns,
+                                                           other.columns))
+                .into());
+        }
+        let mut d = self.take_row_major();
+        d.extend(other.data.iter().cloned());
+        self.index.extend(other.index.iter().cloned());
+        self.index_sorted = false;
+        self.data = Array::from_shape_vec((self.index.len(), self.columns.len()), d).unwrap();
+        Ok(())
+    }
+
+    /// Move the entries out in row-major order, reusing their buffer when they are stored
+    /// contiguously in that order, and copying them otherwise.
+    fn take_row_major(&mut self) -> Vec<T> {
+        let (n, m) = self.data.dim();
+        let start = match self.data.as_slice() {
+            Some(s) => s.as_ptr() as usize,
+            None => return self.data.iter().cloned().collect(),
+        };
+        let empty = Array::from_shape_vec((0, m), Vec::new()).unwrap();
+        let mut d = mem::replace(&mut self.data, empty).into_raw_vec();
+        // The array may start part way into its buffer.
+        let offset = (start - d.as_ptr() as usize) / mem::size_of::<T>();
+        d.truncate(offset + n * m);
+        d.drain(..offset);
+        d
+    }
+}
+
+impl<'b, 'c, T> Index<(&'b str, &'c str)> for DataFrame<T>
+    where T: UtahNum
+{
+    type Output = T;
+
+    /// The entry at a `(row, column)` pair of labels. Panics if either label does not exist; use
+    /// `get_cell` for fallible access.
+    fn index(&self, (row, column): (&'b str, &'c str)) -> &T {
+        match self.get_cell(row, column) {
+            Some(x) => x,
+            None => panic!("no entry at row '{}', column '{}'", row, column),
+        }
+    }
+}
+
+impl<'b, 'c, T> IndexMut<(&'b str, &'c str)> for DataFrame<T>
+    where T: UtahNum
+{
+    /// The entry at a `(row, column)` pair of labels, for editing in place. Panics if either label
+    /// does not exist; use `get_cell_mut` for fallible access.
+    fn index_mut(&mut self, (row, column): (&'b str, &'c str)) -> &mut T {
+        match self.get_cell_mut(row, column) {
+            Some(x) => x,
+            None => panic!("no entry at row '{}', column '{}'", row, column),
+        }
+    }
+}
 
 impl<'a, T> DataFrameMut<'a, T>
     where T: 'a + UtahNum
@@ -97,8 +249,69 @@ impl<'a, T> DataFrameMut<'a, T>
     /// Dereference a mutable dataframe as an owned dataframe.
     pub fn to_df(self) -> Result<DataFrame<T>> {
         let d = self.data.map(|x| ((*x).clone()));
-        let df = DataFrame::new(d).columns(&self.columns[..])?.index(&self.index[..])?;
+        let mut df = DataFrame::new(d).columns(&self.columns[..])?.index(&self.index[..])?;
+        df.index_sorted = self.index_sorted;
         Ok(df)
 
     }
+
+    /// Sort the rows by the values of `columns` in place, moving each row along the cycles of the
+    /// sorting permutation so that no second matrix is allocated.
+    pub fn sort_values_in_place<U: ?Sized>(&mut self,
+                                           columns: &[&'a U],
+                                           ascending: bool,
+                                           empties: EmptyPosition)
+                                           -> Result<()>
+        where String: From<&'a U>,
+              T: PartialOrd
+    {
+        let mut keys = Vec::with_capacity(columns.len());
+        for c in columns {
+            let c: String = String::from(*c);
+            keys.push(position(&self.columns[..], &c)?);
+        }
+        let mut order: Vec<usize> = (0..self.index.len()).collect();
+        {
+            let data = &self.data;
+            order.sort_by(|&a, &b| {
+                keys.iter()
+                    .map(|&k| rank::compare(&*data[[a, k]], &*data[[b, k]], ascending, empties))
+                    .find(|o| *o != Ordering::Equal)
+                    .unwrap_or(Ordering::Equal)
+            });
+        }
+        self.permute_rows(&order[..]);
+        self.index_sorted = false;
+        Ok(())
+    }
+
+    /// Sort the rows by their index labels in place (see `rank::compare_labels`).
+    pub fn sort_index_in_place(&mut self, ascending: bool) {
+        let order = rank::sorted_label_positions(&self.index[..], ascending);
+        self.permute_rows(&order[..]);
+        self.index_sorted = ascending;
+    }
+
+    /// Move row `order[i]` to position `i`, along the cycles of the permutation.
+    fn permute_rows(&mut self, order: &[usize]) {
+        let mut visited = vec![false; order.len()];
+        for i in 0..order.len() {
+            let mut j = i;
+            while !visited[j] && order[j] != i {
+                self.swap_rows(j, order[j]);
+                visited[j] = true;
+                j = order[j];
+            }
+            visited[j] = true;
+        }
+    }
+
+    fn swap_rows(&mut self, a: usize, b: usize) {
+        for k in 0..self.columns.len() {
+            let x = (*self.data[[a, k]]).clone();
+            *self.data[[a, k]] = (*self.data[[b, k]]).clone();
+            *self.data[[b, k]] = x;
+        }
+        self.index.swap(a, b);
+    }
 }
@@ -0,0 +1,180 @@
+//! Utah column expressions
+//!
+//! Expressions describe a column in terms of other columns, e.g. `col("ask") - col("bid")`. A
+//! `VirtualFrame` attaches named expressions to a dataframe as virtual columns, which are only
+//! computed when they are read.
+
+use std::ops::{Add, Sub, Mul, Div};
+use dataframe::DataFrame;
+use ndarray::Array;
+use util::error::*;
+use util::helpers::position;
+use util::traits::UtahNum;
+use util::types::*;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expr<T>
+    where T: UtahNum
+{
+    Col(String),
+    Lit(T),
+    Add(Box<Expr<T>>, Box<Expr<T>>),
+    Sub(Box<Expr<T>>, Box<Expr<T>>),
+    Mul(Box<Expr<T>>, Box<Expr<T>>),
+    Div(Box<Expr<T>>, Box<Expr<T>>),
+}
+
+/// A reference to a column.
+pub fn col<T, U: ?Sized>(name: &U) -> Expr<T>
+    where T: UtahNum,
+          for<'b> String: From<&'b U>
+{
+    Expr::Col(name.into())
+}
+
+/// A constant.
+pub fn lit<T>(value: T) -> Expr<T>
+    where T: UtahNum
+{
+    Expr::Lit(value)
+}
+
+impl<T> Expr<T>
+    where T: UtahNum
+{
+    /// Compute the expression for every row. Columns are looked up first among the virtual
+    /// columns in `defs`, then among the columns of `df`.
+    fn eval(&self, df: &DataFrame<T>, defs: &[(String, Expr<T>)], depth: usize) -> Result<Row<T>> {
+        if depth > defs.len() {
+            return Err(ErrorKind::InvalidExpression("circular column definition".into()).into());
+        }
+        let zip = |a: &Expr<T>, b: &Expr<T>, f: fn(T, T) -> T| -> Result<Row<T>> {
+            let a = a.eval(df, defs, depth)?;
+            let b = b.eval(df, defs, depth)?;
+            let v: Vec<T> = a.iter().zip(b.iter()).map(|(x, y)| f(x.clone(), y.clone())).collect();
+            Ok(Array::from_vec(v))
+        };
+        match *self {
+            Expr::Col(ref name) => {
+                match defs.iter().find(|&&(ref n, _)| n == name) {
+                    Some(&(_, ref e)) => e.eval(df, defs, depth + 1),
+                    None => Ok(df.data.column(position(&df.columns[..], name)?).to_owned()),
+                }
+            }
+            Expr::Lit(ref x) => Ok(Array::from_elem(df.index.len(), x.clone())),
+            Expr::Add(ref a, ref b) => zip(a, b, |x, y| x + y),
+            Expr::Sub(ref a, ref b) => zip(a, b, |x, y| x - y),
+            Expr::Mul(ref a, ref b) => zip(a, b, |x, y| x * y),
+            Expr::Div(ref a, ref b) => zip(a, b, |x, y| x / y),
+        }
+    }
+}
+
+impl<T> Add for Expr<T>
+    where T: UtahNum
+{
+    type Output = Expr<T>;
+    fn add(self, rhs: Expr<T>) -> Expr<T> {
+        Expr::Add(Box::new(self), Box::new(rhs))
+    }
+}
+
+impl<T> Sub for Expr<T>
+    where T: UtahNum
+{
+    type Output = Expr<T>;
+    fn sub(self, rhs: Expr<T>) -> Expr<T> {
+        Expr::Sub(Box::new(self), Box::new(rhs))
+    }
+}
+
+impl<T> Mul for Expr<T>
+    where T: UtahNum
+{
+    type Output = Expr<T>;
+    fn mul(self, rhs: Expr<T>) -> Expr<T> {
+        Expr::Mul(Box::new(self), Box::new(rhs))
+    }
+}
+
+impl<T> Div for Expr<T>
+    where T: UtahNum
+{
+    type Output = Expr<T>;
+    fn div(self, rhs: Expr<T>) -> Expr<T> {
+        Expr::Div(Box::new(self), Box::new(rhs))
+    }
+}
+
+/// A dataframe extended with virtual columns.
+#[derive(Clone, Debug)]
+pub struct VirtualFrame<'a, T: 'a>
+    where T: UtahNum
+{
+    pub df: &'a DataFrame<T>,
+    pub defs: Vec<(String, Expr<T>)>,
+}
+
+impl<'a, T> VirtualFrame<'a, T>
+    where T: UtahNum
+{
+    pub fn new(df: &'a DataFrame<T>) -> VirtualFrame<'a, T> {
+        VirtualFrame {
+            df: df,
+            defs: Vec::new(),
+        }
+    }
+
+    /// Define another virtual column. A later definition replaces an earlier one of the same
+    /// name.
+    pub fn define<U: ?Sized>(mut self, name: &U, expr: Expr<T>) -> VirtualFrame<'a, T>
+        where for<'b> String: From<&'b U>
+    {
+        let name: String = name.into();
+        self.defs.retain(|&(ref n, _)| *n != name);
+        self.defs.push((name, expr));
+        self
+    }
+
+    /// The names of all columns, physical then virtual.
+    pub fn columns(&self) -> Vec<String> {
+        let mut columns = self.df.columns.clone();
+        columns.extend(self.defs.iter().map(|&(ref n, _)| n.clone()));
+        columns
+    }
+
+    /// The values of a physical or virtual column.
+    pub fn column<U: ?Sized>(&self, name: &U) -> Result<Row<T>>
+        where for<'b> String: From<&'b U>
+    {
+        Expr::Col(name.into()).eval(self.df, &self.defs[..], 0)
+    }
+
+    /// Materialize the given columns, physical or virtual, into a dataframe.
+    pub fn select<U: ?Sized>(&self, names: &[&U]) -> Result<DataFrame<T>>
+        where for<'b> String: From<&'b U>
+    {
+        let mut cols = Vec::with_capacity(names.len());
+        for name in names {
+            cols.push(self.column(*name)?);
+        }
+        let nrows = self.df.index.len();
+        let mut c = Vec::with_capacity(nrows * cols.len());
+        for r in 0..nrows {
+            c.extend(cols.iter().map(|col| col[r].clone()));
+        }
+        Ok(DataFrame {
+            columns: names.iter().map(|x| String::from(*x)).collect(),
+            data: Array::from_shape_vec((nrows, cols.len()), c).unwrap(),
+            index: self.df.index.clone(),
+            index_sorted: false,
+        })
+    }
+
+    /// Materialize every column into a dataframe.
+    pub fn as_df(&self) -> Result<DataFrame<T>> {
+        let columns = self.columns();
+        let names: Vec<&str> = columns.iter().map(|x| &x[..]).collect();
+        self.select(&names[..])
+    }
+}
@@ -0,0 +1,285 @@
+//! Utah C API
+//!
+//! A minimal interface for embedding utah in C and C++ programs, or any runtime with a C foreign
+//! function interface. Frames hold `f64` values and are passed around as opaque pointers, which
+//! must be released with `utah_frame_free`. Functions that build a frame return a null pointer on
+//! failure; the others return `0` or a count on success and `-1` on failure, including when given
+//! a null frame. No panic crosses the boundary: one is reported as a failure. The declarations are
+//! in `include/utah.h`.
+
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_int};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+use std::slice;
+use dataframe::DataFrame;
+use ndarray::Array;
+use util::helpers::{position, reduce, take, take_labels};
+use util::types::{Agg, UtahAxis};
+
+/// The aggregation with the given code: 0 sum, 1 mean, 2 max, 3 min, 4 count, 5 empty count.
+fn agg_from_code(code: c_int) -> Option<Agg> {
+    match code {
+        0 => Some(Agg::Sum),
+        1 => Some(Agg::Mean),
+        2 => Some(Agg::Max),
+        3 => Some(Agg::Min),
+        4 => Some(Agg::Count),
+        5 => Some(Agg::EmptyCount),
+        _ => None,
+    }
+}
+
+unsafe fn labels(names: *const *const c_char, n: usize) -> Option<Vec<String>> {
+    if names.is_null() {
+        return None;
+    }
+    slice::from_raw_parts(names, n)
+        .iter()
+        .map(|&p| if p.is_null() { None } else { CStr::from_ptr(p).to_str().ok() })
+        .map(|x| x.map(|s| s.to_string()))
+        .collect()
+}
+
+fn into_raw(df: DataFrame<f64>) -> *mut DataFrame<f64> {
+    Box::into_raw(Box::new(df))
+}
+
+/// Run `f`, returning `failure` if it panics.
+fn guard<R, F: FnOnce() -> R>(failure: R, f: F) -> R {
+    catch_unwind(AssertUnwindSafe(f)).unwrap_or(failure)
+}
+
+/// Copy `label` to the `len` bytes at `out`, truncated and nul-terminated, and return its full
+/// length in bytes. A null `out` only returns the length.
+unsafe fn copy_label(label: &str, out: *mut c_char, len: usize) -> isize {
+    if !out.is_null() && len > 0 {
+        let n = label.len().min(len - 1);
+        ptr::copy_nonoverlapping(label.as_ptr() as *const c_char, out, n);
+        *out.offset(n as isize) = 0;
+    }
+    label.len() as isize
+}
+
+/// Create a frame from `nrows * ncols` values laid out row by row, with `ncols` column names. The
+/// index is `0..nrows`.
+///
+/// # Safety
+///
+/// `data` must point to `nrows * ncols` values and `columns` to `ncols` nul-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn utah_frame_new(data: *const f64,
+                                        nrows: usize,
+                                        ncols: usize,
+                                        columns: *const *const c_char)
+                                        -> *mut DataFrame<f64> {
+    guard(ptr::null_mut(), || {
+        let len = match nrows.checked_mul(ncols) {
+            Some(len) if !data.is_null() => len,
+            _ => return ptr::null_mut(),
+        };
+        let columns = match labels(columns, ncols) {
+            Some(c) => c,
+            None => return ptr::null_mut(),
+        };
+        let values = slice::from_raw_parts(data, len).to_vec();
+        match Array::from_shape_vec((nrows, ncols), values) {
+            Ok(data) => {
+                into_raw(DataFrame {
+                    columns: columns,
+                    data: data,
+                    index: (0..nrows).map(|x| x.to_string()).collect(),
+                    index_sorted: false,
+                })
+            }
+            Err(_) => ptr::null_mut(),
+        }
+    })
+}
+
+/// Release a frame. Null pointers are ignored.
+///
+/// # Safety
+///
+/// `df` must come from this API and must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn utah_frame_free(df: *mut DataFrame<f64>) {
+    if !df.is_null() {
+        guard((), || drop(Box::from_raw(df)));
+    }
+}
+
+/// The number of rows of a frame.
+///
+/// # Safety
+///
+/// `df` must be null or a live frame from this API.
+#[no_mangle]
+pub unsafe extern "C" fn utah_frame_nrows(df: *const DataFrame<f64>) -> isize {
+    match df.as_ref() {
+        Some(df) => df.index.len() as isize,
+        None => -1,
+    }
+}
+
+/// The number of columns of a frame.
+///
+/// # Safety
+///
+/// `df` must be null or a live frame from this API.
+#[no_mangle]
+pub unsafe extern "C" fn utah_frame_ncols(df: *const DataFrame<f64>) -> isize {
+    match df.as_ref() {
+        Some(df) => df.columns.len() as isize,
+        None => -1,
+    }
+}
+
+/// Copy the name of column `i` to the `len` bytes at `out`, truncated and nul-terminated, and
+/// return its full length in bytes. A null `out` only returns the length.
+///
+/// # Safety
+///
+/// `df` must be null or a live frame from this API, and `out` null or writable for `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn utah_frame_column_name(df: *const DataFrame<f64>,
+                                                i: usize,
+                                                out: *mut c_char,
+                                                len: usize)
+                                                -> isize {
+    match df.as_ref().and_then(|df| df.columns.get(i)) {
+        Some(label) => copy_label(label, out, len),
+        None => -1,
+    }
+}
+
+/// Copy the label of row `i` to the `len` bytes at `out`, truncated and nul-terminated, and return
+/// its full length in bytes. A null `out` only returns the length.
+///
+/// # Safety
+///
+/// `df` must be null or a live frame from this API, and `out` null or writable for `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn utah_frame_index_label(df: *const DataFrame<f64>,
+                                                i: usize,
+                                                out: *mut c_char,
+                                                len: usize)
+                                                -> isize {
+    match df.as_ref().and_then(|df| df.index.get(i)) {
+        Some(label) => copy_label(label, out, len),
+        None => -1,
+    }
+}
+
+/// A new frame holding the `n` named columns, in the given order.
+///
+/// # Safety
+///
+/// `df` must be null or a live frame from this API and `names` must point to `n` nul-terminated
+/// strings.
+#[no_mangle]
+pub unsafe extern "C" fn utah_frame_select(df: *const DataFrame<f64>,
+                                           names: *const *const c_char,
+                                           n: usize)
+                                           -> *mut DataFrame<f64> {
+    let df = match df.as_ref() {
+        Some(df) => df,
+        None => return ptr::null_mut(),
+    };
+    guard(ptr::null_mut(), || {
+        let names = match labels(names, n) {
+            Some(names) => names,
+            None => return ptr::null_mut(),
+        };
+        let positions: Option<Vec<usize>> =
+            names.iter().map(|x| position(&df.columns[..], x).ok()).collect();
+        match positions {
+            Some(p) => {
+                into_raw(DataFrame {
+                    columns: take_labels(&df.columns[..], &p[..]),
+                    data: take(&df.data, UtahAxis::Column, &p[..]),
+                    index: df.index.clone(),
+                    index_sorted: false,
+                })
+            }
+            None => ptr::null_mut(),
+        }
+    })
+}
+
+/// A new frame holding the rows whose value in `column` lies within `[lo, hi]`.
+///
+/// # Safety
+///
+/// `df` must be null or a live frame from this API and `column` a nul-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn utah_frame_filter(df: *const DataFrame<f64>,
+                                           column: *const c_char,
+                                           lo: f64,
+                                           hi: f64)
+                                           -> *mut DataFrame<f64> {
+    let df = match df.as_ref() {
+        Some(df) => df,
+        None => return ptr::null_mut(),
+    };
+    guard(ptr::null_mut(), || {
+        let i = match labels(&column, 1).and_then(|c| position(&df.columns[..], &c[0]).ok()) {
+            Some(i) => i,
+            None => return ptr::null_mut(),
+        };
+        let positions: Vec<usize> = (0..df.index.len())
+            .filter(|&r| df.data[[r, i]] >= lo && df.data[[r, i]] <= hi)
+            .collect();
+        into_raw(DataFrame {
+            columns: df.columns.clone(),
+            data: take(&df.data, UtahAxis::Row, &positions[..]),
+            index: take_labels(&df.index[..], &positions[..]),
+            index_sorted: false,
+        })
+    })
+}
+
+/// Aggregate each column with the aggregation `agg` (see `agg_from_code`), writing one value per
+/// column to `out`.
+///
+/// # Safety
+///
+/// `df` must be null or a live frame from this API and `out` must have room for one value per
+/// column.
+#[no_mangle]
+pub unsafe extern "C" fn utah_frame_agg(df: *const DataFrame<f64>,
+                                        agg: c_int,
+                                        out: *mut f64)
+                                        -> c_int {
+    let (df, agg) = match (df.as_ref(), agg_from_code(agg)) {
+        (Some(df), Some(agg)) if !out.is_null() => (df, agg),
+        _ => return -1,
+    };
+    guard(-1, || {
+        let out = slice::from_raw_parts_mut(out, df.columns.len());
+        for (i, x) in out.iter_mut().enumerate() {
+            *x = reduce(df.data.column(i).iter(), agg);
+        }
+        0
+    })
+}
+
+/// Copy the values of a frame to `out`, row by row.
+///
+/// # Safety
+///
+/// `df` must be null or a live frame from this API and `out` must have room for every value.
+#[no_mangle]
+pub unsafe extern "C" fn utah_frame_read(df: *const DataFrame<f64>, out: *mut f64) -> c_int {
+    let df = match df.as_ref() {
+        Some(df) if !out.is_null() => df,
+        _ => return -1,
+    };
+    guard(-1, || {
+        let out = slice::from_raw_parts_mut(out, df.data.len());
+        for (x, y) in out.iter_mut().zip(df.data.iter()) {
+            *x = *y;
+        }
+        0
+    })
+}
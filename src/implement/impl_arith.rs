@@ -0,0 +1,119 @@
+use std::ops::{Add, Sub, Mul, Div};
+use ndarray::Array;
+use util::helpers::align_labels;
+use util::traits::*;
+use util::types::*;
+use dataframe::*;
+
+impl<T> DataFrame<T>
+    where T: UtahNum
+{
+    /// Combine two dataframes cell by cell, aligned on their index and columns. Cells whose row
+    /// or column is missing from either side are left empty.
+    fn zip_aligned<F>(&self, other: &DataFrame<T>, f: F) -> DataFrame<T>
+        where F: Fn(T, T) -> T
+    {
+        let rows = align_labels(&self.index[..], &other.index[..]);
+        let cols = align_labels(&self.columns[..], &other.columns[..]);
+        let mut c = Vec::with_capacity(rows.len() * cols.len());
+        for &(_, ra, rb) in &rows {
+            for &(_, ca, cb) in &cols {
+                c.push(match (ra, rb, ca, cb) {
+                    (Some(ra), Some(rb), Some(ca), Some(cb)) => {
+                        f(self.data[[ra, ca]].clone(), other.data[[rb, cb]].clone())
+                    }
+                    _ => T::empty(),
+                });
+            }
+        }
+        DataFrame {
+            data: Array::from_shape_vec((rows.len(), cols.len()), c).unwrap(),
+            columns: cols.into_iter().map(|(l, _, _)| l).collect(),
+            index: rows.into_iter().map(|(l, _, _)| l).collect(),
+            index_sorted: false,
+        }
+    }
+
+    /// Combine every cell with a scalar into a new dataframe.
+    fn broadcast(&self, op: ArithOp, value: T) -> DataFrame<T> {
+        let c: Vec<T> = self.data.iter().map(|x| op.apply(x.clone(), value.clone())).collect();
+        DataFrame {
+            columns: self.columns.clone(),
+            data: Array::from_shape_vec(self.data.dim(), c).unwrap(),
+            index: self.index.clone(),
+            index_sorted: false,
+        }
+    }
+}
+
+impl<'a, T> Add for &'a DataFrame<T>
+    where T: UtahNum
+{
+    type Output = DataFrame<T>;
+    fn add(self, rhs: &'a DataFrame<T>) -> DataFrame<T> {
+        self.zip_aligned(rhs, |x, y| x + y)
+    }
+}
+
+impl<'a, T> Sub for &'a DataFrame<T>
+    where T: UtahNum
+{
+    type Output = DataFrame<T>;
+    fn sub(self, rhs: &'a DataFrame<T>) -> DataFrame<T> {
+        self.zip_aligned(rhs, |x, y| x - y)
+    }
+}
+
+impl<'a, T> Mul for &'a DataFrame<T>
+    where T: UtahNum
+{
+    type Output = DataFrame<T>;
+    fn mul(self, rhs: &'a DataFrame<T>) -> DataFrame<T> {
+        self.zip_aligned(rhs, |x, y| x * y)
+    }
+}
+
+impl<'a, T> Div for &'a DataFrame<T>
+    where T: UtahNum
+{
+    type Output = DataFrame<T>;
+    fn div(self, rhs: &'a DataFrame<T>) -> DataFrame<T> {
+        self.zip_aligned(rhs, |x, y| x / y)
+    }
+}
+
+impl<'a, T> Add<T> for &'a DataFrame<T>
+    where T: UtahNum
+{
+    type Output = DataFrame<T>;
+    fn add(self, rhs: T) -> DataFrame<T> {
+        self.broadcast(ArithOp::Add, rhs)
+    }
+}
+
+impl<'a, T> Sub<T> for &'a DataFrame<T>
+    where T: UtahNum
+{
+    type Output = DataFrame<T>;
+    fn sub(self, rhs: T) -> DataFrame<T> {
+        self.broadcast(ArithOp::Sub, rhs)
+    }
+}
+
+impl<'a, T> Mul<T> for &'a DataFrame<T>
+    where T: UtahNum
+{
+    type Output = DataFrame<T>;
+    fn mul(self, rhs: T) -> DataFrame<T> {
+        self.broadcast(ArithOp::Mul, rhs)
+    }
+}
+
+impl<'a, T> Div<T> for &'a DataFrame<T>
+    where T: UtahNum
+{
+    type Output = DataFrame<T>;
+    fn div(self, rhs: T) -> DataFrame<T> {
+        self.broadcast(ArithOp::Div, rhs)
+    }
+}
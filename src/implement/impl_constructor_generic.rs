@@ -47,6 +47,7 @@ impl<'a, T> Constructor<'a, T> for DataFrame<T>
             data: data,
             columns: columns,
             index: index,
+            index_sorted: false,
         }
     }
     /// Generate a 1-dimensional DataFrame from an 1-D array of data.
@@ -81,6 +82,7 @@ impl<'a, T> Constructor<'a, T> for DataFrame<T>
             data: data,
             columns: columns,
             index: index,
+            index_sorted: false,
         }
     }
     /// Populate the dataframe with a set of columns. The column elements can be any of `OuterType`. Example:
@@ -139,6 +141,7 @@ impl<'a, T> Constructor<'a, T> for DataFrame<T>
             .map(|x| x.clone().into())
             .collect();
         self.index = new_index;
+        self.index_sorted = false;
         Ok(self)
     }
 
@@ -8,6 +8,9 @@ impl Empty<f64> for f64 {
     fn is_empty(&self) -> bool {
         self.is_nan()
     }
+    fn from_count(n: usize) -> f64 {
+        n as f64
+    }
 }
 
 impl Empty<i32> for i32 {
@@ -17,6 +20,9 @@ impl Empty<i32> for i32 {
     fn is_empty(&self) -> bool {
         *self == 0
     }
+    fn from_count(n: usize) -> i32 {
+        n as i32
+    }
 }
 
 impl Empty<Option<i32>> for Option<i32> {
@@ -0,0 +1,19 @@
+use util::types::*;
+use util::traits::MaskOps;
+use ndarray::Axis;
+
+impl MaskOps for Matrix<bool> {
+    fn any(&self, axis: UtahAxis) -> Row<bool> {
+        match axis {
+            UtahAxis::Row => self.map_axis(Axis(1), |r| r.iter().any(|&x| x)),
+            UtahAxis::Column => self.map_axis(Axis(0), |c| c.iter().any(|&x| x)),
+        }
+    }
+
+    fn all(&self, axis: UtahAxis) -> Row<bool> {
+        match axis {
+            UtahAxis::Row => self.map_axis(Axis(1), |r| r.iter().all(|&x| x)),
+            UtahAxis::Column => self.map_axis(Axis(0), |c| c.iter().all(|&x| x)),
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use util::types::*;
+use util::traits::*;
+use util::error::*;
+use util::helpers::{position, take, take_labels};
+use dataframe::*;
+use mixedtypes::{DType, InnerType};
+use ndarray::Array;
+use std::f64::NAN;
+
+impl DataFrame<InnerType> {
+    /// The type of each column, ignoring empty values.
+    pub fn dtypes(&self) -> Vec<DType> {
+        self.df_iter(UtahAxis::Column)
+            .map(|(_, col)| {
+                col.iter()
+                    .filter(|x| !x.is_empty())
+                    .fold(DType::Empty, |acc, x| match (acc, x.dtype()) {
+                        (DType::Empty, t) => t,
+                        (a, t) if a == t => a,
+                        _ => DType::Mixed,
+                    })
+            })
+            .collect()
+    }
+
+    /// Keep the columns whose type is one of `dtypes`.
+    pub fn select_dtypes(&self, dtypes: &[DType]) -> DataFrame<InnerType> {
+        let positions: Vec<usize> = self.dtypes()
+            .iter()
+            .enumerate()
+            .filter(|&(_, t)| dtypes.contains(t))
+            .map(|(i, _)| i)
+            .collect();
+        DataFrame {
+            columns: take_labels(&self.columns[..], &positions[..]),
+            data: take(&self.data, UtahAxis::Column, &positions[..]),
+            index: self.index.clone(),
+            index_sorted: false,
+        }
+    }
+
+    /// Convert every value of `column` to `dtype`, failing on the first value that cannot be
+    /// converted.
+    pub fn astype<U: ?Sized>(&self, column: &U, dtype: DType) -> Result<DataFrame<InnerType>>
+        where for<'b> String: From<&'b U>
+    {
+        let name: String = column.into();
+        let i = position(&self.columns[..], &name)?;
+        let mut df = self.clone();
+        for x in df.data.column_mut(i).iter_mut() {
+            *x = x.cast(dtype)?;
+        }
+        Ok(df)
+    }
+
+    /// Stack the rows of `other` under these rows like `concat_evolve`, but fail if a column
+    /// shared by both dataframes holds values of different types on each side.
+    pub fn concat_evolve_checked(&self,
+                                 other: &DataFrame<InnerType>)
+                                 -> Result<(DataFrame<InnerType>, SchemaEvolution)> {
+        let (left, right) = (self.dtypes(), other.dtypes());
+        for (i, c) in self.columns.iter().enumerate() {
+            if let Some(j) = other.columns.iter().position(|x| x == c) {
+                match (left[i], right[j]) {
+                    (DType::Empty, _) | (_, DType::Empty) => {}
+                    (a, b) if a == b => {}
+                    (a, b) => {
+                        return Err(ErrorKind::DTypeConflict(c.clone(),
+                                                            format!("{:?}", a),
+                                                            format!("{:?}", b))
+                            .into())
+                    }
+                }
+            }
+        }
+        self.concat_evolve(other)
+    }
+
+    /// The correlation between every pair of numeric columns, like `corr` on a dataframe of
+    /// floats. Other columns are left out.
+    pub fn corr(&self, method: CorrMethod) -> Result<DataFrame<f64>> {
+        let numeric = self.select_dtypes(&[DType::Float, DType::Int64, DType::Int32]);
+        let mut d = Vec::with_capacity(numeric.data.len());
+        for x in numeric.data.iter() {
+            d.push(match x.cast(DType::Float)? {
+                InnerType::Float(f) => f,
+                _ => NAN,
+            });
+        }
+        let data = Array::from_shape_vec(numeric.data.dim(), d).unwrap();
+        Ok(DataFrame {
+                columns: numeric.columns,
+                data: data,
+                index: numeric.index,
+                index_sorted: false,
+            }
+            .corr(method))
+    }
+}
@@ -18,7 +18,7 @@ impl<'a> Operations<'a, f64> for DataFrame<f64> {
     /// assert_eq!(df.shape(), (2,2));
     /// ```
     ///
-    fn shape(self) -> (usize, usize) {
+    fn shape(&self) -> (usize, usize) {
         self.data.dim()
     }
 
@@ -125,19 +125,13 @@ impl<'a> Operations<'a, f64> for DataFrame<f64> {
 
     /// Perform an inner left join between two dataframes *using each index* along the specified `UtahAxis`.
     fn inner_left_join(&'a self, other: &'a DataFrame<f64>) -> InnerJoinIter<'a, f64> {
-        InnerJoin::new(self.df_iter(UtahAxis::Row),
-                       other.df_iter(UtahAxis::Row),
-                       self.columns.clone(),
-                       other.columns.clone())
+        InnerJoin::new(self, other)
     }
 
     /// Perform an outer left join between two dataframes *using each index* along the specified `UtahAxis`.
     fn outer_left_join(&'a self, other: &'a DataFrame<f64>) -> OuterJoinIter<'a, f64> {
 
-        OuterJoin::new(self.df_iter(UtahAxis::Row),
-                       other.df_iter(UtahAxis::Row),
-                       self.columns.clone(),
-                       other.columns.clone())
+        OuterJoin::new(self, other)
 
 
 
@@ -147,19 +141,13 @@ impl<'a> Operations<'a, f64> for DataFrame<f64> {
     /// Perform an inner right join between two dataframes *using each index* along the specified `UtahAxis`.
 
     fn inner_right_join(&'a self, other: &'a DataFrame<f64>) -> InnerJoinIter<'a, f64> {
-        InnerJoin::new(other.df_iter(UtahAxis::Row),
-                       self.df_iter(UtahAxis::Row),
-                       other.columns.clone(),
-                       self.columns.clone())
+        InnerJoin::new(other, self)
 
     }
 
     /// Perform an outer right join between two dataframes *using each index* along the specified `UtahAxis`.
     fn outer_right_join(&'a self, other: &'a DataFrame<f64>) -> OuterJoinIter<'a, f64> {
-        OuterJoin::new(other.df_iter(UtahAxis::Row),
-                       self.df_iter(UtahAxis::Row),
-                       other.columns.clone(),
-                       self.columns.clone())
+        OuterJoin::new(other, self)
 
     }
 
@@ -262,7 +250,7 @@ impl<'a> Operations<'a, f64> for DataFrame<f64> {
 
 
     /// Replace empty values with specified ImputeStrategy and along a `UtahAxis`.
-    fn impute(&'a mut self, strategy: ImputeStrategy, axis: UtahAxis) -> ImputeIter<'a, f64> {
+    fn impute(&'a mut self, strategy: ImputeStrategy<f64>, axis: UtahAxis) -> ImputeIter<'a, f64> {
 
         let index = self.index.clone();
         let columns = self.columns.clone();
@@ -4,19 +4,162 @@ use combinators::aggregate::*;
 use combinators::process::*;
 use combinators::interact::*;
 use combinators::transform::*;
+use combinators::window::*;
+use combinators::groupby::*;
 use util::traits::*;
 use dataframe::*;
-use ndarray::ArrayView1;
+use ndarray::{Array, ArrayView1, Axis};
+use util::error::*;
+use util::helpers::{position, count, reduce, group_positions, take, take_labels, sample_positions,
+                    sample_size, describe, align_labels, DESCRIBE_STATS};
+use rand::Rng;
+use std::collections::{HashMap, HashSet};
+use std::cmp::Ordering;
+use util::keys::RowKey;
+use util::rank;
+use util::stats;
+use util::time;
+use std::time::Duration;
+use util::multiindex::{escape_level, MultiIndex, LEVEL_SEPARATOR};
+use transaction::Transaction;
+use expr::{Expr, VirtualFrame};
+use series::Series;
+use util::blocks::BlockIndex;
+use util::rowset::RowSet;
+use cache::StatsCache;
+use util::bins::{check_edges, default_labels, bin_of, even_edges, bin_counts};
+use mixedtypes::InnerType;
+
+/// Lay out the groups of equal values of the `index` and `columns` columns as a grid, with a row
+/// for each distinct value of `index` and a column for each distinct value of `columns`, in
+/// order of first appearance. Each cell is `f` of the positions of its rows, or `fill` if there
+/// are none.
+fn pivot<T, U, F>(df: &DataFrame<T>,
+                  index: &str,
+                  columns: &str,
+                  fill: U,
+                  f: F)
+                  -> Result<DataFrame<U>>
+    where T: UtahNum,
+          U: UtahNum,
+          F: Fn(&[usize]) -> U
+{
+    let (i, j) = (position(&df.columns[..], index)?, position(&df.columns[..], columns)?);
+    let groups = df.groupby_multi(&[index, columns])?;
+    let (mut rows, mut cols) = (Vec::new(), Vec::new());
+    let (mut row_of, mut col_of) = (HashMap::new(), HashMap::new());
+    let mut cells = Vec::with_capacity(groups.ngroups());
+    for g in &groups.groups {
+        let r = intern(&mut rows, &mut row_of, format!("{:?}", df.data[[g[0], i]]));
+        let c = intern(&mut cols, &mut col_of, format!("{:?}", df.data[[g[0], j]]));
+        cells.push((r, c, f(&g[..])));
+    }
+    let mut data = Array::from_elem((rows.len(), cols.len()), fill);
+    for (r, c, x) in cells {
+        data[[r, c]] = x;
+    }
+    Ok(DataFrame {
+        columns: cols,
+        data: data,
+        index: rows,
+        index_sorted: false,
+    })
+}
+
+/// The position of `label` in `labels`, appending it if it is new.
+fn intern(labels: &mut Vec<String>,
+          positions: &mut HashMap<String, usize>,
+          label: String)
+          -> usize {
+    if let Some(&p) = positions.get(&label) {
+        return p;
+    }
+    positions.insert(label.clone(), labels.len());
+    labels.push(label);
+    labels.len() - 1
+}
+
+/// Fail on the first non-empty entry of `df` that is not positive.
+fn check_positive<T>(df: &DataFrame<T>) -> Result<()>
+    where T: UtahNum + PartialOrd
+{
+    match df.data.iter().find(|x| !x.is_empty() && **x <= T::zero()) {
+        Some(x) => Err(ErrorKind::NonPositiveValue(format!("{:?}", x)).into()),
+        None => Ok(()),
+    }
+}
+
+/// The rows of `df` whose label does (`matched`) or does not appear in the index of `other`.
+fn rows_by_match<T>(df: &DataFrame<T>, other: &DataFrame<T>, matched: bool) -> DataFrame<T>
+    where T: UtahNum
+{
+    let keys: HashSet<&String> = other.index.iter().collect();
+    let positions: Vec<usize> = (0..df.index.len())
+        .filter(|&i| keys.contains(&df.index[i]) == matched)
+        .collect();
+    DataFrame {
+        columns: df.columns.clone(),
+        data: take(&df.data, UtahAxis::Row, &positions[..]),
+        index: take_labels(&df.index[..], &positions[..]),
+        index_sorted: false,
+    }
+}
+
+/// Apply a column-wise ranking function to `column`, either over the whole column or separately
+/// within each group of equal values of `by`.
+fn rank_column<T, F>(df: &DataFrame<T>,
+                     column: String,
+                     by: Option<String>,
+                     f: F)
+                     -> Result<DataFrame<f64>>
+    where T: UtahNum + PartialOrd,
+          F: Fn(&[T]) -> Vec<f64>
+{
+    let i = position(&df.columns[..], &column)?;
+    let values: Vec<T> = df.data.column(i).iter().cloned().collect();
+    let ranked = match by {
+        None => f(&values[..]),
+        Some(by) => {
+            let j = position(&df.columns[..], &by)?;
+            let keys: Vec<T> = df.data.column(j).iter().cloned().collect();
+            let mut ranked = vec![::std::f64::NAN; values.len()];
+            for (_, positions) in group_positions(&keys[..]) {
+                let group: Vec<T> = positions.iter().map(|&p| values[p].clone()).collect();
+                for (&p, r) in positions.iter().zip(f(&group[..])) {
+                    ranked[p] = r;
+                }
+            }
+            ranked
+        }
+    };
+    let d = Array::from_shape_vec((ranked.len(), 1), ranked).unwrap();
+    DataFrame::new(d).columns(&[column])?.index(&df.index[..])
+}
 
 #[cfg(not(feature = "specialization"))]
 impl<'a, T> Operations<'a, T> for DataFrame<T>
     where T: 'a + UtahNum
 {
     /// Get the dimensions of the dataframe.
-    fn shape(self) -> (usize, usize) {
+    fn shape(&self) -> (usize, usize) {
         self.data.dim()
     }
 
+    /// Get the number of rows of the dataframe.
+    fn nrows(&self) -> usize {
+        self.data.rows()
+    }
+
+    /// Get the number of columns of the dataframe.
+    fn ncols(&self) -> usize {
+        self.data.cols()
+    }
+
+    /// Whether the dataframe has no cells.
+    fn is_empty(&self) -> bool {
+        self.data.len() == 0
+    }
+
 
 
     /// Select rows or columns over the specified `UtahAxis`.
@@ -96,36 +239,25 @@ impl<'a, T> Operations<'a, T> for DataFrame<T>
 
     /// Perform an inner left join between two dataframes along the specified `UtahAxis`.
     fn inner_left_join(&'a self, other: &'a DataFrame<T>) -> InnerJoinIter<'a, T> {
-        InnerJoin::new(self.df_iter(UtahAxis::Row),
-                       other.df_iter(UtahAxis::Row),
-                       self.columns.clone(),
-                       other.columns.clone())
+        InnerJoin::new(self, other)
     }
 
     /// Perform an outer left join between two dataframes along the specified `UtahAxis`.
     fn outer_left_join(&'a self, other: &'a DataFrame<T>) -> OuterJoinIter<'a, T> {
 
-        OuterJoin::new(self.df_iter(UtahAxis::Row),
-                       other.df_iter(UtahAxis::Row),
-                       self.columns.clone(),
-                       other.columns.clone())
+        OuterJoin::new(self, other)
     }
 
     /// Perform an inner right join between two dataframes along the specified `UtahAxis`.
     fn inner_right_join(&'a self, other: &'a DataFrame<T>) -> InnerJoinIter<'a, T> {
-        InnerJoin::new(other.df_iter(UtahAxis::Row),
-                       self.df_iter(UtahAxis::Row),
-                       other.columns.clone(),
-                       self.columns.clone())
+        InnerJoin::new(other, self)
 
     }
 
     /// Perform an outer right join between two dataframes along the specified `UtahAxis`.
     fn outer_right_join(&'a self, other: &'a DataFrame<T>) -> OuterJoinIter<'a, T> {
-        OuterJoin::new(other.df_iter(UtahAxis::Row),
-                       self.df_iter(UtahAxis::Row),
-                       other.columns.clone(),
-                       self.columns.clone())
+        OuterJoin::new(other, self)
+            .swapped()
 
     }
 
@@ -134,7 +266,7 @@ impl<'a, T> Operations<'a, T> for DataFrame<T>
             UtahAxis::Row => {
                 Concat::new(self.df_iter(UtahAxis::Column),
                             other.df_iter(UtahAxis::Column),
-                            self.columns.clone(),
+                            self.index.clone(),
                             UtahAxis::Column)
             }
             UtahAxis::Column => {
@@ -215,7 +347,7 @@ impl<'a, T> Operations<'a, T> for DataFrame<T>
     }
 
     /// Replace empty values with specified ImputeStrategy along the specified `UtahAxis`.
-    fn impute(&'a mut self, strategy: ImputeStrategy, axis: UtahAxis) -> ImputeIter<'a, T> {
+    fn impute(&'a mut self, strategy: ImputeStrategy<T>, axis: UtahAxis) -> ImputeIter<'a, T> {
 
         let index = self.index.clone();
         let columns = self.columns.clone();
@@ -235,6 +367,1535 @@ impl<'a, T> Operations<'a, T> for DataFrame<T>
 
         }
     }
+
+    /// Percent rank of the entries of a column, `(rank - 1) / (n - 1)`, as floats.
+    fn pct_rank<U: ?Sized>(&'a self, column: &'a U) -> Result<DataFrame<f64>>
+        where String: From<&'a U>,
+              T: PartialOrd
+    {
+        rank_column(self, column.into(), None, rank::pct_rank)
+    }
+
+    /// Percent rank of the entries of a column within each group of the `by` column.
+    fn pct_rank_by<U: ?Sized>(&'a self, column: &'a U, by: &'a U) -> Result<DataFrame<f64>>
+        where String: From<&'a U>,
+              T: PartialOrd
+    {
+        rank_column(self, column.into(), Some(by.into()), rank::pct_rank)
+    }
+
+    /// Cumulative distribution of the entries of a column.
+    fn cume_dist<U: ?Sized>(&'a self, column: &'a U) -> Result<DataFrame<f64>>
+        where String: From<&'a U>,
+              T: PartialOrd
+    {
+        rank_column(self, column.into(), None, rank::cume_dist)
+    }
+
+    /// Cumulative distribution of the entries of a column within each group of the `by` column.
+    fn cume_dist_by<U: ?Sized>(&'a self, column: &'a U, by: &'a U) -> Result<DataFrame<f64>>
+        where String: From<&'a U>,
+              T: PartialOrd
+    {
+        rank_column(self, column.into(), Some(by.into()), rank::cume_dist)
+    }
+
+    /// Sort the rows by the values of one or more columns. The sort is stable, and empty values
+    /// are placed first or last regardless of the sort direction.
+    fn sort_values<U: ?Sized>(&'a self,
+                              columns: &'a [&'a U],
+                              ascending: bool,
+                              empties: EmptyPosition)
+                              -> Result<DataFrame<T>>
+        where String: From<&'a U>,
+              T: PartialOrd
+    {
+        let mut keys = Vec::with_capacity(columns.len());
+        for c in columns {
+            let c: String = String::from(*c);
+            keys.push(position(&self.columns[..], &c)?);
+        }
+        let mut order: Vec<usize> = (0..self.index.len()).collect();
+        order.sort_by(|&a, &b| {
+            keys.iter()
+                .map(|&k| rank::compare(&self.data[[a, k]], &self.data[[b, k]], ascending, empties))
+                .find(|o| *o != Ordering::Equal)
+                .unwrap_or(Ordering::Equal)
+        });
+        Ok(DataFrame {
+            columns: self.columns.clone(),
+            data: take(&self.data, UtahAxis::Row, &order[..]),
+            index: take_labels(&self.index[..], &order[..]),
+            index_sorted: false,
+        })
+    }
+
+    /// Discretize a column into bins delimited by `edges`. The result has a `label` column with
+    /// the label of each entry's bin, and a `bin` column with the bin's position. When `labels` is
+    /// empty, bins are labelled in interval notation. Entries outside the edges are left empty.
+    fn cut<U: ?Sized>(&'a self,
+                      column: &'a U,
+                      edges: &[T],
+                      labels: &[&str])
+                      -> Result<DataFrame<InnerType>>
+        where String: From<&'a U>,
+              T: PartialOrd
+    {
+        let column: String = column.into();
+        let i = position(&self.columns[..], &column)?;
+        check_edges(edges, labels.len())?;
+        let labels: Vec<String> = if labels.is_empty() {
+            default_labels(edges)
+        } else {
+            labels.iter().map(|x| x.to_string()).collect()
+        };
+        let mut c = Vec::with_capacity(self.index.len() * 2);
+        for x in self.data.column(i).iter() {
+            match bin_of(x, edges) {
+                Some(b) => {
+                    c.push(InnerType::Str(labels[b].clone()));
+                    c.push(InnerType::Int64(b as i64));
+                }
+                None => {
+                    c.push(InnerType::Empty);
+                    c.push(InnerType::Empty);
+                }
+            }
+        }
+        Ok(DataFrame {
+            columns: vec!["label".to_string(), "bin".to_string()],
+            data: Array::from_shape_vec((self.index.len(), 2), c).unwrap(),
+            index: self.index.clone(),
+            index_sorted: false,
+        })
+    }
+
+    /// Sort the rows by their index labels.
+    fn sort_index(&'a self, ascending: bool) -> DataFrame<T> {
+        let order = rank::sorted_label_positions(&self.index[..], ascending);
+        DataFrame {
+            columns: self.columns.clone(),
+            data: take(&self.data, UtahAxis::Row, &order[..]),
+            index: take_labels(&self.index[..], &order[..]),
+            index_sorted: ascending,
+        }
+    }
+
+    /// Rolling window of `window` rows, over each column.
+    fn rolling(&'a self, window: usize) -> Rolling<'a, T> {
+        Rolling::new(self.df_iter(UtahAxis::Column), window, self.index.clone())
+    }
+
+    /// Group the rows by the values of the `key` column, in order of first appearance. Values
+    /// are grouped by their debug representation (see `util::keys`), which is also the label of
+    /// each group, and empty values form one group, as in `groupby_multi`.
+    fn groupby<U: ?Sized>(&'a self, key: &'a U) -> Result<GroupBy<'a, T>>
+        where String: From<&'a U>
+    {
+        let key: String = key.into();
+        let i = position(&self.columns[..], &key)?;
+        let keys: Vec<T> = self.data.column(i).iter().cloned().collect();
+        Ok(GroupBy::new(self, key, group_positions(&keys[..])))
+    }
+
+    /// Draw a random sample of `n` rows, with or without replacement.
+    fn sample<R: Rng>(&'a self, n: usize, replace: bool, rng: &mut R) -> Result<DataFrame<T>> {
+        let positions = sample_positions(rng, self.index.len(), n, replace)?;
+        Ok(DataFrame {
+            columns: self.columns.clone(),
+            data: take(&self.data, UtahAxis::Row, &positions[..]),
+            index: take_labels(&self.index[..], &positions[..]),
+            index_sorted: false,
+        })
+    }
+
+    /// Draw a random sample of a fraction of the rows, with or without replacement.
+    fn sample_frac<R: Rng>(&'a self,
+                           frac: f64,
+                           replace: bool,
+                           rng: &mut R)
+                           -> Result<DataFrame<T>> {
+        let n = sample_size(frac, self.index.len())?;
+        self.sample(n, replace, rng)
+    }
+
+    /// Draw a random sample of a fraction of the rows within each group of the `by` column, so
+    /// that each group keeps its share of the rows.
+    fn sample_stratified<U: ?Sized, R: Rng>(&'a self,
+                                            by: &'a U,
+                                            frac: f64,
+                                            replace: bool,
+                                            rng: &mut R)
+                                            -> Result<DataFrame<T>>
+        where String: From<&'a U>
+    {
+        let by: String = by.into();
+        let i = position(&self.columns[..], &by)?;
+        sample_size(frac, self.index.len())?;
+        let keys: Vec<T> = self.data.column(i).iter().cloned().collect();
+        let mut positions = Vec::new();
+        for (_, group) in group_positions(&keys[..]) {
+            let n = sample_size(frac, group.len())?;
+            let drawn = sample_positions(rng, group.len(), n, replace)?;
+            positions.extend(drawn.into_iter().map(|p| group[p]));
+        }
+        Ok(DataFrame {
+            columns: self.columns.clone(),
+            data: take(&self.data, UtahAxis::Row, &positions[..]),
+            index: take_labels(&self.index[..], &positions[..]),
+            index_sorted: false,
+        })
+    }
+
+    /// Rank the entries of each row across the columns, in place.
+    fn rank_rows(&'a mut self) -> RankIter<'a, T> {
+        let columns = self.columns.clone();
+        Rank::new(self.df_iter_mut(UtahAxis::Row),
+                  RankMethod::Average,
+                  None,
+                  columns,
+                  UtahAxis::Row)
+    }
+
+    /// Subtract the mean of each row from its entries, in place.
+    fn demean_rows(&'a mut self) -> DemeanIter<'a, T> {
+        let columns = self.columns.clone();
+        Demean::new(self.df_iter_mut(UtahAxis::Row), columns, UtahAxis::Row)
+    }
+
+    /// Randomly permute the rows, together with the index, in place.
+    fn shuffle<R: Rng>(&mut self, rng: &mut R) {
+        let mut order: Vec<usize> = (0..self.index.len()).collect();
+        rng.shuffle(&mut order[..]);
+        self.data = take(&self.data, UtahAxis::Row, &order[..]);
+        self.index = take_labels(&self.index[..], &order[..]);
+    }
+
+    /// Get the mean of entries along the specified `UtahAxis`, after discarding the `frac`
+    /// smallest and `frac` largest of them.
+    fn trimmed_mean(&'a self, frac: f64, axis: UtahAxis) -> TrimmedMeanIter<'a, T> {
+        match axis {
+            UtahAxis::Row => {
+                TrimmedMean::new(self.df_iter(UtahAxis::Row),
+                                 frac,
+                                 self.index.clone(),
+                                 UtahAxis::Row)
+            }
+            UtahAxis::Column => {
+                TrimmedMean::new(self.df_iter(UtahAxis::Column),
+                                 frac,
+                                 self.columns.clone(),
+                                 UtahAxis::Column)
+            }
+        }
+    }
+
+    /// Get the mean of entries along the specified `UtahAxis`, after clamping the `frac` smallest
+    /// and `frac` largest of them to the remaining extremes.
+    fn winsorized_mean(&'a self, frac: f64, axis: UtahAxis) -> WinsorizedMeanIter<'a, T> {
+        match axis {
+            UtahAxis::Row => {
+                WinsorizedMean::new(self.df_iter(UtahAxis::Row),
+                                    frac,
+                                    self.index.clone(),
+                                    UtahAxis::Row)
+            }
+            UtahAxis::Column => {
+                WinsorizedMean::new(self.df_iter(UtahAxis::Column),
+                                    frac,
+                                    self.columns.clone(),
+                                    UtahAxis::Column)
+            }
+        }
+    }
+
+    /// Group the rows by the bin of `column` they fall into, with bins as in `cut`. Groups are
+    /// keyed by the lower edge of their bin and come in bin order. Rows outside the edges are
+    /// left out.
+    fn groupby_binned<U: ?Sized>(&'a self, column: &'a U, edges: &[T]) -> Result<GroupBy<'a, T>>
+        where String: From<&'a U>,
+              T: PartialOrd
+    {
+        let column: String = column.into();
+        let i = position(&self.columns[..], &column)?;
+        check_edges(edges, 0)?;
+        let mut groups: Vec<Vec<usize>> = vec![Vec::new(); edges.len() - 1];
+        for (r, x) in self.data.column(i).iter().enumerate() {
+            if let Some(b) = bin_of(x, edges) {
+                groups[b].push(r);
+            }
+        }
+        let groups = edges.iter()
+            .cloned()
+            .zip(groups.into_iter())
+            .filter(|&(_, ref g)| !g.is_empty())
+            .collect();
+        Ok(GroupBy::new(self, column, groups))
+    }
+
+    /// The distinct values of a column, in order of first appearance.
+    fn unique<U: ?Sized>(&'a self, column: &'a U) -> Result<Row<T>>
+        where String: From<&'a U>
+    {
+        let column: String = column.into();
+        let i = position(&self.columns[..], &column)?;
+        let values: Vec<T> = self.data.column(i).iter().cloned().collect();
+        let keys: Vec<T> = group_positions(&values[..]).into_iter().map(|(k, _)| k).collect();
+        Ok(Array::from_vec(keys))
+    }
+
+    /// Reduce every cell of the dataframe to a single value. Empty cells are skipped, except by
+    /// `Agg::EmptyCount`, which counts them.
+    fn reduce(&'a self, agg: Agg) -> T
+        where T: PartialOrd
+    {
+        reduce(self.data.iter(), agg)
+    }
+
+    /// Count the occurrences of each distinct value of a column. The result has the value and its
+    /// count on each row, most frequent first, with ties in order of first appearance.
+    fn value_counts<U: ?Sized>(&'a self, column: &'a U) -> Result<DataFrame<T>>
+        where String: From<&'a U>
+    {
+        let column: String = column.into();
+        let i = position(&self.columns[..], &column)?;
+        let values: Vec<T> = self.data.column(i).iter().cloned().collect();
+        let mut groups = group_positions(&values[..]);
+        groups.sort_by(|a, b| b.1.len().cmp(&a.1.len()));
+        let n = groups.len();
+        let mut c = Vec::with_capacity(n * 2);
+        for (k, g) in groups {
+            c.push(k);
+            c.push(count(g.len()));
+        }
+        Ok(DataFrame {
+            columns: vec![column, "count".to_string()],
+            data: Array::from_shape_vec((n, 2), c).unwrap(),
+            index: (0..n).map(|x| x.to_string()).collect(),
+            index_sorted: false,
+        })
+    }
+
+    /// Select the rows whose hierarchical index has `key` at `level`, and drop that level from
+    /// their labels.
+    fn xs(&'a self, level: usize, key: &str) -> Result<DataFrame<T>> {
+        let index = MultiIndex::from_labels(&self.index[..]);
+        let positions: Vec<usize> = index.level(level)?
+            .iter()
+            .enumerate()
+            .filter(|&(_, x)| x == key)
+            .map(|(i, _)| i)
+            .collect();
+        let rest = index.droplevel(level)?;
+        Ok(DataFrame {
+            columns: self.columns.clone(),
+            data: take(&self.data, UtahAxis::Row, &positions[..]),
+            index: take_labels(&rest.labels()[..], &positions[..]),
+            index_sorted: false,
+        })
+    }
+
+    /// Group the rows by the labels at one level of their hierarchical index, in order of first
+    /// appearance.
+    fn groupby_level(&'a self, level: usize) -> Result<GroupBy<'a, T>> {
+        let labels = MultiIndex::from_labels(&self.index[..]).level(level)?;
+        let mut seen: HashMap<&str, usize> = HashMap::new();
+        let mut groups: Vec<(String, Vec<usize>)> = Vec::new();
+        for (i, x) in labels.iter().enumerate() {
+            let g = *seen.entry(&x[..]).or_insert(groups.len());
+            if g == groups.len() {
+                groups.push((escape_level(x), Vec::new()));
+            }
+            groups[g].1.push(i);
+        }
+        Ok(GroupBy::labelled(self, groups))
+    }
+
+    /// Insert a new column at position `pos`, shifting the columns after it to the right.
+    fn insert_column_at<U: ?Sized>(&'a self,
+                                   pos: usize,
+                                   name: &'a U,
+                                   data: ArrayView1<'a, T>)
+                                   -> InsertIter<'a, T>
+        where String: From<&'a U>
+    {
+        Insert::new(self.df_iter(UtahAxis::Column),
+                    pos,
+                    name.into(),
+                    data,
+                    self.index.clone(),
+                    UtahAxis::Column)
+    }
+
+    /// Remove a column from the dataframe and return its values.
+    fn pop_column<U: ?Sized>(&mut self, name: &U) -> Result<Row<T>>
+        where for<'b> String: From<&'b U>
+    {
+        let name: String = name.into();
+        let i = position(&self.columns[..], &name)?;
+        let values = self.data.column(i).to_owned();
+        let rest: Vec<usize> = (0..self.columns.len()).filter(|&x| x != i).collect();
+        self.data = take(&self.data, UtahAxis::Column, &rest[..]);
+        self.columns.remove(i);
+        Ok(values)
+    }
+
+    /// Add a column computed from each row, which is looked up by column name.
+    fn with_column<U: ?Sized, F>(&'a self, name: &'a U, f: F) -> WithColumnIter<'a, T, F>
+        where String: From<&'a U>,
+              F: Fn(&RowView<'a, T>) -> T
+    {
+        WithColumn::new(self.df_iter(UtahAxis::Row), &self.columns[..], name.into(), f)
+    }
+
+    /// Start a transaction, whose changes to the dataframe can be committed or rolled back.
+    fn begin(&'a mut self) -> Transaction<'a, T> {
+        Transaction::new(self)
+    }
+
+    /// Map each row or column, along the specified `UtahAxis`, to a new row or column.
+    fn apply<F>(&'a self, f: F, axis: UtahAxis) -> ApplyIter<'a, T, F>
+        where F: Fn(ArrayView1<'a, T>) -> Vec<T>
+    {
+        match axis {
+            UtahAxis::Row => {
+                Apply::new(self.df_iter(UtahAxis::Row), f, self.columns.clone(), UtahAxis::Row)
+            }
+            UtahAxis::Column => {
+                Apply::new(self.df_iter(UtahAxis::Column),
+                           f,
+                           self.index.clone(),
+                           UtahAxis::Column)
+            }
+        }
+    }
+
+    /// Define a virtual column as an expression over other columns. It is computed only when
+    /// read.
+    fn define<U: ?Sized>(&'a self, name: &U, expr: Expr<T>) -> VirtualFrame<'a, T>
+        where for<'b> String: From<&'b U>
+    {
+        VirtualFrame::new(self).define(name, expr)
+    }
+
+    /// Extract a column as a series labelled by the index.
+    fn column<U: ?Sized>(&'a self, name: &U) -> Result<Series<T>>
+        where for<'b> String: From<&'b U>
+    {
+        let name: String = name.into();
+        let i = position(&self.columns[..], &name)?;
+        Ok(Series {
+            name: name,
+            index: self.index.clone(),
+            data: self.data.column(i).to_owned(),
+        })
+    }
+
+    /// Insert a series as a new column at position `pos`, aligned on the index. Rows missing
+    /// from the series are left empty. Fails if a column already has the name of the series.
+    fn insert_series_at(&mut self, pos: usize, series: &Series<T>) -> Result<()> {
+        if self.columns.contains(&series.name) {
+            return Err(ErrorKind::DuplicateColumn(series.name.clone()).into());
+        }
+        let values = series.reindex(&self.index[..]).data;
+        let pos = pos.min(self.columns.len());
+        let mut c = Vec::with_capacity(self.data.len() + values.len());
+        for (r, row) in self.data.outer_iter().enumerate() {
+            c.extend(row.iter().take(pos).cloned());
+            c.push(values[r].clone());
+            c.extend(row.iter().skip(pos).cloned());
+        }
+        self.data = Array::from_shape_vec((self.index.len(), self.columns.len() + 1), c).unwrap();
+        self.columns.insert(pos, series.name.clone());
+        Ok(())
+    }
+
+    /// Add a series as the last column, aligned on the index, as in `insert_series_at`.
+    fn append_series(&mut self, series: &Series<T>) -> Result<()> {
+        let pos = self.columns.len();
+        self.insert_series_at(pos, series)
+    }
+
+    /// Record the smallest and largest value of a column in each block of `block_size` rows, to
+    /// speed up `filter_range`.
+    fn block_index<U: ?Sized>(&'a self, column: &U, block_size: usize) -> Result<BlockIndex<T>>
+        where for<'b> String: From<&'b U>,
+              T: PartialOrd
+    {
+        let column: String = column.into();
+        let i = position(&self.columns[..], &column)?;
+        let values: Vec<T> = self.data.column(i).iter().cloned().collect();
+        Ok(BlockIndex::new(column, &values[..], block_size))
+    }
+
+    /// Keep the rows whose value in the indexed column lies within `[lo, hi]`. Only the blocks
+    /// whose bounds overlap the range are scanned.
+    fn filter_range(&'a self, index: &BlockIndex<T>, lo: &T, hi: &T) -> Result<DataFrame<T>>
+        where T: PartialOrd
+    {
+        if index.nrows != self.index.len() {
+            return Err(ErrorKind::StaleIndex(format!("expected {} rows, found {}",
+                                                     index.nrows,
+                                                     self.index.len()))
+                .into());
+        }
+        let i = position(&self.columns[..], &index.column)?;
+        let column = self.data.column(i);
+        let mut positions = Vec::new();
+        for (start, end) in index.candidates(lo, hi) {
+            positions.extend((start..end).filter(|&r| {
+                let x = &column[r];
+                !x.is_empty() && *x >= *lo && *x <= *hi
+            }));
+        }
+        Ok(DataFrame {
+            columns: self.columns.clone(),
+            data: take(&self.data, UtahAxis::Row, &positions[..]),
+            index: take_labels(&self.index[..], &positions[..]),
+            index_sorted: false,
+        })
+    }
+
+    /// Combine every entry with a scalar, in place, along the specified `UtahAxis`.
+    fn scalar_op(&'a mut self, op: ArithOp, value: T, axis: UtahAxis) -> ScalarOpIter<'a, T> {
+        let columns = self.columns.clone();
+        let index = self.index.clone();
+        match axis {
+            UtahAxis::Row => {
+                ScalarOp::new(self.df_iter_mut(UtahAxis::Row), op, value, columns, UtahAxis::Row)
+            }
+            UtahAxis::Column => {
+                ScalarOp::new(self.df_iter_mut(UtahAxis::Column),
+                              op,
+                              value,
+                              index,
+                              UtahAxis::Column)
+            }
+        }
+    }
+
+    /// Matrix product with another dataframe, whose index must match the columns of this one.
+    /// The result keeps this index and takes the other's columns.
+    fn dot(&'a self, other: &DataFrame<T>) -> Result<DataFrame<T>> {
+        if self.columns != other.index {
+            return Err(ErrorKind::MisalignedLabels("the columns of the left frame must match the \
+                                                    index of the right frame"
+                    .into())
+                .into());
+        }
+        let (n, k) = self.data.dim();
+        let m = other.columns.len();
+        let mut c = Vec::with_capacity(n * m);
+        for i in 0..n {
+            for j in 0..m {
+                c.push((0..k).fold(T::zero(), |acc, l| {
+                    acc + self.data[[i, l]].clone() * other.data[[l, j]].clone()
+                }));
+            }
+        }
+        Ok(DataFrame {
+            columns: other.columns.clone(),
+            data: Array::from_shape_vec((n, m), c).unwrap(),
+            index: self.index.clone(),
+            index_sorted: false,
+        })
+    }
+
+    /// Get the running sum of entries along the specified `UtahAxis`, skipping empty entries.
+    fn cumsum(&'a self, axis: UtahAxis) -> CumulativeIter<'a, T> {
+        match axis {
+            UtahAxis::Row => {
+                Cumulative::new(self.df_iter(UtahAxis::Row),
+                                ArithOp::Add,
+                                self.columns.clone(),
+                                UtahAxis::Row)
+            }
+            UtahAxis::Column => {
+                Cumulative::new(self.df_iter(UtahAxis::Column),
+                                ArithOp::Add,
+                                self.index.clone(),
+                                UtahAxis::Column)
+            }
+        }
+    }
+
+    /// Get the running product of entries along the specified `UtahAxis`, skipping empty
+    /// entries.
+    fn cumprod(&'a self, axis: UtahAxis) -> CumulativeIter<'a, T> {
+        match axis {
+            UtahAxis::Row => {
+                Cumulative::new(self.df_iter(UtahAxis::Row),
+                                ArithOp::Mul,
+                                self.columns.clone(),
+                                UtahAxis::Row)
+            }
+            UtahAxis::Column => {
+                Cumulative::new(self.df_iter(UtahAxis::Column),
+                                ArithOp::Mul,
+                                self.index.clone(),
+                                UtahAxis::Column)
+            }
+        }
+    }
+
+    /// Check that the non-empty values of a column are monotonic in the given direction. The
+    /// error lists the labels of the rows that break the order with their predecessor.
+    fn assert_monotonic<U: ?Sized>(&'a self, column: &U, direction: Monotonic) -> Result<()>
+        where for<'b> String: From<&'b U>,
+              T: PartialOrd
+    {
+        let column: String = column.into();
+        let i = position(&self.columns[..], &column)?;
+        let mut offending = Vec::new();
+        let mut previous: Option<&T> = None;
+        for (r, x) in self.data.column(i).iter().enumerate().filter(|&(_, x)| !x.is_empty()) {
+            if let Some(p) = previous {
+                let ok = match direction {
+                    Monotonic::Increasing => x >= p,
+                    Monotonic::Decreasing => x <= p,
+                    Monotonic::StrictlyIncreasing => x > p,
+                    Monotonic::StrictlyDecreasing => x < p,
+                };
+                if !ok {
+                    offending.push(self.index[r].clone());
+                }
+            }
+            previous = Some(x);
+        }
+        if offending.is_empty() {
+            Ok(())
+        } else {
+            Err(ErrorKind::ConstraintViolation(column, offending).into())
+        }
+    }
+
+    /// Check that the non-empty values of a column lie within `[lo, hi]`. The error lists the
+    /// labels of the rows outside the range.
+    fn assert_in_range<U: ?Sized>(&'a self, column: &U, lo: &T, hi: &T) -> Result<()>
+        where for<'b> String: From<&'b U>,
+              T: PartialOrd
+    {
+        let column: String = column.into();
+        let i = position(&self.columns[..], &column)?;
+        let offending: Vec<String> = self.data
+            .column(i)
+            .iter()
+            .zip(self.index.iter())
+            .filter(|&(x, _)| !x.is_empty() && (x < lo || x > hi))
+            .map(|(_, l)| l.clone())
+            .collect();
+        if offending.is_empty() {
+            Ok(())
+        } else {
+            Err(ErrorKind::ConstraintViolation(column, offending).into())
+        }
+    }
+
+    /// Move entries by `periods` positions along the specified `UtahAxis`, filling the vacated
+    /// entries with `fill`.
+    fn shift(&'a self, periods: isize, fill: T, axis: UtahAxis) -> Shift<'a, T> {
+        match axis {
+            UtahAxis::Row => {
+                Shift::new(self.df_iter(UtahAxis::Row),
+                           periods,
+                           LagOp::Shift(fill),
+                           self.columns.clone(),
+                           UtahAxis::Row)
+            }
+            UtahAxis::Column => {
+                Shift::new(self.df_iter(UtahAxis::Column),
+                           periods,
+                           LagOp::Shift(fill),
+                           self.index.clone(),
+                           UtahAxis::Column)
+            }
+        }
+    }
+
+    /// Subtract from each entry the one `periods` positions before it along the specified
+    /// `UtahAxis`. Entries without a predecessor are empty.
+    fn diff(&'a self, periods: isize, axis: UtahAxis) -> Shift<'a, T> {
+        match axis {
+            UtahAxis::Row => {
+                Shift::new(self.df_iter(UtahAxis::Row),
+                           periods,
+                           LagOp::Diff,
+                           self.columns.clone(),
+                           UtahAxis::Row)
+            }
+            UtahAxis::Column => {
+                Shift::new(self.df_iter(UtahAxis::Column),
+                           periods,
+                           LagOp::Diff,
+                           self.index.clone(),
+                           UtahAxis::Column)
+            }
+        }
+    }
+
+    /// Get the first non-empty entry along the specified `UtahAxis`.
+    fn first_valid(&'a self, axis: UtahAxis) -> ReduceIter<'a, T> {
+        match axis {
+            UtahAxis::Row => {
+                Reduce::new(self.df_iter(UtahAxis::Row),
+                            Agg::FirstValid,
+                            self.index.clone(),
+                            UtahAxis::Row)
+            }
+            UtahAxis::Column => {
+                Reduce::new(self.df_iter(UtahAxis::Column),
+                            Agg::FirstValid,
+                            self.columns.clone(),
+                            UtahAxis::Column)
+            }
+        }
+    }
+
+    /// Get the last non-empty entry along the specified `UtahAxis`.
+    fn last_valid(&'a self, axis: UtahAxis) -> ReduceIter<'a, T> {
+        match axis {
+            UtahAxis::Row => {
+                Reduce::new(self.df_iter(UtahAxis::Row),
+                            Agg::LastValid,
+                            self.index.clone(),
+                            UtahAxis::Row)
+            }
+            UtahAxis::Column => {
+                Reduce::new(self.df_iter(UtahAxis::Column),
+                            Agg::LastValid,
+                            self.columns.clone(),
+                            UtahAxis::Column)
+            }
+        }
+    }
+
+    /// Rank the entries along the specified `UtahAxis` in place, breaking ties with `method`.
+    /// Empty entries stay empty unless `empties` places them before or after the others.
+    fn rank(&'a mut self,
+            axis: UtahAxis,
+            method: RankMethod,
+            empties: Option<EmptyPosition>)
+            -> RankIter<'a, T> {
+        match axis {
+            UtahAxis::Row => {
+                let columns = self.columns.clone();
+                Rank::new(self.df_iter_mut(UtahAxis::Row), method, empties, columns, axis)
+            }
+            UtahAxis::Column => {
+                let index = self.index.clone();
+                Rank::new(self.df_iter_mut(UtahAxis::Column), method, empties, index, axis)
+            }
+        }
+    }
+
+    /// The set of rows whose value in `column` satisfies `f`.
+    fn mask<U: ?Sized, F>(&'a self, column: &U, f: F) -> Result<RowSet>
+        where for<'b> String: From<&'b U>,
+              F: Fn(&T) -> bool
+    {
+        let name: String = column.into();
+        let i = position(&self.columns[..], &name)?;
+        let column = self.data.column(i);
+        Ok(RowSet::from_fn(column.len(), |r| f(&column[r])))
+    }
+
+    /// Keep the rows in `rows`, in their original order.
+    fn take_rows(&'a self, rows: &RowSet) -> Result<DataFrame<T>> {
+        if rows.len() != self.index.len() {
+            return Err(ErrorKind::IndexShapeMismatch(self.index.len().to_string(),
+                                                     rows.len().to_string())
+                .into());
+        }
+        let positions = rows.positions();
+        Ok(DataFrame {
+            columns: self.columns.clone(),
+            data: take(&self.data, UtahAxis::Row, &positions[..]),
+            index: take_labels(&self.index[..], &positions[..]),
+            index_sorted: false,
+        })
+    }
+
+    /// Limit every entry to `[lo, hi]`, in place. Empty entries stay empty.
+    fn clip(&'a mut self, lo: T, hi: T) -> ClipIter<'a, T>
+        where T: PartialOrd
+    {
+        let columns = self.columns.clone();
+        Clip::new(self.df_iter_mut(UtahAxis::Row),
+                  Some(lo),
+                  Some(hi),
+                  columns,
+                  UtahAxis::Row)
+    }
+
+    /// Raise every entry below `lo` to `lo`, in place.
+    fn clip_lower(&'a mut self, lo: T) -> ClipIter<'a, T>
+        where T: PartialOrd
+    {
+        let columns = self.columns.clone();
+        Clip::new(self.df_iter_mut(UtahAxis::Row), Some(lo), None, columns, UtahAxis::Row)
+    }
+
+    /// Lower every entry above `hi` to `hi`, in place.
+    fn clip_upper(&'a mut self, hi: T) -> ClipIter<'a, T>
+        where T: PartialOrd
+    {
+        let columns = self.columns.clone();
+        Clip::new(self.df_iter_mut(UtahAxis::Row), None, Some(hi), columns, UtahAxis::Row)
+    }
+
+    /// Summarize each column by its count, mean, min, max and number of empty entries.
+    fn describe(&'a self) -> DataFrame<T>
+        where T: PartialOrd
+    {
+        let columns = self.data
+            .axis_iter(Axis(1))
+            .map(|c| DESCRIBE_STATS.iter().map(|&(_, agg)| reduce(c.iter(), agg)).collect())
+            .collect();
+        describe(self, columns)
+    }
+
+    /// Like `describe`, but reuse the statistics of columns already seen by `cache`.
+    fn describe_cached(&'a self, cache: &StatsCache<T>) -> Result<DataFrame<T>>
+        where T: PartialOrd
+    {
+        let columns = self.data
+            .axis_iter(Axis(1))
+            .map(|c| cache.column_stats(c))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(describe(self, columns))
+    }
+
+    /// Substitute `new` for every entry equal to `old`, for each `(old, new)` pair, in place. An
+    /// empty `old` matches empty entries. Only `columns` are touched, if given.
+    fn replace(&'a mut self, pairs: &[(T, T)], columns: Option<&[&str]>) -> ReplaceIter<'a, T> {
+        let index = self.index.clone();
+        let only = columns.map(|c| c.iter().map(|x| x.to_string()).collect());
+        Replace::new(self.df_iter_mut(UtahAxis::Column),
+                     pairs.to_vec(),
+                     only,
+                     index,
+                     UtahAxis::Column)
+    }
+
+    /// Substitute `f(x)` for every entry `x` for which it is `Some`, in place. Only `columns` are
+    /// touched, if given.
+    fn replace_with<F>(&'a mut self, f: F, columns: Option<&[&str]>) -> ReplaceWithIter<'a, T, F>
+        where F: Fn(&T) -> Option<T>
+    {
+        let index = self.index.clone();
+        let only = columns.map(|c| c.iter().map(|x| x.to_string()).collect());
+        ReplaceWith::new(self.df_iter_mut(UtahAxis::Column), f, only, index, UtahAxis::Column)
+    }
+
+    /// A boolean matrix holding `f` of each entry.
+    fn cell_mask<F>(&'a self, f: F) -> Matrix<bool>
+        where F: Fn(&T) -> bool
+    {
+        let (nrows, ncols) = self.data.dim();
+        Array::from_shape_vec((nrows, ncols), self.data.iter().map(f).collect()).unwrap()
+    }
+
+    /// Keep the entries where `mask` is true and substitute `other` elsewhere.
+    fn where_mask(&'a self, mask: &Matrix<bool>, other: T) -> Result<DataFrame<T>> {
+        if mask.dim() != self.data.dim() {
+            return Err(ErrorKind::RowShapeMismatch.into());
+        }
+        let d = self.data
+            .iter()
+            .zip(mask.iter())
+            .map(|(x, &keep)| if keep { x.clone() } else { other.clone() })
+            .collect();
+        Ok(DataFrame {
+            columns: self.columns.clone(),
+            data: Array::from_shape_vec(self.data.dim(), d).unwrap(),
+            index: self.index.clone(),
+            index_sorted: false,
+        })
+    }
+
+    /// Keep the entries where `mask` is true and take the matching entries of `other` elsewhere.
+    /// Both frames must have the same labels.
+    fn where_mask_df(&'a self, mask: &Matrix<bool>, other: &DataFrame<T>) -> Result<DataFrame<T>> {
+        if self.columns != other.columns || self.index != other.index {
+            return Err(ErrorKind::MisalignedLabels("both frames must have the same columns and \
+                                                    index"
+                    .into())
+                .into());
+        }
+        if mask.dim() != self.data.dim() {
+            return Err(ErrorKind::RowShapeMismatch.into());
+        }
+        let d = self.data
+            .iter()
+            .zip(other.data.iter())
+            .zip(mask.iter())
+            .map(|((x, y), &keep)| if keep { x.clone() } else { y.clone() })
+            .collect();
+        Ok(DataFrame {
+            columns: self.columns.clone(),
+            data: Array::from_shape_vec(self.data.dim(), d).unwrap(),
+            index: self.index.clone(),
+            index_sorted: false,
+        })
+    }
+
+    /// A mutable view of a column, with its entries addressed by index label.
+    fn get_mut(&'a mut self, column: &str) -> Option<RowViewMut<'a, T>> {
+        let DataFrame { ref columns, ref mut data, ref index, .. } = *self;
+        columns.iter().position(|x| x == column).map(move |i| {
+            RowViewMut {
+                labels: &index[..],
+                values: data.column_mut(i),
+            }
+        })
+    }
+
+    /// A mutable view of a row, with its entries addressed by column name.
+    fn row_mut(&'a mut self, label: &str) -> Option<RowViewMut<'a, T>> {
+        let DataFrame { ref columns, ref mut data, ref index, .. } = *self;
+        index.iter().position(|x| x == label).map(move |i| {
+            RowViewMut {
+                labels: &columns[..],
+                values: data.row_mut(i),
+            }
+        })
+    }
+
+    /// Get the median of entries along the specified `UtahAxis`.
+    fn median(&'a self, axis: UtahAxis) -> MedianIter<'a, T> {
+        match axis {
+            UtahAxis::Row => Median::new(self.df_iter(UtahAxis::Row), self.index.clone(), axis),
+            UtahAxis::Column => {
+                Median::new(self.df_iter(UtahAxis::Column), self.columns.clone(), axis)
+            }
+        }
+    }
+
+    /// Apply a stream of changes, in order. Each row of `changes` carries its operation in the
+    /// `op` column, read with `decode`, and the row's values for every column of the dataframe.
+    /// Rows are matched on the `keys` columns through a hash index (see `util::keys`): inserts
+    /// append a row, updates overwrite the matching row, and deletes drop the matching row, if
+    /// any. Inserting a key that is present, updating one that is not, or starting from a
+    /// dataframe with repeated keys is an `InvalidChangeOp` error.
+    fn apply_cdc<F>(&'a self,
+                    changes: &DataFrame<T>,
+                    op: &str,
+                    keys: &[&str],
+                    decode: F)
+                    -> Result<DataFrame<T>>
+        where F: Fn(&T) -> Option<ChangeOp>
+    {
+        let op = position(&changes.columns[..], op)?;
+        let mut from = Vec::with_capacity(self.columns.len());
+        for c in &self.columns {
+            from.push(position(&changes.columns[..], c)?);
+        }
+        let mut key_positions = Vec::with_capacity(keys.len());
+        for k in keys {
+            key_positions.push(position(&self.columns[..], k)?);
+        }
+        let change_keys: Vec<usize> = key_positions.iter().map(|&k| from[k]).collect();
+        let invalid = |what: &str, label: &String| -> Error {
+            ErrorKind::InvalidChangeOp(format!("{} at row '{}'", what, label)).into()
+        };
+
+        let mut rows: Vec<Option<(String, Vec<T>)>> = self.index
+            .iter()
+            .zip(self.data.outer_iter())
+            .map(|(i, r)| Some((i.clone(), r.iter().cloned().collect())))
+            .collect();
+        let mut lookup = HashMap::with_capacity(rows.len());
+        for (p, (label, r)) in self.index.iter().zip(self.data.outer_iter()).enumerate() {
+            let key = RowKey {
+                row: r,
+                keys: &key_positions[..],
+            };
+            if lookup.insert(key, p).is_some() {
+                return Err(invalid("repeated key", label));
+            }
+        }
+
+        for (label, change) in changes.index.iter().zip(changes.data.outer_iter()) {
+            let key = RowKey {
+                row: change,
+                keys: &change_keys[..],
+            };
+            let values = || from.iter().map(|&c| change[c].clone()).collect::<Vec<T>>();
+            match decode(&change[op]) {
+                Some(ChangeOp::Insert) => {
+                    if lookup.contains_key(&key) {
+                        return Err(invalid("insert of a present key", label));
+                    }
+                    lookup.insert(key, rows.len());
+                    rows.push(Some((label.clone(), values())));
+                }
+                Some(ChangeOp::Update) => {
+                    match lookup.get(&key) {
+                        Some(&p) => {
+                            if let Some((_, ref mut row)) = rows[p] {
+                                *row = values();
+                            }
+                        }
+                        None => return Err(invalid("update of a missing key", label)),
+                    }
+                }
+                Some(ChangeOp::Delete) => {
+                    if let Some(p) = lookup.remove(&key) {
+                        rows[p] = None;
+                    }
+                }
+                None => {
+                    return Err(ErrorKind::InvalidChangeOp(format!("{:?}", change[op])).into())
+                }
+            }
+        }
+
+        let mut index = Vec::new();
+        let mut d = Vec::new();
+        for (i, r) in rows.into_iter().filter_map(|r| r) {
+            index.push(i);
+            d.extend(r);
+        }
+        Ok(DataFrame {
+            columns: self.columns.clone(),
+            data: Array::from_shape_vec((index.len(), self.columns.len()), d).unwrap(),
+            index: index,
+            index_sorted: false,
+        })
+    }
+
+    /// Stack the rows of `other` under the rows of this dataframe, even if their columns differ.
+    /// The result has the columns of both, and each row is filled with empty values in the
+    /// columns its dataframe lacks. The columns added on either side are reported.
+    fn concat_evolve(&'a self, other: &DataFrame<T>) -> Result<(DataFrame<T>, SchemaEvolution)> {
+        let aligned = align_labels(&self.columns[..], &other.columns[..]);
+        let evolution = SchemaEvolution {
+            added: aligned.iter().filter(|c| c.1.is_none()).map(|c| c.0.clone()).collect(),
+            missing: aligned.iter().filter(|c| c.2.is_none()).map(|c| c.0.clone()).collect(),
+        };
+        Ok((self.concat_rows(other, ConcatJoin::Outer, None)?, evolution))
+    }
+
+    /// Get the most frequent entry along the specified `UtahAxis`.
+    fn mode(&'a self, axis: UtahAxis) -> ModeIter<'a, T> {
+        match axis {
+            UtahAxis::Row => Mode::new(self.df_iter(UtahAxis::Row), self.index.clone(), axis),
+            UtahAxis::Column => {
+                Mode::new(self.df_iter(UtahAxis::Column), self.columns.clone(), axis)
+            }
+        }
+    }
+
+    /// Count the non-empty entries along the specified `UtahAxis`.
+    fn count(&'a self, axis: UtahAxis) -> ReduceIter<'a, T> {
+        match axis {
+            UtahAxis::Row => {
+                Reduce::new(self.df_iter(UtahAxis::Row),
+                            Agg::Count,
+                            self.index.clone(),
+                            UtahAxis::Row)
+            }
+            UtahAxis::Column => {
+                Reduce::new(self.df_iter(UtahAxis::Column),
+                            Agg::Count,
+                            self.columns.clone(),
+                            UtahAxis::Column)
+            }
+        }
+    }
+
+    /// The correlation between every pair of columns, as a square dataframe labeled by column on
+    /// both axes. Each pair only uses the rows where both entries are non-empty.
+    fn corr(&'a self, method: CorrMethod) -> DataFrame<f64>
+        where T: Into<f64>
+    {
+        let n = self.columns.len();
+        let mut d = Vec::with_capacity(n * n);
+        for i in 0..n {
+            for j in 0..n {
+                let (x, y): (Vec<f64>, Vec<f64>) = self.data
+                    .column(i)
+                    .iter()
+                    .zip(self.data.column(j).iter())
+                    .filter(|&(a, b)| !a.is_empty() && !b.is_empty())
+                    .map(|(a, b)| (a.clone().into(), b.clone().into()))
+                    .unzip();
+                d.push(stats::corr(&x[..], &y[..], method));
+            }
+        }
+        DataFrame {
+            columns: self.columns.clone(),
+            data: Array::from_shape_vec((n, n), d).unwrap(),
+            index: self.columns.clone(),
+            index_sorted: false,
+        }
+    }
+
+    /// Get the geometric mean of entries along the specified `UtahAxis`. Fails if any non-empty
+    /// entry is not positive.
+    fn gmean(&'a self, axis: UtahAxis) -> Result<GMeanIter<'a, T>>
+        where T: PartialOrd + Into<f64> + From<f64>
+    {
+        check_positive(self)?;
+        Ok(match axis {
+            UtahAxis::Row => GMean::new(self.df_iter(UtahAxis::Row), self.index.clone(), axis),
+            UtahAxis::Column => {
+                GMean::new(self.df_iter(UtahAxis::Column), self.columns.clone(), axis)
+            }
+        })
+    }
+
+    /// Get the harmonic mean of entries along the specified `UtahAxis`. Fails if any non-empty
+    /// entry is not positive.
+    fn hmean(&'a self, axis: UtahAxis) -> Result<HMeanIter<'a, T>>
+        where T: PartialOrd + Into<f64> + From<f64>
+    {
+        check_positive(self)?;
+        Ok(match axis {
+            UtahAxis::Row => HMean::new(self.df_iter(UtahAxis::Row), self.index.clone(), axis),
+            UtahAxis::Column => {
+                HMean::new(self.df_iter(UtahAxis::Column), self.columns.clone(), axis)
+            }
+        })
+    }
+
+    /// Multiply the non-empty entries along the specified `UtahAxis`.
+    fn prod(&'a self, axis: UtahAxis) -> ReduceIter<'a, T> {
+        match axis {
+            UtahAxis::Row => {
+                Reduce::new(self.df_iter(UtahAxis::Row),
+                            Agg::Prod,
+                            self.index.clone(),
+                            UtahAxis::Row)
+            }
+            UtahAxis::Column => {
+                Reduce::new(self.df_iter(UtahAxis::Column),
+                            Agg::Prod,
+                            self.columns.clone(),
+                            UtahAxis::Column)
+            }
+        }
+    }
+
+    /// Count the distinct non-empty entries along the specified `UtahAxis`. Given a `tolerance`,
+    /// sorted entries no further apart than it count as one, which suits float data.
+    fn nunique(&'a self, axis: UtahAxis, tolerance: Option<T>) -> NUniqueIter<'a, T> {
+        match axis {
+            UtahAxis::Row => {
+                NUnique::new(self.df_iter(UtahAxis::Row),
+                             tolerance,
+                             self.index.clone(),
+                             UtahAxis::Row)
+            }
+            UtahAxis::Column => {
+                NUnique::new(self.df_iter(UtahAxis::Column),
+                             tolerance,
+                             self.columns.clone(),
+                             UtahAxis::Column)
+            }
+        }
+    }
+
+    /// An expanding window over the rows, from the first row to each row in turn.
+    fn expanding(&'a self, min_periods: usize) -> Rolling<'a, T> {
+        Rolling::expanding(self.df_iter(UtahAxis::Column), min_periods, self.index.clone())
+    }
+
+    /// An exponentially weighted window over the rows.
+    fn ewm(&'a self, decay: Decay) -> Result<Ewm<'a, T>> {
+        Ewm::new(self.df_iter(UtahAxis::Column), decay, self.index.clone())
+    }
+
+    /// Reduce each row or column along the specified `UtahAxis` with `f`.
+    fn agg<F>(&'a self, f: F, axis: UtahAxis) -> AggWithIter<'a, T, F>
+        where F: Fn(ArrayView1<'a, T>) -> T
+    {
+        match axis {
+            UtahAxis::Row => AggWith::new(self.df_iter(UtahAxis::Row), f, self.index.clone(), axis),
+            UtahAxis::Column => {
+                AggWith::new(self.df_iter(UtahAxis::Column), f, self.columns.clone(), axis)
+            }
+        }
+    }
+
+    /// Group the rows by the values of several key columns, in order of first appearance. Each
+    /// group is labelled by its key values, as a `MultiIndex`. Key values are compared as in
+    /// `groupby`, by their debug representation, with empty values all equal.
+    fn groupby_multi(&'a self, by: &[&str]) -> Result<GroupBy<'a, T>> {
+        let mut positions = Vec::with_capacity(by.len());
+        for k in by {
+            positions.push(position(&self.columns[..], k)?);
+        }
+        let mut groups: Vec<(Vec<T>, Vec<usize>)> = Vec::new();
+        let mut lookup: HashMap<RowKey<T>, usize> = HashMap::new();
+        for (r, row) in self.data.outer_iter().enumerate() {
+            let key = RowKey {
+                row: row,
+                keys: &positions[..],
+            };
+            match lookup.get(&key).cloned() {
+                Some(g) => groups[g].1.push(r),
+                None => {
+                    let values = positions.iter().map(|&i| key.row[i].clone()).collect();
+                    lookup.insert(key, groups.len());
+                    groups.push((values, vec![r]));
+                }
+            }
+        }
+        Ok(GroupBy::multi(self, by.iter().map(|k| k.to_string()).collect(), groups))
+    }
+
+    /// Reshape long data into a wide dataframe, with a row for each distinct value of `index`
+    /// and a column for each distinct value of `columns`, both in order of first appearance.
+    /// Each cell reduces the `values` of the matching rows with `agg`, and cells with no
+    /// matching rows are empty.
+    fn pivot_table(&'a self,
+                   index: &str,
+                   columns: &str,
+                   values: &str,
+                   agg: Agg)
+                   -> Result<DataFrame<T>>
+        where T: PartialOrd
+    {
+        let v = position(&self.columns[..], values)?;
+        let column = self.data.column(v);
+        pivot(self, index, columns, T::empty(), |g| reduce(g.iter().map(|&k| &column[k]), agg))
+    }
+
+    /// Reshape wide data into long data: one row per row and column of `value_vars`, holding
+    /// the `id_vars` of the row, the name of the column in `variable` and its entry in `value`.
+    /// The rows are labelled by position.
+    fn melt(&'a self, id_vars: &[&str], value_vars: &[&str]) -> Result<DataFrame<T>>
+        where T: From<String>
+    {
+        let mut ids = Vec::with_capacity(id_vars.len());
+        for c in id_vars {
+            ids.push(position(&self.columns[..], c)?);
+        }
+        let mut vars = Vec::with_capacity(value_vars.len());
+        for c in value_vars {
+            vars.push(position(&self.columns[..], c)?);
+        }
+        let width = ids.len() + 2;
+        let mut d = Vec::with_capacity(vars.len() * self.index.len() * width);
+        for &v in &vars {
+            for row in self.data.outer_iter() {
+                d.extend(ids.iter().map(|&i| row[i].clone()));
+                d.push(T::from(self.columns[v].clone()));
+                d.push(row[v].clone());
+            }
+        }
+        let mut columns: Vec<String> = id_vars.iter().map(|c| c.to_string()).collect();
+        columns.push("variable".to_string());
+        columns.push("value".to_string());
+        let n = d.len() / width;
+        Ok(DataFrame {
+            columns: columns,
+            data: Array::from_shape_vec((n, width), d).unwrap(),
+            index: (0..n).map(|i| i.to_string()).collect(),
+            index_sorted: false,
+        })
+    }
+
+    /// Count the rows with each pair of values of the `a` and `b` columns, with a row for each
+    /// distinct value of `a` and a column for each distinct value of `b`. With `normalize`, the
+    /// counts are divided by the total of their row or column. The counts are `f64` whatever the
+    /// type of the frame, so that the fractions do not truncate.
+    fn crosstab(&'a self, a: &str, b: &str, normalize: Option<UtahAxis>) -> Result<DataFrame<f64>> {
+        let mut df = pivot(self, a, b, 0., |g| g.len() as f64)?;
+        match normalize {
+            Some(UtahAxis::Row) => {
+                for mut row in df.data.outer_iter_mut() {
+                    let total: f64 = row.iter().sum();
+                    row.mapv_inplace(|x| x / total);
+                }
+            }
+            Some(UtahAxis::Column) => {
+                for mut column in df.data.axis_iter_mut(Axis(1)) {
+                    let total: f64 = column.iter().sum();
+                    column.mapv_inplace(|x| x / total);
+                }
+            }
+            None => {}
+        }
+        Ok(df)
+    }
+
+    /// Group the rows into buckets of time, by their index labels read as timestamps. Buckets
+    /// are `every` long, aligned on the epoch, labelled by their start and in chronological
+    /// order; buckets with no rows are left out. Reduce them with the usual `GroupBy`
+    /// aggregations, such as `sum`, `mean`, `last` or `ohlc`.
+    fn resample(&'a self, every: Duration) -> Result<GroupBy<'a, T>> {
+        let every = every.as_secs() as i64;
+        if every == 0 {
+            return Err(ErrorKind::InvalidTimestamp("0S".to_string()).into());
+        }
+        let mut buckets: HashMap<i64, Vec<usize>> = HashMap::new();
+        for (r, label) in self.index.iter().enumerate() {
+            let t = time::parse_timestamp(label)?;
+            let start = t - ((t % every) + every) % every;
+            buckets.entry(start).or_insert_with(Vec::new).push(r);
+        }
+        let mut buckets: Vec<(i64, Vec<usize>)> = buckets.into_iter().collect();
+        buckets.sort_by_key(|&(b, _)| b);
+        Ok(GroupBy::labelled(self,
+                             buckets.into_iter()
+                                 .map(|(b, g)| (time::format_timestamp(b), g))
+                                 .collect()))
+    }
+
+    /// Bin the values of a column like `cut`, into `q` bins holding about as many values each.
+    /// The edges are the quantiles of the non-empty values; it fails if ties make two edges
+    /// equal.
+    fn qcut<U: ?Sized>(&'a self,
+                       column: &'a U,
+                       q: usize,
+                       labels: &[&str])
+                       -> Result<DataFrame<InnerType>>
+        where String: From<&'a U>,
+              T: PartialOrd + From<f64>
+    {
+        let name: String = column.into();
+        let i = position(&self.columns[..], &name)?;
+        let values: Vec<T> = self.data.column(i).iter().cloned().collect();
+        let edges: Vec<T> = (0..q + 1)
+            .map(|k| rank::quantile(&values[..], k as f64 / q.max(1) as f64))
+            .collect();
+        self.cut(column, &edges[..], labels)
+    }
+
+    /// Count the values of a column in `bins` bins of equal width spanning its non-empty values.
+    /// Each row of the result holds the `lo` and `hi` edges of a bin and its `count`, with bins
+    /// closed on the right like in `cut`.
+    fn hist<U: ?Sized>(&'a self, column: &'a U, bins: usize) -> Result<DataFrame<T>>
+        where String: From<&'a U>,
+              T: PartialOrd
+    {
+        let column: String = column.into();
+        let i = position(&self.columns[..], &column)?;
+        let df = DataFrame {
+            columns: vec![column],
+            data: take(&self.data, UtahAxis::Column, &[i]),
+            index: self.index.clone(),
+            index_sorted: false,
+        };
+        let mut z = df.hist_all(bins);
+        z.columns[2] = "count".to_string();
+        Ok(z)
+    }
+
+    /// Count the values of every column in the same `bins` bins of equal width, spanning all the
+    /// non-empty values. Each row of the result holds the `lo` and `hi` edges of a bin, followed
+    /// by its count in each column.
+    fn hist_all(&'a self, bins: usize) -> DataFrame<T>
+        where T: PartialOrd
+    {
+        let edges = even_edges(self.data.iter(), bins).unwrap_or_default();
+        let nbins = edges.len().saturating_sub(1);
+        let counts: Vec<Vec<usize>> = self.data
+            .axis_iter(Axis(1))
+            .map(|c| if nbins == 0 { Vec::new() } else { bin_counts(c.iter(), &edges[..]) })
+            .collect();
+        let mut d = Vec::with_capacity(nbins * (counts.len() + 2));
+        for b in 0..nbins {
+            d.push(edges[b].clone());
+            d.push(edges[b + 1].clone());
+            d.extend(counts.iter().map(|c| count(c[b])));
+        }
+        let mut columns = vec!["lo".to_string(), "hi".to_string()];
+        columns.extend(self.columns.iter().cloned());
+        DataFrame {
+            data: Array::from_shape_vec((nbins, columns.len()), d).unwrap(),
+            columns: columns,
+            index: (0..nbins).map(|b| b.to_string()).collect(),
+            index_sorted: false,
+        }
+    }
+
+    /// Scale each column (or row) to zero mean and unit standard deviation, in place.
+    fn standardize(&'a mut self, axis: UtahAxis) -> StandardizeIter<'a, T>
+        where T: From<f64> + Into<f64>
+    {
+        match axis {
+            UtahAxis::Row => {
+                let columns = self.columns.clone();
+                Standardize::new(self.df_iter_mut(UtahAxis::Row), columns, UtahAxis::Row)
+            }
+            UtahAxis::Column => {
+                let index = self.index.clone();
+                Standardize::new(self.df_iter_mut(UtahAxis::Column), index, UtahAxis::Column)
+            }
+        }
+    }
+
+    /// Rescale each column (or row) linearly onto `feature_range`, in place.
+    fn minmax_scale(&'a mut self, feature_range: (T, T), axis: UtahAxis) -> MinMaxScaleIter<'a, T>
+        where T: PartialOrd
+    {
+        let other = match axis {
+            UtahAxis::Row => self.columns.clone(),
+            UtahAxis::Column => self.index.clone(),
+        };
+        MinMaxScale::new(self.df_iter_mut(axis.clone()), feature_range, other, axis)
+    }
+
+    /// Perform an inner left join between two dataframes on the values of the column `on`,
+    /// which both must hold.
+    fn inner_join_on(&'a self, other: &'a DataFrame<T>, on: &str) -> Result<InnerJoinIter<'a, T>> {
+        self.inner_join_on_keys(other, &[on])
+    }
+
+    /// Perform an outer left join between two dataframes on the values of the column `on`,
+    /// which both must hold.
+    fn outer_join_on(&'a self, other: &'a DataFrame<T>, on: &str) -> Result<OuterJoinIter<'a, T>> {
+        self.outer_join_on_keys(other, &[on])
+    }
+
+    /// Perform an inner left join between two dataframes on the values of the columns `on`,
+    /// which both must hold. Rows match when all their key values are equal.
+    fn inner_join_on_keys(&'a self,
+                          other: &'a DataFrame<T>,
+                          on: &[&str])
+                          -> Result<InnerJoinIter<'a, T>> {
+        let left_keys = on.iter().map(|x| position(&self.columns[..], x)).collect::<Result<_>>()?;
+        let right_keys = on.iter().map(|x| position(&other.columns[..], x)).collect::<Result<_>>()?;
+        Ok(InnerJoin::on(self, other, left_keys, right_keys))
+    }
+
+    /// Perform an outer left join between two dataframes on the values of the columns `on`,
+    /// which both must hold. Rows match when all their key values are equal.
+    fn outer_join_on_keys(&'a self,
+                          other: &'a DataFrame<T>,
+                          on: &[&str])
+                          -> Result<OuterJoinIter<'a, T>> {
+        let left_keys = on.iter().map(|x| position(&self.columns[..], x)).collect::<Result<_>>()?;
+        let right_keys = on.iter().map(|x| position(&other.columns[..], x)).collect::<Result<_>>()?;
+        Ok(OuterJoin::on(self, other, left_keys, right_keys))
+    }
+
+    /// The rows whose label does not appear in the index of `other`, in order.
+    fn anti_join(&'a self, other: &DataFrame<T>) -> DataFrame<T> {
+        rows_by_match(self, other, false)
+    }
+
+    /// The rows whose label appears in the index of `other`, in order and once each, with only
+    /// the columns of `self`.
+    fn semi_join(&'a self, other: &DataFrame<T>) -> DataFrame<T> {
+        rows_by_match(self, other, true)
+    }
+
+    /// Align two dataframes on their index and put the columns of `other` after those of `self`,
+    /// as a full outer join: the rows of `self`, each with every row of `other` of the same label,
+    /// followed by the rows only `other` has. Missing values are filled with empties.
+    fn join_columns(&'a self, other: &DataFrame<T>) -> DataFrame<T> {
+        let positions = full_join_positions(self, other);
+        let width = self.columns.len() + other.columns.len();
+        let mut d = Vec::with_capacity(positions.len() * width);
+        let mut index = Vec::with_capacity(positions.len());
+        for &(i, j) in &positions {
+            for &(df, p) in [(self, i), (other, j)].iter() {
+                match p {
+                    Some(p) => d.extend(df.data.row(p).iter().cloned()),
+                    None => d.extend((0..df.columns.len()).map(|_| T::empty())),
+                }
+            }
+            index.push(match (i, j) {
+                (Some(i), _) => self.index[i].clone(),
+                (None, Some(j)) => other.index[j].clone(),
+                (None, None) => unreachable!(),
+            });
+        }
+        DataFrame {
+            columns: self.columns.iter().chain(other.columns.iter()).cloned().collect(),
+            data: Array::from_shape_vec((positions.len(), width), d).unwrap(),
+            index: index,
+            index_sorted: false,
+        }
+    }
+
+    /// Stack the rows of `other` under those of `self`, aligning the columns by name. With
+    /// `keys`, each index label is prefixed by the key of its source as an outer level. Fails if
+    /// either side repeats a column name, since the columns could not be matched up.
+    fn concat_rows(&'a self,
+                   other: &DataFrame<T>,
+                   join: ConcatJoin,
+                   keys: Option<(&str, &str)>)
+                   -> Result<DataFrame<T>> {
+        for columns in &[&self.columns, &other.columns] {
+            let mut seen = HashSet::with_capacity(columns.len());
+            if let Some(c) = columns.iter().find(|c| !seen.insert(&c[..])) {
+                return Err(ErrorKind::DuplicateColumn(c.clone()).into());
+            }
+        }
+        let aligned: Vec<_> = align_labels(&self.columns[..], &other.columns[..])
+            .into_iter()
+            .filter(|c| join == ConcatJoin::Outer || (c.1.is_some() && c.2.is_some()))
+            .collect();
+        let mut d = Vec::with_capacity((self.index.len() + other.index.len()) * aligned.len());
+        for row in self.data.outer_iter() {
+            d.extend(aligned.iter().map(|c| c.1.map_or_else(T::empty, |i| row[i].clone())));
+        }
+        for row in other.data.outer_iter() {
+            d.extend(aligned.iter().map(|c| c.2.map_or_else(T::empty, |j| row[j].clone())));
+        }
+        let index: Vec<String> = match keys {
+            Some((a, b)) => {
+                let label = |key: &str, x: &String| {
+                    format!("{}{}{}", escape_level(key), LEVEL_SEPARATOR, x)
+                };
+                self.index
+                    .iter()
+                    .map(|x| label(a, x))
+                    .chain(other.index.iter().map(|x| label(b, x)))
+                    .collect()
+            }
+            None => self.index.iter().chain(other.index.iter()).cloned().collect(),
+        };
+        Ok(DataFrame {
+            data: Array::from_shape_vec((index.len(), aligned.len()), d).unwrap(),
+            columns: aligned.into_iter().map(|c| c.0).collect(),
+            index: index,
+            index_sorted: false,
+        })
+    }
+
+    /// Remove the rows (or columns) with empty values, as `how` says. With a `thresh`, remove
+    /// instead those with fewer than `thresh` non-empty values.
+    fn dropna(&'a self, axis: UtahAxis, how: DropHow, thresh: Option<usize>) -> DataFrame<T> {
+        let lanes = match axis {
+            UtahAxis::Row => self.data.axis_iter(Axis(0)),
+            UtahAxis::Column => self.data.axis_iter(Axis(1)),
+        };
+        let keep: Vec<usize> = lanes.enumerate()
+            .filter(|&(_, lane)| {
+                let valid = lane.iter().filter(|x| !x.is_empty()).count();
+                match (thresh, how) {
+                    (Some(t), _) => valid >= t,
+                    (None, DropHow::Any) => valid == lane.len(),
+                    (None, DropHow::All) => valid > 0,
+                }
+            })
+            .map(|(i, _)| i)
+            .collect();
+        match axis {
+            UtahAxis::Row => {
+                DataFrame {
+                    columns: self.columns.clone(),
+                    data: take(&self.data, UtahAxis::Row, &keep[..]),
+                    index: take_labels(&self.index[..], &keep[..]),
+                    index_sorted: false,
+                }
+            }
+            UtahAxis::Column => {
+                DataFrame {
+                    columns: take_labels(&self.columns[..], &keep[..]),
+                    data: take(&self.data, UtahAxis::Column, &keep[..]),
+                    index: self.index.clone(),
+                    index_sorted: false,
+                }
+            }
+        }
+    }
 }
 
 
@@ -244,7 +1905,7 @@ impl<'a, T> Operations<'a, T> for DataFrame<T>
           S: Identifier
 {
     /// Get the dimensions of the dataframe.
-    default fn shape(self) -> (usize, usize) {
+    default fn shape(&self) -> (usize, usize) {
         self.data.dim()
     }
 
@@ -327,36 +1988,24 @@ impl<'a, T> Operations<'a, T> for DataFrame<T>
 
     /// Perform an inner left join between two dataframes along the specified `UtahAxis`.
     default fn inner_left_join(&'a self, other: &'a DataFrame<T>) -> InnerJoinIter<'a, T> {
-        InnerJoin::new(self.df_iter(UtahAxis::Row),
-                       other.df_iter(UtahAxis::Row),
-                       self.columns.clone(),
-                       other.columns.clone())
+        InnerJoin::new(self, other)
     }
 
     /// Perform an outer left join between two dataframes along the specified `UtahAxis`.
     default fn outer_left_join(&'a self, other: &'a DataFrame<T>) -> OuterJoinIter<'a, T> {
 
-        OuterJoin::new(self.df_iter(UtahAxis::Row),
-                       other.df_iter(UtahAxis::Row),
-                       self.columns.clone(),
-                       other.columns.clone())
+        OuterJoin::new(self, other)
     }
 
     /// Perform an inner right join between two dataframes along the specified `UtahAxis`.
     default fn inner_right_join(&'a self, other: &'a DataFrame<T>) -> InnerJoinIter<'a, T> {
-        InnerJoin::new(other.df_iter(UtahAxis::Row),
-                       self.df_iter(UtahAxis::Row),
-                       other.columns.clone(),
-                       self.columns.clone())
+        InnerJoin::new(other, self)
 
     }
 
     /// Perform an outer right join between two dataframes along the specified `UtahAxis`.
     default fn outer_right_join(&'a self, other: &'a DataFrame<T>) -> OuterJoinIter<'a, T> {
-        OuterJoin::new(other.df_iter(UtahAxis::Row),
-                       self.df_iter(UtahAxis::Row),
-                       other.columns.clone(),
-                       self.columns.clone())
+        OuterJoin::new(other, self)
 
     }
 
@@ -446,7 +2095,10 @@ impl<'a, T> Operations<'a, T> for DataFrame<T>
     }
 
     /// Replace empty values with specified ImputeStrategy along the specified `UtahAxis`.
-    default fn impute(&'a mut self, strategy: ImputeStrategy, axis: UtahAxis) -> ImputeIter<'a, T> {
+    default fn impute(&'a mut self,
+                      strategy: ImputeStrategy<T>,
+                      axis: UtahAxis)
+                      -> ImputeIter<'a, T> {
 
         let index = self.index.clone();
         let columns = self.columns.clone();
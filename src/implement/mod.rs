@@ -2,4 +2,7 @@ pub mod impl_constructor_generic;
 #[cfg(features = "specialization")]
 pub mod impl_ops_f64_string;
 pub mod impl_ops_generic;
+pub mod impl_arith;
 pub mod impl_empty;
+pub mod impl_mask;
+pub mod impl_mixed;
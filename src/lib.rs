@@ -190,12 +190,24 @@ extern crate csv;
 
 
 
+pub mod aggstate;
+pub mod cache;
 pub mod combinators;
 pub mod dataframe;
 #[macro_use]
 pub mod util;
 mod implement;
 pub mod mixedtypes;
+pub mod expr;
+pub mod ffi;
+pub mod nullable;
+pub mod panel;
+pub mod privacy;
+pub mod provenance;
+pub mod recipe;
+pub mod series;
+pub mod shared;
+pub mod transaction;
 mod bench;
 #[macro_use]
 mod tests;
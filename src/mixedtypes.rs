@@ -6,9 +6,11 @@ use num::traits::{One, Zero};
 use util::traits::Empty;
 use std::f64::NAN;
 use std::str::FromStr;
-use util::error::ErrorKind;
+use std::{i32, i64};
+use util::error::{Error, ErrorKind};
+use rustc_serialize::{Decodable, Decoder};
 
-#[derive(RustcDecodable, Hash, PartialOrd, PartialEq, Eq , Ord , Clone,  Debug)]
+#[derive(Hash, PartialOrd, PartialEq, Eq , Ord , Clone,  Debug)]
 pub enum OuterType {
     Str(String),
     Int64(i64),
@@ -16,7 +18,7 @@ pub enum OuterType {
     USize(usize),
 }
 
-#[derive(RustcDecodable,Clone, Debug)]
+#[derive(Clone, Debug)]
 pub enum InnerType {
     Float(f64),
     Int64(i64),
@@ -25,6 +27,106 @@ pub enum InnerType {
     Empty,
 }
 
+impl Decodable for OuterType {
+    fn decode<D: Decoder>(d: &mut D) -> Result<OuterType, D::Error> {
+        d.read_enum("OuterType", |d| {
+            d.read_enum_variant(&["Str", "Int64", "Int32", "USize"], |d, i| {
+                Ok(match i {
+                    0 => OuterType::Str(d.read_enum_variant_arg(0, Decodable::decode)?),
+                    1 => OuterType::Int64(d.read_enum_variant_arg(0, Decodable::decode)?),
+                    2 => OuterType::Int32(d.read_enum_variant_arg(0, Decodable::decode)?),
+                    _ => OuterType::USize(d.read_enum_variant_arg(0, Decodable::decode)?),
+                })
+            })
+        })
+    }
+}
+
+impl Decodable for InnerType {
+    fn decode<D: Decoder>(d: &mut D) -> Result<InnerType, D::Error> {
+        d.read_enum("InnerType", |d| {
+            d.read_enum_variant(&["Float", "Int64", "Int32", "Str", "Empty"], |d, i| {
+                Ok(match i {
+                    0 => InnerType::Float(d.read_enum_variant_arg(0, Decodable::decode)?),
+                    1 => InnerType::Int64(d.read_enum_variant_arg(0, Decodable::decode)?),
+                    2 => InnerType::Int32(d.read_enum_variant_arg(0, Decodable::decode)?),
+                    3 => InnerType::Str(d.read_enum_variant_arg(0, Decodable::decode)?),
+                    _ => InnerType::Empty,
+                })
+            })
+        })
+    }
+}
+
+/// The type of the values held in a column of mixed types.
+#[derive(Hash, PartialEq, Eq, Clone, Copy, Debug)]
+pub enum DType {
+    Float,
+    Int64,
+    Int32,
+    Str,
+    /// A column holding values of several types.
+    Mixed,
+    /// A column with no values.
+    Empty,
+}
+
+impl InnerType {
+    /// The type of this value.
+    pub fn dtype(&self) -> DType {
+        match *self {
+            InnerType::Float(_) => DType::Float,
+            InnerType::Int64(_) => DType::Int64,
+            InnerType::Int32(_) => DType::Int32,
+            InnerType::Str(_) => DType::Str,
+            InnerType::Empty => DType::Empty,
+        }
+    }
+
+    /// Convert this value to `dtype`. Strings are parsed, floats become integers only if they
+    /// are whole and in range, and empty values stay empty.
+    pub fn cast(&self, dtype: DType) -> Result<InnerType, Error> {
+        let fail = || -> Error {
+            ErrorKind::InvalidCast(format!("{:?}", self), format!("{:?}", dtype)).into()
+        };
+        let whole = |x: f64, lo: f64, hi: f64| x.fract() == 0. && x >= lo && x <= hi;
+        match (self, dtype) {
+            (&InnerType::Empty, _) => Ok(InnerType::Empty),
+            (&InnerType::Float(x), DType::Float) => Ok(InnerType::Float(x)),
+            (&InnerType::Float(x), _) if x.is_nan() => Ok(InnerType::Empty),
+            (&InnerType::Float(x), DType::Int64) if whole(x, i64::MIN as f64, i64::MAX as f64) => {
+                Ok(InnerType::Int64(x as i64))
+            }
+            (&InnerType::Float(x), DType::Int32) if whole(x, i32::MIN as f64, i32::MAX as f64) => {
+                Ok(InnerType::Int32(x as i32))
+            }
+            (&InnerType::Int64(x), DType::Float) => Ok(InnerType::Float(x as f64)),
+            (&InnerType::Int64(x), DType::Int64) => Ok(InnerType::Int64(x)),
+            (&InnerType::Int64(x), DType::Int32) if x >= i32::MIN as i64 &&
+                                                     x <= i32::MAX as i64 => {
+                Ok(InnerType::Int32(x as i32))
+            }
+            (&InnerType::Int32(x), DType::Float) => Ok(InnerType::Float(x as f64)),
+            (&InnerType::Int32(x), DType::Int64) => Ok(InnerType::Int64(x as i64)),
+            (&InnerType::Int32(x), DType::Int32) => Ok(InnerType::Int32(x)),
+            (&InnerType::Str(ref s), DType::Float) => {
+                s.trim().parse().map(InnerType::Float).map_err(|_| fail())
+            }
+            (&InnerType::Str(ref s), DType::Int64) => {
+                s.trim().parse().map(InnerType::Int64).map_err(|_| fail())
+            }
+            (&InnerType::Str(ref s), DType::Int32) => {
+                s.trim().parse().map(InnerType::Int32).map_err(|_| fail())
+            }
+            (&InnerType::Str(ref s), DType::Str) => Ok(InnerType::Str(s.clone())),
+            (&InnerType::Float(x), DType::Str) => Ok(InnerType::Str(x.to_string())),
+            (&InnerType::Int64(x), DType::Str) => Ok(InnerType::Str(x.to_string())),
+            (&InnerType::Int32(x), DType::Str) => Ok(InnerType::Str(x.to_string())),
+            _ => Err(fail()),
+        }
+    }
+}
+
 impl AsMut<InnerType> for InnerType {
     fn as_mut(&mut self) -> &mut InnerType {
         &mut (*self)
@@ -306,6 +408,9 @@ impl Empty<InnerType> for InnerType {
             _ => false,
         }
     }
+    fn from_count(n: usize) -> InnerType {
+        InnerType::Float(n as f64)
+    }
 }
 
 
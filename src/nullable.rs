@@ -0,0 +1,108 @@
+//! Utah nullable values
+//!
+//! Most element types mark missing data with a sentinel, e.g. `NAN` for `f64`, which makes a
+//! genuine `NAN` indistinguishable from a missing value. `Nullable<T>` wraps an `Option<T>`
+//! instead: `None` is the only empty value, and arithmetic with a missing operand is missing.
+//! Aggregations and `impute` skip empty values, so they work unchanged on a
+//! `DataFrame<Nullable<f64>>`.
+
+use std::cmp::Ordering;
+use std::ops::{Add, Sub, Mul, Div};
+use num::traits::{One, Zero};
+use util::traits::Empty;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Nullable<T>(pub Option<T>);
+
+impl<T> Nullable<T> {
+    /// A missing value.
+    pub fn null() -> Nullable<T> {
+        Nullable(None)
+    }
+
+    pub fn value(self) -> Option<T> {
+        self.0
+    }
+
+    pub fn is_null(&self) -> bool {
+        self.0.is_none()
+    }
+}
+
+impl<T> From<T> for Nullable<T> {
+    fn from(x: T) -> Nullable<T> {
+        Nullable(Some(x))
+    }
+}
+
+impl<T> From<Option<T>> for Nullable<T> {
+    fn from(x: Option<T>) -> Nullable<T> {
+        Nullable(x)
+    }
+}
+
+impl<T> Default for Nullable<T> {
+    fn default() -> Nullable<T> {
+        Nullable(None)
+    }
+}
+
+impl<T> Empty<Nullable<T>> for Nullable<T> {
+    fn empty() -> Nullable<T> {
+        Nullable(None)
+    }
+    fn is_empty(&self) -> bool {
+        self.0.is_none()
+    }
+}
+
+impl<T> PartialOrd for Nullable<T>
+    where T: PartialOrd
+{
+    fn partial_cmp(&self, other: &Nullable<T>) -> Option<Ordering> {
+        match (&self.0, &other.0) {
+            (&Some(ref a), &Some(ref b)) => a.partial_cmp(b),
+            _ => None,
+        }
+    }
+}
+
+macro_rules! nullable_op {
+    ($trait_:ident, $method:ident) => {
+        impl<T> $trait_ for Nullable<T>
+            where T: $trait_<Output = T>
+        {
+            type Output = Nullable<T>;
+            fn $method(self, other: Nullable<T>) -> Nullable<T> {
+                match (self.0, other.0) {
+                    (Some(a), Some(b)) => Nullable(Some(a.$method(b))),
+                    _ => Nullable(None),
+                }
+            }
+        }
+    }
+}
+
+nullable_op!(Add, add);
+nullable_op!(Sub, sub);
+nullable_op!(Mul, mul);
+nullable_op!(Div, div);
+
+impl<T> One for Nullable<T>
+    where T: One
+{
+    fn one() -> Nullable<T> {
+        Nullable(Some(T::one()))
+    }
+}
+
+impl<T> Zero for Nullable<T>
+    where T: Zero
+{
+    fn zero() -> Nullable<T> {
+        Nullable(Some(T::zero()))
+    }
+    fn is_zero(&self) -> bool {
+        self.0.as_ref().map_or(false, |x| x.is_zero())
+    }
+}
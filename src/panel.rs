@@ -0,0 +1,88 @@
+//! Utah panel checks
+//!
+//! A panel is a set of dataframes, one per entity, that are meant to be concatenated or joined.
+//! `check_panel` reports the ways in which they disagree before that happens.
+
+use std::collections::HashMap;
+use dataframe::DataFrame;
+use mixedtypes::{DType, InnerType};
+use util::traits::UtahNum;
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct PanelReport {
+    /// Frames whose columns differ from those of the first frame.
+    pub column_mismatches: Vec<usize>,
+    /// Frames and columns whose type differs from the same column in the first frame.
+    pub dtype_mismatches: Vec<(usize, String)>,
+    /// Pairs of frames sharing index labels, with the shared labels.
+    pub index_overlaps: Vec<(usize, usize, Vec<String>)>,
+    /// Whether all frames have the same index.
+    pub index_aligned: bool,
+}
+
+impl PanelReport {
+    /// Whether the frames can be concatenated: same columns and types, and no shared labels.
+    pub fn can_concat(&self) -> bool {
+        self.column_mismatches.is_empty() && self.dtype_mismatches.is_empty() &&
+        self.index_overlaps.is_empty()
+    }
+
+    /// Whether the frames can be joined on their index: aligned indices.
+    pub fn can_join(&self) -> bool {
+        self.index_aligned
+    }
+}
+
+/// Compare the columns and indices of a set of frames.
+pub fn check_panel<T>(frames: &[DataFrame<T>]) -> PanelReport
+    where T: UtahNum
+{
+    let mut report = PanelReport::default();
+    let first = match frames.first() {
+        Some(f) => f,
+        None => {
+            report.index_aligned = true;
+            return report;
+        }
+    };
+    report.column_mismatches = (1..frames.len())
+        .filter(|&i| frames[i].columns != first.columns)
+        .collect();
+    report.index_aligned = frames.iter().all(|f| f.index == first.index);
+    for i in 0..frames.len() {
+        for j in i + 1..frames.len() {
+            let shared: Vec<String> = frames[i]
+                .index
+                .iter()
+                .filter(|x| frames[j].index.contains(x))
+                .cloned()
+                .collect();
+            if !shared.is_empty() {
+                report.index_overlaps.push((i, j, shared));
+            }
+        }
+    }
+    report
+}
+
+/// Compare the columns, column types and indices of a set of mixed-type frames. Column types
+/// are compared by column name, so reordered columns are matched up. Columns with no values, or
+/// missing from the first frame, match any type.
+pub fn check_panel_mixed(frames: &[DataFrame<InnerType>]) -> PanelReport {
+    let mut report = check_panel(frames);
+    let first: HashMap<&str, DType> = match frames.first() {
+        Some(f) => f.columns.iter().map(|c| &c[..]).zip(f.dtypes()).collect(),
+        None => return report,
+    };
+    for (i, f) in frames.iter().enumerate().skip(1) {
+        for (name, t) in f.columns.iter().zip(f.dtypes()) {
+            match first.get(&name[..]) {
+                Some(t0) if t != *t0 && t != DType::Empty && *t0 != DType::Empty => {
+                    report.dtype_mismatches.push((i, name.clone()))
+                }
+                _ => {}
+            }
+        }
+    }
+    report
+}
@@ -10,7 +10,18 @@
 // fn main() {}
 // ```
 
+pub use aggstate::{AggState, ColumnState};
+pub use cache::StatsCache;
 pub use dataframe::DataFrame;
+pub use nullable::Nullable;
+pub use panel::{PanelReport, check_panel, check_panel_mixed};
+pub use privacy::{Mechanism, PrivateSession};
+pub use provenance::{Origin, Tracked};
+pub use recipe::{Recipe, Step};
+pub use series::Series;
+pub use shared::SharedDataFrame;
+pub use expr::{Expr, VirtualFrame, col, lit};
+pub use transaction::Transaction;
 pub use util::traits::*;
 pub use util::types::*;
 pub use ndarray::{arr2, arr1, ArrayView1, ArrayView2, Axis, stack};
@@ -18,3 +29,7 @@ pub use mixedtypes::*;
 pub use util::macros::*;
 pub use util::error::*;
 pub use util::readcsv::*;
+pub use util::multiindex::*;
+pub use util::blocks::BlockIndex;
+pub use util::rowset::RowSet;
+pub use util::time::frequency;
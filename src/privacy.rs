@@ -0,0 +1,193 @@
+//! Utah differentially private aggregation
+//!
+//! A `PrivateSession` answers sum, mean and count queries on `f64` columns with calibrated noise,
+//! spending part of a fixed privacy budget (epsilon, and delta for Gaussian noise) on each query.
+//! Values are clamped to caller supplied bounds, which fix the sensitivity of each query. Once the
+//! budget is spent, further queries fail.
+
+use rand::Rng;
+use rand::distributions::{IndependentSample, Normal};
+use dataframe::DataFrame;
+use util::error::*;
+use util::helpers::position;
+
+/// The noise added to a query answer.
+#[derive(Clone, Debug, Copy, PartialEq)]
+pub enum Mechanism {
+    /// Laplace noise, for pure epsilon-differential privacy.
+    Laplace,
+    /// Gaussian noise, for (epsilon, delta)-differential privacy, spending the given delta on
+    /// each noisy release. The classic calibration used requires delta in (0, 1) and each
+    /// query's epsilon below one.
+    Gaussian(f64),
+}
+
+pub struct PrivateSession<R>
+    where R: Rng
+{
+    remaining: f64,
+    remaining_delta: f64,
+    mechanism: Mechanism,
+    rng: R,
+}
+
+impl<R> PrivateSession<R>
+    where R: Rng
+{
+    /// Start a session with a total privacy budget of `epsilon` and `delta`. `delta` is only
+    /// spent by Gaussian noise, and may be zero for Laplace noise.
+    pub fn new(epsilon: f64,
+               delta: f64,
+               mechanism: Mechanism,
+               rng: R)
+               -> Result<PrivateSession<R>> {
+        check_parameter("epsilon", epsilon, epsilon > 0.)?;
+        check_parameter("delta", delta, delta >= 0. && delta < 1.)?;
+        if let Mechanism::Gaussian(d) = mechanism {
+            check_parameter("delta", d, d > 0. && d < 1.)?;
+        }
+        Ok(PrivateSession {
+            remaining: epsilon,
+            remaining_delta: delta,
+            mechanism: mechanism,
+            rng: rng,
+        })
+    }
+
+    /// The epsilon budget left to spend.
+    pub fn remaining(&self) -> f64 {
+        self.remaining
+    }
+
+    /// The delta budget left to spend.
+    pub fn remaining_delta(&self) -> f64 {
+        self.remaining_delta
+    }
+
+    /// A noisy count of the non-empty entries of `column`.
+    pub fn count<U: ?Sized>(&mut self,
+                            df: &DataFrame<f64>,
+                            column: &U,
+                            epsilon: f64)
+                            -> Result<f64>
+        where for<'b> String: From<&'b U>
+    {
+        let n = self.values(df, column, None)?.len() as f64;
+        self.spend(epsilon, 1)?;
+        Ok(n + self.noise(1., epsilon))
+    }
+
+    /// A noisy sum of the non-empty entries of `column`, each clamped to `[lo, hi]`.
+    pub fn sum<U: ?Sized>(&mut self,
+                          df: &DataFrame<f64>,
+                          column: &U,
+                          lo: f64,
+                          hi: f64,
+                          epsilon: f64)
+                          -> Result<f64>
+        where for<'b> String: From<&'b U>
+    {
+        let sum: f64 = self.values(df, column, Some((lo, hi)))?.iter().sum();
+        self.spend(epsilon, 1)?;
+        Ok(sum + self.noise(lo.abs().max(hi.abs()), epsilon))
+    }
+
+    /// A noisy mean of the non-empty entries of `column`, each clamped to `[lo, hi]`. The budget
+    /// is split evenly between a noisy sum and a noisy count.
+    pub fn mean<U: ?Sized>(&mut self,
+                           df: &DataFrame<f64>,
+                           column: &U,
+                           lo: f64,
+                           hi: f64,
+                           epsilon: f64)
+                           -> Result<f64>
+        where for<'b> String: From<&'b U>
+    {
+        let values = self.values(df, column, Some((lo, hi)))?;
+        self.spend(epsilon, 2)?;
+        let sum = values.iter().sum::<f64>() + self.noise(lo.abs().max(hi.abs()), epsilon / 2.);
+        let n = values.len() as f64 + self.noise(1., epsilon / 2.);
+        Ok((sum / n.max(1.)).max(lo).min(hi))
+    }
+
+    /// The non-empty entries of `column`, clamped to `bounds` if given. The bounds must be
+    /// finite and in order, since they fix the sensitivity of the query.
+    fn values<U: ?Sized>(&self,
+                         df: &DataFrame<f64>,
+                         column: &U,
+                         bounds: Option<(f64, f64)>)
+                         -> Result<Vec<f64>>
+        where for<'b> String: From<&'b U>
+    {
+        if let Some((lo, hi)) = bounds {
+            check_parameter("lo", lo, lo.is_finite())?;
+            check_parameter("hi", hi, hi.is_finite() && lo <= hi)?;
+        }
+        let name: String = column.into();
+        let i = position(&df.columns[..], &name)?;
+        let column = df.data.column(i);
+        let values = column.iter().filter(|x| !x.is_nan()).cloned();
+        Ok(match bounds {
+            Some((lo, hi)) => values.map(|x| x.max(lo).min(hi)).collect(),
+            None => values.collect(),
+        })
+    }
+
+    /// Charge a query answered with `releases` noisy values, which share `epsilon`. Each
+    /// Gaussian release also spends the mechanism's delta.
+    fn spend(&mut self, epsilon: f64, releases: usize) -> Result<()> {
+        let delta = match self.mechanism {
+            Mechanism::Laplace => {
+                check_parameter("epsilon", epsilon, epsilon > 0.)?;
+                0.
+            }
+            Mechanism::Gaussian(d) => {
+                check_parameter("epsilon", epsilon, epsilon > 0. && epsilon < 1.)?;
+                d * releases as f64
+            }
+        };
+        if epsilon > self.remaining {
+            return Err(ErrorKind::PrivacyBudgetExceeded("epsilon".to_string(),
+                                                        epsilon.to_string(),
+                                                        self.remaining.to_string())
+                .into());
+        }
+        if delta > self.remaining_delta {
+            return Err(ErrorKind::PrivacyBudgetExceeded("delta".to_string(),
+                                                        delta.to_string(),
+                                                        self.remaining_delta.to_string())
+                .into());
+        }
+        self.remaining -= epsilon;
+        self.remaining_delta -= delta;
+        Ok(())
+    }
+
+    /// Noise for a query with the given sensitivity, answered with budget `epsilon`.
+    fn noise(&mut self, sensitivity: f64, epsilon: f64) -> f64 {
+        match self.mechanism {
+            Mechanism::Laplace => {
+                // Draw from the open interval (0, 1), so the logarithm stays finite.
+                let mut p: f64 = self.rng.gen();
+                while p == 0. {
+                    p = self.rng.gen();
+                }
+                let u = p - 0.5;
+                -sensitivity / epsilon * u.signum() * (1. - 2. * u.abs()).ln()
+            }
+            Mechanism::Gaussian(delta) => {
+                let sigma = sensitivity * (2. * (1.25 / delta).ln()).sqrt() / epsilon;
+                Normal::new(0., sigma).ind_sample(&mut self.rng)
+            }
+        }
+    }
+}
+
+/// Fail unless `value` is finite and `valid`.
+fn check_parameter(name: &str, value: f64, valid: bool) -> Result<()> {
+    if value.is_finite() && valid {
+        Ok(())
+    } else {
+        Err(ErrorKind::InvalidPrivacyParameter(name.to_string(), value.to_string()).into())
+    }
+}
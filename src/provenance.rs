@@ -0,0 +1,117 @@
+//! Utah row provenance
+//!
+//! A `Tracked` dataframe carries, for every row, the rows of the source frames it was derived
+//! from, so an output row can be traced back to the inputs that produced it. Tracking is opt-in:
+//! wrap a frame with `Tracked::new` and go through the wrapper's operations, which keep the tags
+//! in step with the rows.
+
+use combinators::interact::inner_join_positions;
+use dataframe::DataFrame;
+use util::error::*;
+use util::helpers::{position, take, take_labels};
+use util::rowset::RowSet;
+use util::traits::*;
+use util::types::{ConcatJoin, UtahAxis};
+
+/// A row of a source frame.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Origin {
+    pub source: String,
+    pub row: usize,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tracked<T>
+    where T: UtahNum
+{
+    pub frame: DataFrame<T>,
+    origins: Vec<Vec<Origin>>,
+}
+
+impl<T> Tracked<T>
+    where T: UtahNum
+{
+    /// Start tracking a frame read from `source`. Each row is tagged with its own position.
+    pub fn new<U: ?Sized>(frame: DataFrame<T>, source: &U) -> Tracked<T>
+        where for<'b> String: From<&'b U>
+    {
+        let source: String = source.into();
+        let origins = (0..frame.index.len())
+            .map(|row| {
+                vec![Origin {
+                         source: source.clone(),
+                         row: row,
+                     }]
+            })
+            .collect();
+        Tracked {
+            frame: frame,
+            origins: origins,
+        }
+    }
+
+    /// The origins of each row, in row order.
+    pub fn provenance(&self) -> &[Vec<Origin>] {
+        &self.origins[..]
+    }
+
+    /// The origins of each row with the given index label, in row order. Labels need not be
+    /// unique, for instance after `concat`.
+    pub fn provenance_of(&self, label: &str) -> Vec<&[Origin]> {
+        self.frame
+            .index
+            .iter()
+            .zip(self.origins.iter())
+            .filter(|&(x, _)| x == label)
+            .map(|(_, o)| &o[..])
+            .collect()
+    }
+
+    /// Keep the named columns, in the given order.
+    pub fn select_columns(&self, names: &[&str]) -> Result<Tracked<T>> {
+        let positions = names.iter()
+            .map(|x| position(&self.frame.columns[..], x))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Tracked {
+            frame: DataFrame {
+                columns: take_labels(&self.frame.columns[..], &positions[..]),
+                data: take(&self.frame.data, UtahAxis::Column, &positions[..]),
+                index: self.frame.index.clone(),
+                index_sorted: false,
+            },
+            origins: self.origins.clone(),
+        })
+    }
+
+    /// Keep the rows in `rows`, in their original order.
+    pub fn take_rows(&self, rows: &RowSet) -> Result<Tracked<T>> {
+        let frame = self.frame.take_rows(rows)?;
+        Ok(Tracked {
+            frame: frame,
+            origins: rows.positions().into_iter().map(|p| self.origins[p].clone()).collect(),
+        })
+    }
+
+    /// Stack the rows of `other` below these rows, aligning the columns by name.
+    pub fn concat(&self, other: &Tracked<T>) -> Result<Tracked<T>> {
+        let frame = self.frame.concat_rows(&other.frame, ConcatJoin::Outer, None)?;
+        Ok(Tracked {
+            frame: frame,
+            origins: self.origins.iter().chain(other.origins.iter()).cloned().collect(),
+        })
+    }
+
+    /// Inner join with `other` on the index. Each joined row is tagged with the origins of both
+    /// rows it combines, found by position, so repeated labels keep their own origins.
+    pub fn inner_join(&self, other: &Tracked<T>) -> Result<Tracked<T>> {
+        let frame = self.frame.inner_left_join(&other.frame).as_df()?;
+        let origins = inner_join_positions(&self.frame, &other.frame)
+            .into_iter()
+            .map(|(i, j)| self.origins[i].iter().chain(other.origins[j].iter()).cloned().collect())
+            .collect();
+        Ok(Tracked {
+            frame: frame,
+            origins: origins,
+        })
+    }
+}
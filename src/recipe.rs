@@ -0,0 +1,178 @@
+//! Utah recipes
+//!
+//! A `Recipe` is a named sequence of operations kept as plain data, so that a standard
+//! computation can be stored, shared, and applied to any dataframe with the columns it uses.
+
+use dataframe::DataFrame;
+use util::error::*;
+use util::traits::*;
+use util::types::*;
+use util::helpers::{position, take, take_labels};
+use rustc_serialize::{Decodable, Decoder, Encodable, Encoder};
+use std::result;
+
+/// One operation of a recipe.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Step {
+    /// Keep only these columns, in this order.
+    Select(Vec<String>),
+    /// Drop these columns.
+    Remove(Vec<String>),
+    /// Keep the rows whose value in a column lies within a closed range.
+    FilterRange(String, f64, f64),
+    /// Sort the rows by these columns, ascending or not. Empty values go last.
+    Sort(Vec<String>, bool),
+    /// Group the rows by a column and reduce the other columns within each group.
+    GroupBy(String, Agg),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Recipe {
+    pub name: String,
+    pub steps: Vec<Step>,
+}
+
+const STEPS: [&'static str; 5] = ["Select", "Remove", "FilterRange", "Sort", "GroupBy"];
+
+impl Encodable for Step {
+    fn encode<S: Encoder>(&self, s: &mut S) -> result::Result<(), S::Error> {
+        s.emit_enum("Step", |s| match *self {
+            Step::Select(ref c) => {
+                s.emit_enum_variant(STEPS[0], 0, 1, |s| s.emit_enum_variant_arg(0, |s| c.encode(s)))
+            }
+            Step::Remove(ref c) => {
+                s.emit_enum_variant(STEPS[1], 1, 1, |s| s.emit_enum_variant_arg(0, |s| c.encode(s)))
+            }
+            Step::FilterRange(ref c, lo, hi) => {
+                s.emit_enum_variant(STEPS[2], 2, 3, |s| {
+                    s.emit_enum_variant_arg(0, |s| c.encode(s))?;
+                    s.emit_enum_variant_arg(1, |s| lo.encode(s))?;
+                    s.emit_enum_variant_arg(2, |s| hi.encode(s))
+                })
+            }
+            Step::Sort(ref c, ascending) => {
+                s.emit_enum_variant(STEPS[3], 3, 2, |s| {
+                    s.emit_enum_variant_arg(0, |s| c.encode(s))?;
+                    s.emit_enum_variant_arg(1, |s| ascending.encode(s))
+                })
+            }
+            Step::GroupBy(ref c, agg) => {
+                s.emit_enum_variant(STEPS[4], 4, 2, |s| {
+                    s.emit_enum_variant_arg(0, |s| c.encode(s))?;
+                    s.emit_enum_variant_arg(1, |s| agg.encode(s))
+                })
+            }
+        })
+    }
+}
+
+impl Decodable for Step {
+    fn decode<D: Decoder>(d: &mut D) -> result::Result<Step, D::Error> {
+        d.read_enum("Step", |d| {
+            d.read_enum_variant(&STEPS, |d, i| {
+                Ok(match i {
+                    0 => Step::Select(d.read_enum_variant_arg(0, Decodable::decode)?),
+                    1 => Step::Remove(d.read_enum_variant_arg(0, Decodable::decode)?),
+                    2 => {
+                        Step::FilterRange(d.read_enum_variant_arg(0, Decodable::decode)?,
+                                          d.read_enum_variant_arg(1, Decodable::decode)?,
+                                          d.read_enum_variant_arg(2, Decodable::decode)?)
+                    }
+                    3 => {
+                        Step::Sort(d.read_enum_variant_arg(0, Decodable::decode)?,
+                                   d.read_enum_variant_arg(1, Decodable::decode)?)
+                    }
+                    _ => {
+                        Step::GroupBy(d.read_enum_variant_arg(0, Decodable::decode)?,
+                                      d.read_enum_variant_arg(1, Decodable::decode)?)
+                    }
+                })
+            })
+        })
+    }
+}
+
+impl Encodable for Recipe {
+    fn encode<S: Encoder>(&self, s: &mut S) -> result::Result<(), S::Error> {
+        s.emit_struct("Recipe", 2, |s| {
+            s.emit_struct_field("name", 0, |s| self.name.encode(s))?;
+            s.emit_struct_field("steps", 1, |s| self.steps.encode(s))
+        })
+    }
+}
+
+impl Decodable for Recipe {
+    fn decode<D: Decoder>(d: &mut D) -> result::Result<Recipe, D::Error> {
+        d.read_struct("Recipe", 2, |d| {
+            Ok(Recipe {
+                name: d.read_struct_field("name", 0, Decodable::decode)?,
+                steps: d.read_struct_field("steps", 1, Decodable::decode)?,
+            })
+        })
+    }
+}
+
+fn take_columns<T>(df: &DataFrame<T>, positions: &[usize]) -> DataFrame<T>
+    where T: UtahNum
+{
+    DataFrame {
+        columns: take_labels(&df.columns[..], positions),
+        data: take(&df.data, UtahAxis::Column, positions),
+        index: df.index.clone(),
+        index_sorted: false,
+    }
+}
+
+impl Recipe {
+    pub fn new<U: ?Sized>(name: &U) -> Recipe
+        where for<'b> String: From<&'b U>
+    {
+        Recipe {
+            name: name.into(),
+            steps: Vec::new(),
+        }
+    }
+
+    /// Add a step at the end of the recipe.
+    pub fn then(mut self, step: Step) -> Recipe {
+        self.steps.push(step);
+        self
+    }
+
+    /// Run the recipe on a dataframe.
+    pub fn apply<T>(&self, df: &DataFrame<T>) -> Result<DataFrame<T>>
+        where T: UtahNum + PartialOrd + From<f64>
+    {
+        let mut df = df.clone();
+        for step in &self.steps {
+            df = match *step {
+                Step::Select(ref names) => {
+                    let mut positions = Vec::with_capacity(names.len());
+                    for name in names {
+                        positions.push(position(&df.columns[..], name)?);
+                    }
+                    take_columns(&df, &positions[..])
+                }
+                Step::Remove(ref names) => {
+                    for name in names {
+                        position(&df.columns[..], name)?;
+                    }
+                    let positions: Vec<usize> = (0..df.columns.len())
+                        .filter(|&i| !names.contains(&df.columns[i]))
+                        .collect();
+                    take_columns(&df, &positions[..])
+                }
+                Step::FilterRange(ref column, lo, hi) => {
+                    let index = df.block_index(&column[..], df.index.len())?;
+                    df.filter_range(&index, &T::from(lo), &T::from(hi))?
+                }
+                Step::Sort(ref names, ascending) => {
+                    let names: Vec<&str> = names.iter().map(|x| &x[..]).collect();
+                    df.sort_values(&names[..], ascending, EmptyPosition::Last)?
+                }
+                Step::GroupBy(ref key, agg) => df.groupby(&key[..])?.agg(agg),
+            };
+        }
+        Ok(df)
+    }
+}
@@ -0,0 +1,173 @@
+//! Utah series
+//!
+//! A `Series` is a single labelled column. Arithmetic between series aligns them on their
+//! labels, leaving entries empty where a label is missing from either side.
+
+use std::collections::HashMap;
+use std::ops::{Add, Sub, Mul, Div};
+use ndarray::Array;
+use util::helpers::{align_labels, count};
+use util::traits::UtahNum;
+use util::types::*;
+use dataframe::DataFrame;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Series<T>
+    where T: UtahNum
+{
+    pub name: String,
+    pub index: Vec<String>,
+    pub data: Row<T>,
+}
+
+impl<T> Series<T>
+    where T: UtahNum
+{
+    /// Create a series labelled by position.
+    pub fn new<U: ?Sized>(name: &U, data: Row<T>) -> Series<T>
+        where for<'b> String: From<&'b U>
+    {
+        Series {
+            name: name.into(),
+            index: (0..data.len()).map(|x| x.to_string()).collect(),
+            data: data,
+        }
+    }
+
+    /// The number of entries.
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Whether the series has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.data.len() == 0
+    }
+
+    /// The entry with a given label, if any.
+    pub fn get(&self, label: &str) -> Option<&T> {
+        self.index.iter().position(|x| x == label).map(|i| &self.data[i])
+    }
+
+    /// Rearrange the series to follow `labels`. Labels missing from the series get empty entries,
+    /// and a repeated label of the series gives its first entry, as in `get`.
+    pub fn reindex(&self, labels: &[String]) -> Series<T> {
+        let mut positions: HashMap<&str, usize> = HashMap::with_capacity(self.index.len());
+        for (i, x) in self.index.iter().enumerate().rev() {
+            positions.insert(&x[..], i);
+        }
+        let v: Vec<T> = labels.iter()
+            .map(|l| positions.get(&l[..]).map_or_else(T::empty, |&i| self.data[i].clone()))
+            .collect();
+        Series {
+            name: self.name.clone(),
+            index: labels.to_vec(),
+            data: Array::from_vec(v),
+        }
+    }
+
+    /// The sum of the non-empty entries.
+    pub fn sum(&self) -> T {
+        self.data.iter().filter(|x| !x.is_empty()).fold(T::zero(), |acc, x| acc + x.clone())
+    }
+
+    /// The number of non-empty entries.
+    pub fn count(&self) -> usize {
+        self.data.iter().filter(|x| !x.is_empty()).count()
+    }
+
+    /// The mean of the non-empty entries, or empty if there are none.
+    pub fn mean(&self) -> T {
+        match self.count() {
+            0 => T::empty(),
+            n => self.sum() / count(n),
+        }
+    }
+
+    /// The largest non-empty entry, or empty if there are none.
+    pub fn max(&self) -> T
+        where T: PartialOrd
+    {
+        self.data.iter().filter(|x| !x.is_empty()).fold(T::empty(), |acc, x| {
+            if acc.is_empty() || *x > acc { x.clone() } else { acc }
+        })
+    }
+
+    /// The smallest non-empty entry, or empty if there are none.
+    pub fn min(&self) -> T
+        where T: PartialOrd
+    {
+        self.data.iter().filter(|x| !x.is_empty()).fold(T::empty(), |acc, x| {
+            if acc.is_empty() || *x < acc { x.clone() } else { acc }
+        })
+    }
+
+    /// Combine two series entry by entry, aligned on their labels.
+    fn zip_aligned<F>(&self, other: &Series<T>, f: F) -> Series<T>
+        where F: Fn(T, T) -> T
+    {
+        let aligned = align_labels(&self.index[..], &other.index[..]);
+        let v: Vec<T> = aligned.iter()
+            .map(|&(_, i, j)| match (i, j) {
+                (Some(i), Some(j)) => f(self.data[i].clone(), other.data[j].clone()),
+                _ => T::empty(),
+            })
+            .collect();
+        Series {
+            name: self.name.clone(),
+            index: aligned.into_iter().map(|(l, _, _)| l).collect(),
+            data: Array::from_vec(v),
+        }
+    }
+}
+
+impl<'a, T> Add for &'a Series<T>
+    where T: UtahNum
+{
+    type Output = Series<T>;
+    fn add(self, rhs: &'a Series<T>) -> Series<T> {
+        self.zip_aligned(rhs, |x, y| x + y)
+    }
+}
+
+impl<'a, T> Sub for &'a Series<T>
+    where T: UtahNum
+{
+    type Output = Series<T>;
+    fn sub(self, rhs: &'a Series<T>) -> Series<T> {
+        self.zip_aligned(rhs, |x, y| x - y)
+    }
+}
+
+impl<'a, T> Mul for &'a Series<T>
+    where T: UtahNum
+{
+    type Output = Series<T>;
+    fn mul(self, rhs: &'a Series<T>) -> Series<T> {
+        self.zip_aligned(rhs, |x, y| x * y)
+    }
+}
+
+impl<'a, T> Div for &'a Series<T>
+    where T: UtahNum
+{
+    type Output = Series<T>;
+    fn div(self, rhs: &'a Series<T>) -> Series<T> {
+        self.zip_aligned(rhs, |x, y| x / y)
+    }
+}
+
+/// A single-column dataframe named after the series.
+impl<T> From<Series<T>> for DataFrame<T>
+    where T: UtahNum
+{
+    fn from(s: Series<T>) -> DataFrame<T> {
+        let n = s.data.len();
+        DataFrame {
+            columns: vec![s.name],
+            data: s.data.into_shape((n, 1)).unwrap(),
+            index: s.index,
+            index_sorted: false,
+        }
+    }
+}
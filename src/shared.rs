@@ -0,0 +1,116 @@
+//! Utah shared dataframe
+//!
+//! A `SharedDataFrame` collects rows from several threads at once. Each column sits behind its own
+//! lock, so threads feeding different columns never wait on each other. Whole rows are appended
+//! under every lock at once, taken in column order, so they never interleave with other writers.
+
+use std::sync::{Mutex, MutexGuard};
+use util::error::*;
+use util::traits::UtahNum;
+use util::helpers::position;
+use dataframe::DataFrame;
+use ndarray::{Array, Axis};
+
+#[derive(Debug)]
+pub struct SharedDataFrame<T>
+    where T: UtahNum
+{
+    pub columns: Vec<String>,
+    data: Vec<Mutex<Vec<T>>>,
+}
+
+impl<T> SharedDataFrame<T>
+    where T: UtahNum
+{
+    /// Create an empty shared dataframe with the given columns.
+    pub fn new<U: Clone>(columns: &[U]) -> SharedDataFrame<T>
+        where String: From<U>
+    {
+        SharedDataFrame {
+            columns: columns.iter().map(|x| String::from(x.clone())).collect(),
+            data: columns.iter().map(|_| Mutex::new(Vec::new())).collect(),
+        }
+    }
+
+    /// Lock the `i`th column. A column whose writer panicked is reported as an error rather than
+    /// read, since it may hold part of a write.
+    fn lock<'a>(&'a self, i: usize) -> Result<MutexGuard<'a, Vec<T>>> {
+        self.data[i].lock().map_err(|_| ErrorKind::PoisonedLock(self.columns[i].clone()).into())
+    }
+
+    /// Lock every column, in column order.
+    fn lock_all<'a>(&'a self) -> Result<Vec<MutexGuard<'a, Vec<T>>>> {
+        (0..self.data.len()).map(|i| self.lock(i)).collect()
+    }
+
+    /// Append values to the end of a column, locking only that column.
+    pub fn extend<U: ?Sized>(&self, column: &U, values: &[T]) -> Result<()>
+        where for<'b> String: From<&'b U>
+    {
+        let column: String = column.into();
+        let i = position(&self.columns[..], &column)?;
+        self.lock(i)?.extend_from_slice(values);
+        Ok(())
+    }
+
+    /// Append whole rows, given one after another, holding every column lock for the duration.
+    pub fn append_rows(&self, rows: &[T]) -> Result<()> {
+        let width = self.columns.len();
+        if width == 0 || rows.len() % width != 0 {
+            return Err(ErrorKind::RowShapeMismatch.into());
+        }
+        let mut cols = self.lock_all()?;
+        for row in rows.chunks(width) {
+            for (c, x) in cols.iter_mut().zip(row.iter()) {
+                c.push(x.clone());
+            }
+        }
+        Ok(())
+    }
+
+    /// Append the rows of a dataframe with the same columns, holding every column lock for the
+    /// duration.
+    pub fn append_partition(&self, df: &DataFrame<T>) -> Result<()> {
+        if df.columns != self.columns {
+            return Err(ErrorKind::MisalignedLabels(format!("{:?} and {:?}",
+                                                           self.columns,
+                                                           df.columns))
+                .into());
+        }
+        let mut cols = self.lock_all()?;
+        for (c, x) in cols.iter_mut().zip(df.data.axis_iter(Axis(1))) {
+            c.extend(x.iter().cloned());
+        }
+        Ok(())
+    }
+
+    /// The number of values appended to a column so far.
+    pub fn column_len<U: ?Sized>(&self, column: &U) -> Result<usize>
+        where for<'b> String: From<&'b U>
+    {
+        let column: String = column.into();
+        let i = position(&self.columns[..], &column)?;
+        Ok(self.lock(i)?.len())
+    }
+
+    /// Copy the collected values into a dataframe. All columns must hold the same number of
+    /// values.
+    pub fn to_df(&self) -> Result<DataFrame<T>> {
+        let cols = self.lock_all()?;
+        let nrows = cols.first().map_or(0, |c| c.len());
+        if let Some(c) = cols.iter().find(|c| c.len() != nrows) {
+            return Err(ErrorKind::IndexShapeMismatch(nrows.to_string(), c.len().to_string())
+                .into());
+        }
+        let mut d = Vec::with_capacity(nrows * cols.len());
+        for r in 0..nrows {
+            d.extend(cols.iter().map(|c| c[r].clone()));
+        }
+        Ok(DataFrame {
+            columns: self.columns.clone(),
+            data: Array::from_shape_vec((nrows, cols.len()), d).unwrap(),
+            index: (0..nrows).map(|x| x.to_string()).collect(),
+            index_sorted: false,
+        })
+    }
+}
@@ -10,7 +10,8 @@ use ndarray_rand::RandomExt;
 use std::rc::Rc;
 use rand::{thread_rng, Rng};
 use std::collections::{HashMap, BTreeMap};
-use std::f64::NAN;
+use std::f64::{INFINITY, NAN};
+use std::time::Duration;
 use prelude::*;
 
 #[test]
@@ -117,7 +118,7 @@ fn dataframe_append() {
         let mut df: DataFrame<f64> = DataFrame::new(a).columns(&["a", "b"]).unwrap();
         let col = df.select(&["a"], UtahAxis::Column).as_array().unwrap();
         let z = df.append("c", col.view(), UtahAxis::Column).as_df();
-        let b = arr2(&[[2., 3., 2.], [3., 3., 4.]]);
+        let b = arr2(&[[2., 2., 3.], [3., 3., 4.]]);
         let expected = DataFrame::new(b)
             .columns(&["c", "a", "b"])
             .unwrap();
@@ -283,3 +284,1892 @@ fn dataframe_macro() {
 //     }
 //
 // }
+
+#[test]
+fn dataframe_pct_rank() {
+    {
+        let a = arr2(&[[1., 10.], [1., 40.], [2., 20.], [2., 30.]]);
+        let df: DataFrame<f64> = DataFrame::new(a).columns(&["g", "x"]).unwrap();
+        let z = df.pct_rank("x").unwrap();
+        let b = arr2(&[[0.], [1.], [1. / 3.], [2. / 3.]]);
+        let expected = DataFrame::new(b).columns(&["x"]).unwrap();
+        assert_eq!(z, expected);
+        let z = df.pct_rank_by("x", "g").unwrap();
+        let b = arr2(&[[0.], [1.], [0.], [1.]]);
+        let expected = DataFrame::new(b).columns(&["x"]).unwrap();
+        assert_eq!(z, expected);
+    }
+    {
+        let a = arr2(&[[1., 10.], [1., 10.], [2., NAN], [2., 30.]]);
+        let df: DataFrame<f64> = DataFrame::new(a).columns(&["g", "x"]).unwrap();
+        let z = df.cume_dist("x").unwrap();
+        assert_eq!(z.data[[1, 0]], 2. / 3.);
+        assert!(z.data[[2, 0]].is_nan());
+        let z = df.cume_dist_by("x", "g").unwrap();
+        assert_eq!(z.data[[0, 0]], 1.);
+        assert_eq!(z.data[[3, 0]], 1.);
+        assert!(df.pct_rank("y").is_err());
+    }
+    {
+        // Integer frames still get fractional ranks.
+        let a = arr2(&[[1, 10], [1, 40], [2, 20], [2, 30]]);
+        let df: DataFrame<i32> = DataFrame::new(a).columns(&["g", "x"]).unwrap();
+        let z = df.pct_rank("x").unwrap();
+        assert_eq!(z.data, arr2(&[[0.], [1.], [1. / 3.], [2. / 3.]]));
+        let z = df.cume_dist("x").unwrap();
+        assert_eq!(z.data, arr2(&[[0.25], [1.], [0.5], [0.75]]));
+        let z = df.cume_dist_by("x", "g").unwrap();
+        assert_eq!(z.data, arr2(&[[0.5], [1.], [0.5], [1.]]));
+    }
+}
+
+#[test]
+fn dataframe_sort_values() {
+    let a = arr2(&[[2., 1.], [1., NAN], [2., 0.], [1., 5.]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["a", "b"]).unwrap();
+    let z = df.sort_values(&["a", "b"], true, EmptyPosition::Last).unwrap();
+    assert_eq!(z.index, vec!["3", "1", "2", "0"]);
+    let z = df.sort_values(&["a", "b"], false, EmptyPosition::First).unwrap();
+    assert_eq!(z.index, vec!["0", "2", "1", "3"]);
+    let z = df.sort_values(&["a"], true, EmptyPosition::Last).unwrap();
+    assert_eq!(z.index, vec!["1", "3", "0", "2"]);
+    assert!(z.data[[0, 1]].is_nan());
+    assert_eq!(z.data[[3, 1]], 0.);
+}
+
+#[test]
+fn dataframe_cut() {
+    let a = arr2(&[[0.], [1.5], [2.], [7.], [NAN]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["x"]).unwrap();
+    let z = df.cut("x", &[0., 2., 5.], &["low", "high"]).unwrap();
+    let b = arr2(&[[InnerType::Str("low".into()), InnerType::Int64(0)],
+                   [InnerType::Str("low".into()), InnerType::Int64(0)],
+                   [InnerType::Str("low".into()), InnerType::Int64(0)],
+                   [InnerType::Empty, InnerType::Empty],
+                   [InnerType::Empty, InnerType::Empty]]);
+    let expected = DataFrame::new(b).columns(&["label", "bin"]).unwrap();
+    assert_eq!(z, expected);
+    let z = df.cut("x", &[0., 2., 5.], &[]).unwrap();
+    assert_eq!(z.data[[0, 0]], InnerType::Str("(0.0, 2.0]".into()));
+    assert!(df.cut("x", &[0., 2., 5.], &["one"]).is_err());
+    assert!(df.cut("x", &[2., 0.], &[]).is_err());
+}
+
+#[test]
+fn dataframe_sort_index() {
+    let a = arr2(&[[1.], [2.], [3.]]);
+    let mut df: DataFrame<f64> = DataFrame::new(a)
+        .columns(&["a"])
+        .unwrap()
+        .index(&["10", "2", "1"])
+        .unwrap();
+    let z = df.sort_index(true);
+    assert_eq!(z.index, vec!["1", "2", "10"]);
+    assert_eq!(z.data, arr2(&[[3.], [2.], [1.]]));
+    assert!(z.index_sorted && !df.index_sorted && !df.sort_index(false).index_sorted);
+    let mut y = z.clone();
+    y.push_row("3", &[0.]).unwrap();
+    assert!(!y.index_sorted);
+    {
+        let data: Vec<&mut f64> = df.data.iter_mut().collect();
+        let mut m = ::dataframe::DataFrameMut {
+            columns: df.columns.clone(),
+            data: Array::from_shape_vec((3, 1), data).unwrap(),
+            index: df.index.clone(),
+            index_sorted: false,
+        };
+        m.sort_index_in_place(false);
+        assert_eq!(m.index, vec!["10", "2", "1"]);
+        m.sort_index_in_place(true);
+        assert_eq!(m.index, vec!["1", "2", "10"]);
+        assert!(m.index_sorted);
+    }
+    assert_eq!(df.data, arr2(&[[3.], [2.], [1.]]));
+
+    let a = arr2(&[[1.], [2.]]);
+    let df: DataFrame<f64> = DataFrame::new(a).index(&["2017-02-01", "2016-12-31"]).unwrap();
+    assert_eq!(df.sort_index(true).index, vec!["2016-12-31", "2017-02-01"]);
+
+    let labels = ["b", "10", "NaN", "a", "2", "1e1"];
+    let a = Array::from_shape_vec((labels.len(), 1), vec![0.; labels.len()]).unwrap();
+    let df: DataFrame<f64> = DataFrame::new(a).index(&labels[..]).unwrap();
+    assert_eq!(df.sort_index(true).index, vec!["2", "10", "1e1", "NaN", "a", "b"]);
+}
+
+#[test]
+fn dataframe_rolling_min_max() {
+    let a = arr2(&[[1., 5.], [3., 4.], [2., NAN], [0., 6.], [4., 1.]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["a", "b"]).unwrap();
+    let z = df.rolling(2).max().as_df().unwrap();
+    assert_eq!(z.columns, vec!["a", "b"]);
+    assert_eq!(z.index, df.index);
+    assert!(z.data[[0, 0]].is_nan());
+    let res: Vec<f64> = z.data.column(0).iter().skip(1).cloned().collect();
+    assert_eq!(res, vec![3., 3., 2., 4.]);
+    assert_eq!(z.data[[2, 1]], 4.);
+    let z = df.rolling(3).min().as_df().unwrap();
+    let res: Vec<f64> = z.data.column(0).iter().skip(2).cloned().collect();
+    assert_eq!(res, vec![1., 0., 0.]);
+    let res: Vec<f64> = z.data.column(1).iter().skip(2).cloned().collect();
+    assert_eq!(res, vec![4., 4., 1.]);
+}
+
+#[test]
+fn dataframe_groupby_head_tail() {
+    let a = arr2(&[[1., 1.], [2., 2.], [1., 3.], [1., 4.], [2., 5.]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["g", "x"]).unwrap();
+    let g = df.groupby("g").unwrap();
+    assert_eq!(g.ngroups(), 2);
+    let z = g.head(2);
+    assert_eq!(z.index, vec!["0", "1", "2", "4"]);
+    assert_eq!(z.data, arr2(&[[1., 1.], [2., 2.], [1., 3.], [2., 5.]]));
+    let z = g.tail(1);
+    assert_eq!(z.index, vec!["3", "4"]);
+    assert!(df.groupby("y").is_err());
+}
+
+#[test]
+fn dataframe_sample() {
+    let a = arr2(&[[1., 1.], [1., 2.], [1., 3.], [1., 4.], [2., 5.], [2., 6.]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["g", "x"]).unwrap();
+    let mut rng = thread_rng();
+    let z = df.sample(4, false, &mut rng).unwrap();
+    let mut index = z.index.clone();
+    index.sort();
+    index.dedup();
+    assert_eq!(index.len(), 4);
+    assert_eq!(df.sample(10, true, &mut rng).unwrap().shape(), (10, 2));
+    assert!(df.sample(10, false, &mut rng).is_err());
+    assert_eq!(df.sample_frac(0.5, false, &mut rng).unwrap().shape(), (3, 2));
+    let z = df.sample_stratified("g", 0.5, false, &mut rng).unwrap();
+    assert_eq!(z.data.column(0).iter().filter(|&x| *x == 1.).count(), 2);
+    assert_eq!(z.data.column(0).iter().filter(|&x| *x == 2.).count(), 1);
+    assert!(df.sample_frac(-0.5, true, &mut rng).is_err());
+    assert!(df.sample_frac(NAN, true, &mut rng).is_err());
+    assert!(df.sample_stratified("g", INFINITY, true, &mut rng).is_err());
+}
+
+#[test]
+fn dataframe_rank_demean_rows() {
+    {
+        let a = arr2(&[[3., 1., 2.], [5., 5., NAN]]);
+        let mut df: DataFrame<f64> = DataFrame::new(a).columns(&["a", "b", "c"]).unwrap();
+        let z = df.rank_rows().as_df().unwrap();
+        assert_eq!(z.data.row(0).to_owned(), arr1(&[3., 1., 2.]));
+        assert_eq!(z.data[[1, 0]], 1.5);
+        assert!(df.data[[1, 2]].is_nan());
+        assert_eq!(df.data[[1, 1]], 1.5);
+    }
+    {
+        let a = arr2(&[[3., 1., 2.], [4., 6., NAN]]);
+        let mut df: DataFrame<f64> = DataFrame::new(a).columns(&["a", "b", "c"]).unwrap();
+        let z = df.demean_rows().as_df().unwrap();
+        assert_eq!(z.columns, vec!["a", "b", "c"]);
+        assert_eq!(z.data.row(0).to_owned(), arr1(&[1., -1., 0.]));
+        assert_eq!(df.data[[1, 0]], -1.);
+        assert_eq!(df.data[[1, 1]], 1.);
+    }
+    {
+        let mut df: DataFrame<i32> = DataFrame::new(arr2(&[[0, 0], [4, 6]]));
+        let z = df.demean_rows().as_df().unwrap();
+        assert_eq!(z.data, arr2(&[[0, 0], [-1, 1]]));
+    }
+}
+
+#[test]
+fn dataframe_shuffle() {
+    let a = arr2(&[[0., 0.], [1., 10.], [2., 20.], [3., 30.]]);
+    let mut df: DataFrame<f64> = DataFrame::new(a).columns(&["a", "b"]).unwrap();
+    df.shuffle(&mut thread_rng());
+    let mut index = df.index.clone();
+    index.sort();
+    assert_eq!(index, vec!["0", "1", "2", "3"]);
+    for (i, label) in df.index.iter().enumerate() {
+        let x: f64 = label.parse().unwrap();
+        assert_eq!(df.data.row(i).to_owned(), arr1(&[x, x * 10.]));
+    }
+}
+
+#[test]
+fn dataframe_trimmed_winsorized_mean() {
+    let a = arr2(&[[1., 2.], [2., 2.], [3., 2.], [4., NAN], [100., 2.]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["a", "b"]).unwrap();
+    let z = df.trimmed_mean(0.2, UtahAxis::Column).as_df().unwrap();
+    let expected = DataFrame::new(arr2(&[[3., 2.]])).columns(&["a", "b"]).unwrap();
+    assert_eq!(z, expected);
+    let z = df.winsorized_mean(0.2, UtahAxis::Column).as_array().unwrap();
+    assert_eq!(z, arr1(&[3., 2.]));
+    let z = df.trimmed_mean(0., UtahAxis::Column).as_array().unwrap();
+    assert_eq!(z, arr1(&[22., 2.]));
+
+    let df: DataFrame<i32> = DataFrame::new(arr2(&[[0, 0], [4, 6]]));
+    let z = df.trimmed_mean(0.2, UtahAxis::Row).as_array().unwrap();
+    assert_eq!(z, arr1(&[0, 5]));
+    let z = df.winsorized_mean(0.2, UtahAxis::Row).as_array().unwrap();
+    assert_eq!(z, arr1(&[0, 5]));
+}
+
+#[test]
+fn dataframe_groupby_binned() {
+    let a = arr2(&[[5., 1.], [25., 2.], [15., 3.], [1., 4.], [NAN, 5.], [50., 6.]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["x", "y"]).unwrap();
+    let g = df.groupby_binned("x", &[0., 10., 20., 30.]).unwrap();
+    assert_eq!(g.keys, vec![0., 10., 20.]);
+    assert_eq!(g.groups, vec![vec![0, 3], vec![2], vec![1]]);
+    assert_eq!(g.head(1).index, vec!["0", "1", "2"]);
+    assert!(df.groupby_binned("x", &[10., 0.]).is_err());
+}
+
+#[test]
+fn dataframe_unique() {
+    let a = arr2(&[[3., 1.], [1., 1.], [3., 1.], [2., 1.], [1., 1.]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["a", "b"]).unwrap();
+    assert_eq!(df.unique("a").unwrap(), arr1(&[3., 1., 2.]));
+    assert_eq!(df.unique("b").unwrap(), arr1(&[1.]));
+    assert!(df.unique("c").is_err());
+}
+
+#[test]
+fn dataframe_reduce() {
+    let a = arr2(&[[1., NAN], [4., 2.], [-3., 8.]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["a", "b"]).unwrap();
+    assert_eq!(df.reduce(Agg::Sum), 12.);
+    assert_eq!(df.reduce(Agg::Mean), 2.4);
+    assert_eq!(df.reduce(Agg::Max), 8.);
+    assert_eq!(df.reduce(Agg::Min), -3.);
+    assert_eq!(df.reduce(Agg::Count), 5.);
+    assert_eq!(df.reduce(Agg::EmptyCount), 1.);
+}
+
+#[test]
+fn dataframe_value_counts() {
+    let a = arr2(&[[3., 1.], [1., 1.], [3., 1.], [2., 1.], [1., 1.], [1., 1.]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["a", "b"]).unwrap();
+    let z = df.value_counts("a").unwrap();
+    let expected = DataFrame::new(arr2(&[[1., 3.], [3., 2.], [2., 1.]]))
+        .columns(&["a", "count"])
+        .unwrap();
+    assert_eq!(z, expected);
+    assert!(df.value_counts("c").is_err());
+}
+
+#[test]
+fn dataframe_multi_index_xs() {
+    let index = MultiIndex::from_arrays(&[&["a", "a", "b"], &["x", "y", "x"]]).unwrap();
+    let labels = index.labels();
+    assert_eq!(labels, vec!["a|x", "a|y", "b|x"]);
+    assert_eq!(MultiIndex::from_labels(&labels[..]), index);
+    let a = arr2(&[[1., 2.], [3., 4.], [5., 6.]]);
+    let df: DataFrame<f64> = DataFrame::new(a).index(&labels[..]).unwrap();
+    let z = df.xs(0, "a").unwrap();
+    assert_eq!(z.index, vec!["x", "y"]);
+    assert_eq!(z.data, arr2(&[[1., 2.], [3., 4.]]));
+    let z = df.xs(1, "x").unwrap();
+    assert_eq!(z.index, vec!["a", "b"]);
+    assert!(df.xs(2, "x").is_err());
+    assert!(MultiIndex::from_arrays(&[&["a", "b"], &["x"]]).is_err());
+    let odd = MultiIndex::from_arrays(&[&["a|b", "c\\"], &["x", "y|"]]).unwrap();
+    assert_eq!(odd.labels(), vec!["a\\|b|x", "c\\\\|y\\|"]);
+    assert_eq!(MultiIndex::from_labels(&odd.labels()[..]), odd);
+    assert_eq!(index.to_string(), "a x\n  y\nb x\n");
+    let g = df.groupby_level(1).unwrap();
+    assert_eq!(g.labels, vec!["x", "y"]);
+    assert_eq!(g.groups, vec![vec![0, 2], vec![1]]);
+    assert!(df.groupby_level(2).is_err());
+}
+
+#[test]
+fn dataframe_mut_sort_values_in_place() {
+    let a = arr2(&[[3., 1.], [1., 2.], [2., 3.], [1., 4.], [NAN, 5.]]);
+    let mut df: DataFrame<f64> = DataFrame::new(a).columns(&["a", "b"]).unwrap();
+    {
+        let data: Vec<&mut f64> = df.data.iter_mut().collect();
+        let mut m = ::dataframe::DataFrameMut {
+            columns: df.columns.clone(),
+            data: Array::from_shape_vec((5, 2), data).unwrap(),
+            index: df.index.clone(),
+            index_sorted: false,
+        };
+        m.sort_values_in_place(&["a", "b"], false, EmptyPosition::First).unwrap();
+        assert_eq!(m.index, vec!["4", "0", "2", "3", "1"]);
+        assert!(m.sort_values_in_place(&["c"], true, EmptyPosition::Last).is_err());
+    }
+    assert_eq!(df.data.column(1), arr1(&[5., 1., 3., 4., 2.]));
+}
+
+#[test]
+fn dataframe_insert_column_at() {
+    let a = arr2(&[[1., 2.], [3., 4.]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["a", "b"]).unwrap();
+    let new = arr1(&[5., 6.]);
+    let z = df.insert_column_at(1, "c", new.view()).as_df().unwrap();
+    let expected = DataFrame::new(arr2(&[[1., 5., 2.], [3., 6., 4.]]))
+        .columns(&["a", "c", "b"])
+        .unwrap();
+    assert_eq!(z, expected);
+    let z = df.insert_column_at(10, "c", new.view()).as_df().unwrap();
+    assert_eq!(z.columns, vec!["a", "b", "c"]);
+    let z = df.insert_column_at(0, "c", new.view()).sumdf().as_array().unwrap();
+    assert_eq!(z, arr1(&[11., 4., 6.]));
+}
+
+#[test]
+fn dataframe_shape_accessors() {
+    let a = arr2(&[[1., 2.], [3., 4.], [5., 6.]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["a", "b"]).unwrap();
+    assert_eq!(df.shape(), (3, 2));
+    assert_eq!(df.nrows(), 3);
+    assert_eq!(df.ncols(), 2);
+    assert!(!df.is_empty());
+    assert_eq!(df.df_iter(UtahAxis::Row).len(), 3);
+    let new = arr1(&[7., 8.]);
+    assert_eq!(df.df_iter(UtahAxis::Row).append("3", new.view()).len(), 4);
+    assert_eq!(df.insert_column_at(1, "c", arr1(&[0., 0., 0.]).view()).len(), 3);
+}
+
+#[test]
+fn dataframe_dtypes() {
+    let a = arr2(&[[InnerType::Float(1.), InnerType::Str("x".into()), InnerType::Empty],
+                   [InnerType::Empty, InnerType::Str("y".into()), InnerType::Empty],
+                   [InnerType::Float(2.), InnerType::Int64(3), InnerType::Empty]]);
+    let df: DataFrame<InnerType> = DataFrame::new(a).columns(&["a", "b", "c"]).unwrap();
+    assert_eq!(df.dtypes(), vec![DType::Float, DType::Mixed, DType::Empty]);
+    let z = df.select_dtypes(&[DType::Float, DType::Empty]);
+    assert_eq!(z.columns, vec!["a", "c"]);
+    assert_eq!(z.shape(), (3, 2));
+}
+
+#[test]
+fn dataframe_pop_column() {
+    let a = arr2(&[[1., 2., 3.], [4., 5., 6.]]);
+    let mut df: DataFrame<f64> = DataFrame::new(a).columns(&["a", "target", "c"]).unwrap();
+    let y = df.pop_column("target").unwrap();
+    assert_eq!(y, arr1(&[2., 5.]));
+    let expected = DataFrame::new(arr2(&[[1., 3.], [4., 6.]])).columns(&["a", "c"]).unwrap();
+    assert_eq!(df, expected);
+    assert!(df.pop_column("target").is_err());
+}
+
+#[test]
+fn shared_dataframe_concurrent_extend() {
+    use std::sync::Arc;
+    use std::thread;
+    let shared: Arc<SharedDataFrame<f64>> = Arc::new(SharedDataFrame::new(&["a", "b"]));
+    let handles: Vec<_> = vec![("a", 1.), ("b", 2.)]
+        .into_iter()
+        .map(|(c, x)| {
+            let shared = shared.clone();
+            thread::spawn(move || for _ in 0..100 {
+                shared.extend(c, &[x]).unwrap();
+            })
+        })
+        .collect();
+    for h in handles {
+        h.join().unwrap();
+    }
+    let df = shared.to_df().unwrap();
+    assert_eq!(df.shape(), (100, 2));
+    assert_eq!(df.reduce(Agg::Sum), 300.);
+    shared.extend("a", &[1.]).unwrap();
+    assert_eq!(shared.column_len("a").unwrap(), 101);
+    assert!(shared.to_df().is_err());
+    assert!(shared.extend("c", &[1.]).is_err());
+}
+
+#[test]
+fn shared_dataframe_append_rows() {
+    use std::sync::Arc;
+    use std::thread;
+    let shared: Arc<SharedDataFrame<f64>> = Arc::new(SharedDataFrame::new(&["a", "b"]));
+    let handles: Vec<_> = vec![1., 2., 3.]
+        .into_iter()
+        .map(|x| {
+            let shared = shared.clone();
+            thread::spawn(move || for _ in 0..100 {
+                shared.append_rows(&[x, x, x, x]).unwrap();
+            })
+        })
+        .collect();
+    for h in handles {
+        h.join().unwrap();
+    }
+    let df = shared.to_df().unwrap();
+    assert_eq!(df.shape(), (600, 2));
+    assert!(df.data.outer_iter().all(|r| r[0] == r[1]));
+    assert!(shared.append_rows(&[1., 2., 3.]).is_err());
+    let part = DataFrame::new(arr2(&[[7., 8.]])).columns(&["a", "b"]).unwrap();
+    shared.append_partition(&part).unwrap();
+    assert_eq!(shared.to_df().unwrap().data.row(600).to_owned(), arr1(&[7., 8.]));
+    let part = DataFrame::new(arr2(&[[7., 8.]])).columns(&["b", "a"]).unwrap();
+    assert!(shared.append_partition(&part).is_err());
+}
+
+#[test]
+fn dataframe_with_column() {
+    let a = arr2(&[[1., 2.], [3., 4.]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["a", "b"]).unwrap();
+    let z = df.with_column("c", |row| row["a"] + row["b"]).as_df().unwrap();
+    let expected = DataFrame::new(arr2(&[[1., 2., 3.], [3., 4., 7.]]))
+        .columns(&["a", "b", "c"])
+        .unwrap();
+    assert_eq!(z, expected);
+    let z = df.with_column("d", |row| *row.get("z").unwrap_or(&0.)).as_matrix().unwrap();
+    assert_eq!(z.column(2), arr1(&[0., 0.]));
+}
+
+#[test]
+fn dataframe_transaction() {
+    let a = arr2(&[[1., 2.], [3., 4.]]);
+    let mut df: DataFrame<f64> = DataFrame::new(a).columns(&["a", "b"]).unwrap();
+    let original = df.clone();
+    {
+        let mut tx = df.begin();
+        tx.column_mut("a").unwrap().mapv_inplace(|x| x * 10.);
+        assert_eq!(tx.df().data.column(0), arr1(&[10., 30.]));
+        tx.frame_mut().pop_column("b").unwrap();
+        tx.rollback();
+    }
+    assert_eq!(df, original);
+    {
+        let mut tx = df.begin();
+        tx.column_mut("b").unwrap().mapv_inplace(|x| x + 1.);
+        assert!(tx.column_mut("c").is_err());
+    }
+    assert_eq!(df, original);
+    {
+        let mut tx = df.begin();
+        tx.column_mut("b").unwrap().mapv_inplace(|x| x + 1.);
+        tx.commit();
+    }
+    assert_eq!(df.data.column(1), arr1(&[3., 5.]));
+}
+
+#[test]
+fn dataframe_apply() {
+    let a = arr2(&[[1., 3.], [2., 2.], [3., 7.]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["a", "b"]).unwrap();
+    let z = df.apply(|r| {
+                let s = r.scalar_sum();
+                r.iter().map(|x| x / s).collect()
+            },
+               UtahAxis::Row)
+        .as_df()
+        .unwrap();
+    let expected = DataFrame::new(arr2(&[[0.25, 0.75], [0.5, 0.5], [0.3, 0.7]]))
+        .columns(&["a", "b"])
+        .unwrap();
+    assert_eq!(z, expected);
+    let z = df.apply(|c| vec![c[0], c[2]], UtahAxis::Column).as_df().unwrap();
+    assert_eq!(z.index, vec!["0", "1"]);
+    assert_eq!(z.data, arr2(&[[1., 3.], [3., 7.]]));
+    let z = df.apply(|r| if r[0] > 1. { vec![r[0]] } else { vec![] }, UtahAxis::Row).as_df();
+    assert!(z.is_err());
+}
+
+#[test]
+fn dataframe_define_virtual_columns() {
+    let a = arr2(&[[10., 11.], [20., 23.]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["bid", "ask"]).unwrap();
+    let v = df.define("spread", col("ask") - col("bid"))
+        .define("mid", (col("ask") + col("bid")) / lit(2.));
+    assert_eq!(v.column("spread").unwrap(), arr1(&[1., 3.]));
+    let mut z = v.select(&["mid", "spread"]).unwrap();
+    assert_eq!(z.data, arr2(&[[10.5, 1.], [21.5, 3.]]));
+    assert_eq!(z.sumdf(UtahAxis::Column).as_array().unwrap(), arr1(&[32., 4.]));
+    assert_eq!(v.as_df().unwrap().columns, vec!["bid", "ask", "spread", "mid"]);
+    assert!(v.column("x").is_err());
+    let v = df.define("x", col("y")).define("y", col("x"));
+    assert!(v.column("x").is_err());
+}
+
+#[test]
+fn series_alignment() {
+    let a = arr2(&[[1., 2.], [3., 4.], [5., 6.]]);
+    let mut df: DataFrame<f64> = DataFrame::new(a)
+        .columns(&["a", "b"])
+        .unwrap()
+        .index(&["x", "y", "z"])
+        .unwrap();
+    let s = df.column("b").unwrap();
+    assert_eq!(s.index, vec!["x", "y", "z"]);
+    assert_eq!((s.sum(), s.mean(), s.max(), s.min()), (12., 4., 6., 2.));
+    let mut t = Series::new("t", arr1(&[10., 20.]));
+    t.index = vec!["z".to_string(), "w".to_string()];
+    let u = &s + &t;
+    assert_eq!(u.index, vec!["x", "y", "z", "w"]);
+    assert_eq!(u.get("z"), Some(&16.));
+    assert_eq!(u.count(), 1);
+    let mut v = Series::new("v", arr1(&[1., 2., 3.]));
+    v.index = vec!["z".to_string(), "x".to_string(), "z".to_string()];
+    let u = &s + &v;
+    assert_eq!(u.index, vec!["x", "y", "z", "z"]);
+    assert_eq!(u.get("z"), Some(&7.));
+    assert!(u.data[3].is_nan());
+    df.insert_series_at(1, &t).unwrap();
+    assert_eq!(df.columns, vec!["a", "t", "b"]);
+    assert_eq!(df.data.row(2), arr1(&[5., 10., 6.]));
+    assert!(df.data[[0, 1]].is_nan());
+    assert!(df.append_series(&t).is_err());
+    df.append_series(&v).unwrap();
+    assert_eq!(df.columns, vec!["a", "t", "b", "v"]);
+    assert_eq!((df.data[[0, 3]], df.data[[2, 3]]), (2., 1.));
+    assert!(df.data[[1, 3]].is_nan());
+}
+
+#[test]
+fn dataframe_arithmetic_alignment() {
+    let a: DataFrame<f64> = DataFrame::new(arr2(&[[1., 2.], [3., 4.]]))
+        .columns(&["a", "b"])
+        .unwrap();
+    let b: DataFrame<f64> = DataFrame::new(arr2(&[[10., 20.], [30., 40.]]))
+        .columns(&["b", "c"])
+        .unwrap()
+        .index(&["1", "2"])
+        .unwrap();
+    let z = &a + &b;
+    assert_eq!(z.columns, vec!["a", "b", "c"]);
+    assert_eq!(z.index, vec!["0", "1", "2"]);
+    assert_eq!(z.data[[1, 1]], 14.);
+    assert_eq!(z.data.iter().filter(|x| x.is_nan()).count(), 8);
+    let expected = DataFrame::new(arr2(&[[1., 4.], [9., 16.]])).columns(&["a", "b"]).unwrap();
+    assert_eq!(&a * &a, expected);
+}
+
+#[test]
+fn dataframe_block_index_filter_range() {
+    let a = arr2(&[[1., 0.], [2., 0.], [3., 0.], [4., 0.], [5., 0.], [NAN, 0.], [7., 0.]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["t", "x"]).unwrap();
+    let index = df.block_index("t", 3).unwrap();
+    assert_eq!(index.bounds, vec![Some((1., 3.)), Some((4., 5.)), Some((7., 7.))]);
+    assert_eq!(index.candidates(&3.5, &5.), vec![(3, 6)]);
+    let z = df.filter_range(&index, &2., &4.).unwrap();
+    assert_eq!(z.index, vec!["1", "2", "3"]);
+    let other: DataFrame<f64> = DataFrame::new(arr2(&[[1., 2.]])).columns(&["t", "x"]).unwrap();
+    assert!(other.filter_range(&index, &2., &4.).is_err());
+}
+
+#[test]
+fn dataframe_groupby_median_quantile() {
+    let a = arr2(&[[1., 5.], [2., 1.], [1., 1.], [1., 3.], [2., 4.], [1., NAN]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["g", "x"]).unwrap();
+    let g = df.groupby("g").unwrap();
+    let z = g.median();
+    assert_eq!(z.columns, vec!["x"]);
+    assert_eq!(z.index, vec!["1.0", "2.0"]);
+    assert_eq!(z.data, arr2(&[[3.], [2.5]]));
+    assert_eq!(g.quantile(0.25).data, arr2(&[[2.], [1.75]]));
+    assert_eq!(g.quantile(1.).data, arr2(&[[5.], [4.]]));
+}
+
+#[test]
+fn dataframe_scalar_broadcast() {
+    let a = arr2(&[[1., 2.], [3., NAN]]);
+    let mut df: DataFrame<f64> = DataFrame::new(a).columns(&["a", "b"]).unwrap();
+    let z = &df * 2.;
+    assert_eq!(z.data.row(0), arr1(&[2., 4.]));
+    assert_eq!((&df + 1.).data[[1, 0]], 4.);
+    let z = df.scalar_op(ArithOp::Sub, 1., UtahAxis::Column).as_df().unwrap();
+    assert_eq!(z.columns, vec!["a", "b"]);
+    assert_eq!(z.data.row(0), arr1(&[0., 1.]));
+    assert_eq!(df.data.row(1)[0], 2.);
+    assert!(df.data[[1, 1]].is_nan());
+}
+
+#[test]
+fn mixed_types_decode() {
+    let x: InnerType = ::rustc_serialize::json::decode(r#"{"variant":"Float","fields":[1.5]}"#)
+        .unwrap();
+    assert_eq!(x, InnerType::Float(1.5));
+    let x: InnerType = ::rustc_serialize::json::decode(r#""Empty""#).unwrap();
+    assert_eq!(x, InnerType::Empty);
+    let x: OuterType = ::rustc_serialize::json::decode(r#"{"variant":"Str","fields":["a"]}"#)
+        .unwrap();
+    assert_eq!(x, OuterType::Str("a".to_string()));
+    assert!(::rustc_serialize::json::decode::<InnerType>(r#""Bool""#).is_err());
+}
+
+#[test]
+fn recipe_apply() {
+    let a = arr2(&[[1., 10., 0.], [2., 20., 0.], [1., 30., 0.], [2., 99., 0.], [1., 5., 0.]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["g", "x", "y"]).unwrap();
+    let recipe = Recipe::new("mean x by g")
+        .then(Step::Remove(vec!["y".to_string()]))
+        .then(Step::FilterRange("x".to_string(), 0., 50.))
+        .then(Step::GroupBy("g".to_string(), Agg::Mean));
+    let z = recipe.apply(&df).unwrap();
+    assert_eq!(z.columns, vec!["x"]);
+    assert_eq!(z.data, arr2(&[[15.], [20.]]));
+    let bad = Recipe::new("bad").then(Step::Select(vec!["z".to_string()]));
+    assert!(bad.apply(&df).is_err());
+    let recipe = recipe.then(Step::Sort(vec!["x".to_string()], false));
+    let encoded = ::rustc_serialize::json::encode(&recipe).unwrap();
+    assert!(encoded.contains(r#"{"variant":"GroupBy","fields":["g","Mean"]}"#));
+    let decoded: Recipe = ::rustc_serialize::json::decode(&encoded).unwrap();
+    assert_eq!(decoded, recipe);
+    let unknown = r#"{"name":"x","steps":["Pivot"]}"#;
+    assert!(::rustc_serialize::json::decode::<Recipe>(unknown).is_err());
+}
+
+#[test]
+fn panel_consistency_check() {
+    let a: DataFrame<f64> = DataFrame::new(arr2(&[[1., 2.]])).columns(&["a", "b"]).unwrap();
+    let b: DataFrame<f64> = DataFrame::new(arr2(&[[3., 4.]]))
+        .columns(&["a", "b"])
+        .unwrap()
+        .index(&["1"])
+        .unwrap();
+    let report = check_panel(&[a.clone(), b.clone()]);
+    assert!(report.can_concat());
+    assert!(!report.can_join());
+    let c: DataFrame<f64> = DataFrame::new(arr2(&[[5., 6.]])).columns(&["a", "c"]).unwrap();
+    let report = check_panel(&[a, b, c]);
+    assert_eq!(report.column_mismatches, vec![2]);
+    assert_eq!(report.index_overlaps, vec![(0, 2, vec!["0".to_string()])]);
+    let m = DataFrame::new(arr2(&[[InnerType::Float(1.), InnerType::Str("x".into())]]))
+        .columns(&["a", "b"])
+        .unwrap();
+    let n = DataFrame::new(arr2(&[[InnerType::Float(1.), InnerType::Int64(2)]]))
+        .columns(&["a", "b"])
+        .unwrap()
+        .index(&["1"])
+        .unwrap();
+    let o = DataFrame::new(arr2(&[[InnerType::Str("y".into()), InnerType::Float(3.)]]))
+        .columns(&["b", "a"])
+        .unwrap();
+    let report = check_panel_mixed(&[m.clone(), o]);
+    assert!(report.dtype_mismatches.is_empty());
+    let report = check_panel_mixed(&[m, n]);
+    assert_eq!(report.dtype_mismatches, vec![(1, "b".to_string())]);
+    assert!(!report.can_concat());
+}
+
+#[test]
+fn dataframe_dot() {
+    let x: DataFrame<f64> = DataFrame::new(arr2(&[[1., 2.], [3., 4.]]))
+        .columns(&["f1", "f2"])
+        .unwrap();
+    let w: DataFrame<f64> = DataFrame::new(arr2(&[[1., 0., 2.], [0., 1., 1.]]))
+        .columns(&["a", "b", "c"])
+        .unwrap()
+        .index(&["f1", "f2"])
+        .unwrap();
+    let z = x.dot(&w).unwrap();
+    assert_eq!(z.columns, vec!["a", "b", "c"]);
+    assert_eq!(z.index, x.index);
+    assert_eq!(z.data, arr2(&[[1., 2., 4.], [3., 4., 10.]]));
+    assert!(w.dot(&x).is_err());
+}
+
+#[test]
+fn dataframe_cumsum_cumprod() {
+    let a = arr2(&[[1., 2.], [NAN, 3.], [4., 4.]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["a", "b"]).unwrap();
+    let z = df.cumsum(UtahAxis::Column).as_df().unwrap();
+    assert_eq!(z.columns, vec!["a", "b"]);
+    assert_eq!(z.data.column(0)[2], 5.);
+    assert!(z.data[[1, 0]].is_nan());
+    assert_eq!(z.data.column(1), arr1(&[2., 5., 9.]));
+    let z = df.cumprod(UtahAxis::Row).as_matrix().unwrap();
+    assert_eq!(z.row(2), arr1(&[4., 16.]));
+    assert_eq!(z.row(1)[1], 3.);
+}
+
+#[test]
+fn dataframe_assertions() {
+    let a = arr2(&[[1., 5.], [2., 5.], [NAN, 1.], [2., 7.], [1., 9.]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["t", "x"]).unwrap();
+    assert!(df.assert_monotonic("t", Monotonic::Increasing).is_err());
+    match df.assert_monotonic("x", Monotonic::Increasing) {
+        Err(Error(ErrorKind::ConstraintViolation(c, labels), _)) => {
+            assert_eq!(c, "x");
+            assert_eq!(labels, vec!["2"]);
+        }
+        _ => panic!(),
+    }
+    match df.assert_monotonic("t", Monotonic::StrictlyIncreasing) {
+        Err(Error(ErrorKind::ConstraintViolation(_, labels), _)) => {
+            assert_eq!(labels, vec!["3", "4"])
+        }
+        _ => panic!(),
+    }
+    assert!(df.assert_in_range("t", &1., &2.).is_ok());
+    assert!(df.assert_in_range("x", &1., &8.).is_err());
+    assert!(df.assert_in_range("z", &1., &8.).is_err());
+}
+
+#[test]
+fn dataframe_groupby_str_join() {
+    let a = arr2(&[[InnerType::Int64(1), InnerType::Str("red".into())],
+                   [InnerType::Int64(2), InnerType::Str("blue".into())],
+                   [InnerType::Int64(1), InnerType::Empty],
+                   [InnerType::Int64(1), InnerType::Str("green".into())]]);
+    let df: DataFrame<InnerType> = DataFrame::new(a).columns(&["user", "tag"]).unwrap();
+    let z = df.groupby("user").unwrap().str_join(",");
+    assert_eq!(z.columns, vec!["tag"]);
+    assert_eq!(z.data.column(0),
+               arr1(&[InnerType::Str("red,green".into()), InnerType::Str("blue".into())]));
+}
+
+#[test]
+fn dataframe_shift_diff() {
+    let a = arr2(&[[1., 10.], [3., 20.], [6., 40.]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["a", "b"]).unwrap();
+    let z = df.shift(1, 0., UtahAxis::Column).as_df().unwrap();
+    assert_eq!(z.columns, vec!["a", "b"]);
+    assert_eq!(z.data, arr2(&[[0., 0.], [1., 10.], [3., 20.]]));
+    let z = df.shift(-2, 0., UtahAxis::Column).as_matrix().unwrap();
+    assert_eq!(z.column(1), arr1(&[40., 0., 0.]));
+    let z = df.diff(1, UtahAxis::Column).as_matrix().unwrap();
+    assert!(z[[0, 0]].is_nan());
+    assert_eq!(z.column(0).slice(s![1..]), arr1(&[2., 3.]));
+    let z = df.diff(1, UtahAxis::Row).as_matrix().unwrap();
+    assert_eq!(z.column(1), arr1(&[9., 17., 34.]));
+}
+
+#[test]
+fn dataframe_first_last_valid() {
+    let a = arr2(&[[1., NAN, 1.], [2., 5., NAN], [1., NAN, 3.], [2., 6., NAN]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["g", "x", "y"]).unwrap();
+    let z = df.first_valid(UtahAxis::Column).as_array().unwrap();
+    assert_eq!(z, arr1(&[1., 5., 1.]));
+    let z = df.last_valid(UtahAxis::Column).as_df().unwrap();
+    assert_eq!(z.columns, vec!["g", "x", "y"]);
+    assert_eq!(z.data, arr2(&[[2., 6., 3.]]));
+    assert_eq!(df.last_valid(UtahAxis::Row).as_array().unwrap(), arr1(&[1., 5., 3., 6.]));
+    let z = df.groupby("g").unwrap().agg(Agg::FirstValid);
+    assert!(z.data[[0, 0]].is_nan() && z.data[[1, 1]].is_nan());
+    assert_eq!((z.data[[0, 1]], z.data[[1, 0]]), (1., 5.));
+}
+
+#[test]
+fn dataframe_rank() {
+    let a = arr2(&[[2., 1.], [NAN, 3.], [2., 3.], [1., 3.]]);
+    {
+        let mut df: DataFrame<f64> = DataFrame::new(a.clone()).columns(&["a", "b"]).unwrap();
+        let z = df.rank(UtahAxis::Column, RankMethod::Average, None).as_df().unwrap();
+        assert_eq!(z.columns, vec!["a", "b"]);
+        assert_eq!(z.data.column(1).to_owned(), arr1(&[1., 3., 3., 3.]));
+        assert_eq!(z.data[[0, 0]], 2.5);
+        assert!(z.data[[1, 0]].is_nan());
+    }
+    let cases = [(RankMethod::Min, arr1(&[2., 4., 2., 1.])),
+                 (RankMethod::Max, arr1(&[3., 4., 3., 1.])),
+                 (RankMethod::Dense, arr1(&[2., 3., 2., 1.]))];
+    for &(method, ref expected) in cases.iter() {
+        let mut df: DataFrame<f64> = DataFrame::new(a.clone()).columns(&["a", "b"]).unwrap();
+        df.rank(UtahAxis::Column, method, Some(EmptyPosition::Last)).as_df().unwrap();
+        assert_eq!(df.data.column(0).to_owned(), *expected);
+    }
+    let mut df: DataFrame<f64> = DataFrame::new(a).columns(&["a", "b"]).unwrap();
+    let z = df.rank(UtahAxis::Row, RankMethod::Min, Some(EmptyPosition::First)).as_df().unwrap();
+    assert_eq!(z.data, arr2(&[[2., 1.], [1., 2.], [1., 2.], [1., 2.]]));
+}
+
+#[test]
+fn dataframe_mask_take_rows() {
+    let a = arr2(&[[1., 10.], [2., NAN], [3., 30.], [4., 40.]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["a", "b"]).unwrap();
+    let big = df.mask("a", |x| *x > 1.5).unwrap();
+    let valid = df.mask("b", |x| !x.is_nan()).unwrap();
+    assert_eq!(big.count(), 3);
+    let z = df.take_rows(&(&big & &valid)).unwrap();
+    assert_eq!(z.index, vec!["2", "3"]);
+    assert_eq!(z.data, arr2(&[[3., 30.], [4., 40.]]));
+    let z = df.take_rows(&(&!&big | &!valid)).unwrap();
+    assert_eq!(z.index, vec!["0", "1"]);
+    assert_eq!(RowSet::full(70).count(), 70);
+    assert!(df.take_rows(&RowSet::new(3)).is_err());
+}
+
+#[test]
+fn dataframe_clip() {
+    let a = arr2(&[[-5., 1.], [2., NAN], [9., 4.]]);
+    let mut df: DataFrame<f64> = DataFrame::new(a.clone()).columns(&["a", "b"]).unwrap();
+    let z = df.clip(0., 3.).as_df().unwrap();
+    assert_eq!(z.columns, vec!["a", "b"]);
+    assert_eq!(z.data.column(0).to_owned(), arr1(&[0., 2., 3.]));
+    assert!(df.data[[1, 1]].is_nan());
+    assert_eq!(df.data[[2, 1]], 3.);
+    let mut df: DataFrame<f64> = DataFrame::new(a.clone()).columns(&["a", "b"]).unwrap();
+    df.clip_lower(1.5).as_df().unwrap();
+    assert_eq!(df.data.column(0).to_owned(), arr1(&[1.5, 2., 9.]));
+    let mut df: DataFrame<f64> = DataFrame::new(a).columns(&["a", "b"]).unwrap();
+    df.clip_upper(1.5).as_df().unwrap();
+    assert_eq!(df.data.column(0).to_owned(), arr1(&[-5., 1.5, 1.5]));
+}
+
+#[test]
+fn dataframe_describe_cached() {
+    use cache::fingerprint;
+    let a = arr2(&[[1., 4.], [3., NAN], [2., 8.]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["a", "b"]).unwrap();
+    let z = df.describe();
+    assert_eq!(z.index, vec!["count", "mean", "min", "max", "empty"]);
+    assert_eq!(z.data, arr2(&[[3., 2.], [2., 6.], [1., 4.], [3., 8.], [0., 1.]]));
+    let cache = StatsCache::new();
+    assert_eq!(df.describe_cached(&cache).unwrap().data, z.data);
+    let b = arr2(&[[1., 0.], [3., 0.], [2., 0.]]);
+    let other: DataFrame<f64> = DataFrame::new(b).columns(&["x", "y"]).unwrap();
+    let z = other.describe_cached(&cache).unwrap();
+    assert_eq!(z.columns, vec!["x", "y"]);
+    assert_eq!((cache.len().unwrap(), cache.hits().unwrap()), (3, 1));
+    let mut c = Array::from_elem((100, 2), 1.);
+    c[[1, 1]] = 5.;
+    let other: DataFrame<f64> = DataFrame::new(c).columns(&["x", "y"]).unwrap();
+    assert_eq!(fingerprint(other.data.column(0)), fingerprint(other.data.column(1)));
+    let z = other.describe_cached(&cache).unwrap();
+    assert_eq!(z.data.row(3).to_owned(), arr1(&[1., 5.]));
+    assert_eq!((cache.len().unwrap(), cache.hits().unwrap()), (5, 1));
+    // A limited cache stops storing columns once full, but still describes them.
+    let cache = StatsCache::with_limit(1);
+    assert_eq!(other.describe_cached(&cache).unwrap().data.row(3).to_owned(),
+               arr1(&[1., 5.]));
+    assert_eq!(cache.len().unwrap(), 1);
+    other.describe_cached(&cache).unwrap();
+    assert_eq!(cache.hits().unwrap(), 1);
+}
+
+#[test]
+fn dataframe_replace() {
+    let a = arr2(&[[-999., 1.], [2., -999.], [NAN, 1.]]);
+    {
+        let mut df: DataFrame<f64> = DataFrame::new(a.clone()).columns(&["a", "b"]).unwrap();
+        let z = df.replace(&[(-999., NAN), (NAN, 0.)], None).as_df().unwrap();
+        assert_eq!(z.columns, vec!["a", "b"]);
+        assert!(z.data[[0, 0]].is_nan() && z.data[[1, 1]].is_nan());
+        assert_eq!(z.data[[2, 0]], 0.);
+    }
+    {
+        let mut df: DataFrame<f64> = DataFrame::new(a.clone()).columns(&["a", "b"]).unwrap();
+        df.replace(&[(-999., 0.)], Some(&["b"])).as_df().unwrap();
+        assert_eq!((df.data[[0, 0]], df.data[[1, 1]]), (-999., 0.));
+    }
+    let mut df: DataFrame<f64> = DataFrame::new(a).columns(&["a", "b"]).unwrap();
+    df.replace_with(|x| if *x < 0. { Some(-x) } else { None }, None).as_df().unwrap();
+    assert_eq!(df.data.row(1).to_owned(), arr1(&[2., 999.]));
+}
+
+#[test]
+fn dataframe_where_mask() {
+    let a = arr2(&[[1., -2.], [-3., 4.]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["a", "b"]).unwrap();
+    let mask = df.cell_mask(|x| *x > 0.);
+    assert_eq!(mask, arr2(&[[true, false], [false, true]]));
+    let z = df.where_mask(&mask, 0.).unwrap();
+    assert_eq!(z.data, arr2(&[[1., 0.], [0., 4.]]));
+    let other: DataFrame<f64> = DataFrame::new(arr2(&[[10., 20.], [30., 40.]]))
+        .columns(&["a", "b"])
+        .unwrap();
+    let z = df.where_mask_df(&mask, &other).unwrap();
+    assert_eq!(z.data, arr2(&[[1., 20.], [30., 4.]]));
+    assert!(df.where_mask(&arr2(&[[true, false]]), 0.).is_err());
+    let other = other.columns(&["a", "c"]).unwrap();
+    assert!(df.where_mask_df(&mask, &other).is_err());
+}
+
+#[test]
+fn ffi_frame_roundtrip() {
+    use ffi::*;
+    use std::ffi::{CStr, CString};
+    use std::os::raw::c_char;
+    use std::ptr;
+    let names = [CString::new("a").unwrap(), CString::new("b").unwrap()];
+    let ptrs: Vec<_> = names.iter().map(|x| x.as_ptr()).collect();
+    let data = [1., 10., 2., 20., 3., 30.];
+    unsafe {
+        let df = utah_frame_new(data.as_ptr(), 3, 2, ptrs.as_ptr());
+        assert_eq!((utah_frame_nrows(df), utah_frame_ncols(df)), (3, 2));
+        let filtered = utah_frame_filter(df, ptrs[0], 1.5, 3.);
+        let selected = utah_frame_select(filtered, ptrs[1..].as_ptr(), 1);
+        let mut out = [0.; 2];
+        assert_eq!(utah_frame_read(selected, out.as_mut_ptr()), 0);
+        assert_eq!(out, [20., 30.]);
+        assert_eq!(utah_frame_agg(df, 0, out.as_mut_ptr()), 0);
+        assert_eq!(out, [6., 60.]);
+        assert_eq!(utah_frame_agg(df, 9, out.as_mut_ptr()), -1);
+        assert!(utah_frame_filter(df, CString::new("c").unwrap().as_ptr(), 0., 1.).is_null());
+        let mut label = [0 as c_char; 2];
+        assert_eq!(utah_frame_index_label(filtered, 1, label.as_mut_ptr(), 2), 1);
+        assert_eq!(CStr::from_ptr(label.as_ptr()).to_str().unwrap(), "2");
+        assert_eq!(utah_frame_column_name(selected, 0, ptr::null_mut(), 0), 1);
+        assert_eq!(utah_frame_column_name(selected, 1, label.as_mut_ptr(), 2), -1);
+        assert!(utah_frame_new(data.as_ptr(), usize::max_value(), 2, ptrs.as_ptr()).is_null());
+        assert!(utah_frame_select(ptr::null(), ptrs.as_ptr(), 1).is_null());
+        assert_eq!(utah_frame_nrows(ptr::null()), -1);
+        assert_eq!(utah_frame_agg(ptr::null(), 0, out.as_mut_ptr()), -1);
+        utah_frame_free(selected);
+        utah_frame_free(filtered);
+        utah_frame_free(df);
+    }
+}
+
+#[test]
+fn dataframe_astype() {
+    let a = arr2(&[[InnerType::Str("1.5".into()), InnerType::Float(2.)],
+                   [InnerType::Empty, InnerType::Float(3.)]]);
+    let df: DataFrame<InnerType> = DataFrame::new(a).columns(&["a", "b"]).unwrap();
+    let z = df.astype("a", DType::Float).unwrap();
+    assert_eq!(z.dtypes(), vec![DType::Float, DType::Float]);
+    let z = df.astype("b", DType::Int32).unwrap();
+    assert_eq!(z.dtypes()[1], DType::Int32);
+    let z = z.astype("b", DType::Str).unwrap();
+    assert_eq!(z.data[[1, 1]], InnerType::Str("3".into()));
+    assert!(df.astype("a", DType::Int64).is_err());
+    assert!(df.astype("c", DType::Float).is_err());
+    assert!(InnerType::Float(2.5).cast(DType::Int64).is_err());
+}
+
+#[test]
+fn private_session_budget() {
+    let a = arr2(&[[1.], [5.], [NAN], [100.]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["a"]).unwrap();
+    let mut session = PrivateSession::new(1., 0., Mechanism::Laplace, thread_rng()).unwrap();
+    let n = session.count(&df, "a", 0.5).unwrap();
+    assert!(n.is_finite());
+    let mean = session.mean(&df, "a", 0., 10., 0.25).unwrap();
+    assert!(mean >= 0. && mean <= 10.);
+    assert_eq!(session.remaining(), 0.25);
+    assert!(session.sum(&df, "a", 0., 10., 0.5).is_err());
+    assert!(session.sum(&df, "b", 0., 10., 0.1).is_err());
+    assert_eq!(session.remaining(), 0.25);
+    assert!(session.sum(&df, "a", 0., 10., NAN).is_err());
+    assert!(session.sum(&df, "a", 0., 10., -0.1).is_err());
+    assert!(session.sum(&df, "a", 10., 0., 0.1).is_err());
+    assert!(session.sum(&df, "a", NAN, 10., 0.1).is_err());
+    assert!(session.mean(&df, "a", 0., INFINITY, 0.1).is_err());
+    assert_eq!(session.remaining(), 0.25);
+    let mut session = PrivateSession::new(1., 2.5e-5, Mechanism::Gaussian(1e-5), thread_rng())
+        .unwrap();
+    assert!(session.sum(&df, "a", 0., 10., 0.2).unwrap().is_finite());
+    assert!(session.sum(&df, "a", 0., 10., 1.).is_err());
+    assert!(session.mean(&df, "a", 0., 10., 0.2).is_err());
+    assert!(session.count(&df, "a", 0.2).unwrap().is_finite());
+    assert!(session.remaining_delta() < 1e-5);
+    assert!(PrivateSession::new(1., 0.1, Mechanism::Gaussian(1.5), thread_rng()).is_err());
+    assert!(PrivateSession::new(1., 0.1, Mechanism::Gaussian(NAN), thread_rng()).is_err());
+    assert!(PrivateSession::new(0., 0., Mechanism::Laplace, thread_rng()).is_err());
+}
+
+#[test]
+fn dataframe_nullable() {
+    let a = arr2(&[[Nullable(Some(1.)), Nullable(Some(NAN))],
+                   [Nullable(None), Nullable(Some(2.))],
+                   [Nullable(Some(3.)), Nullable(None)]]);
+    let mut df: DataFrame<Nullable<f64>> = DataFrame::new(a).columns(&["a", "b"]).unwrap();
+    assert_eq!(df.reduce(Agg::EmptyCount), Nullable(Some(2.)));
+    assert_eq!(df.reduce(Agg::Count), Nullable(Some(4.)));
+    let z = df.impute(ImputeStrategy::Mean, UtahAxis::Row).as_df().unwrap();
+    assert_eq!(z.data[[1, 0]], Nullable(Some(2.)));
+    assert_eq!(z.data[[2, 1]], Nullable(Some(3.)));
+    assert!(z.data[[0, 1]].value().unwrap().is_nan());
+    assert_eq!(Nullable(Some(1.)) + Nullable::null(), Nullable::null());
+}
+
+#[test]
+fn dataframe_get_mut_row_mut() {
+    let a = arr2(&[[1., 2.], [3., 4.]]);
+    let mut df: DataFrame<f64> = DataFrame::new(a).columns(&["a", "b"]).unwrap();
+    {
+        let mut col = df.get_mut("b").unwrap();
+        col["1"] = 40.;
+        col.values.mapv_inplace(|x| x * 10.);
+    }
+    assert_eq!(df.data, arr2(&[[1., 20.], [3., 400.]]));
+    {
+        let mut row = df.row_mut("0").unwrap();
+        *row.get_mut("a").unwrap() = -1.;
+        assert!(row.get_mut("c").is_none());
+    }
+    assert_eq!(df.data[[0, 0]], -1.);
+    assert!(df.get_mut("c").is_none());
+    assert!(df.row_mut("2").is_none());
+}
+
+#[test]
+#[allow(deprecated)]
+fn dataframe_as_df_column_axis() {
+    let a = arr2(&[[1., 2., 3.], [4., 5., 6.]]);
+    let mut df: DataFrame<f64> = DataFrame::new(a).columns(&["a", "b", "c"]).unwrap();
+    let z = df.select(&["c", "a"], UtahAxis::Column).as_df().unwrap();
+    assert_eq!(z.columns, vec!["a", "c"]);
+    assert_eq!(z.data, arr2(&[[1., 3.], [4., 6.]]));
+    let z = df.remove(&["b"], UtahAxis::Column).as_matrix().unwrap();
+    assert_eq!(z, arr2(&[[1., 3.], [4., 6.]]));
+    assert_eq!(df.df_iter(UtahAxis::Column).as_df().unwrap(), df);
+    let z = df.concat(&df, UtahAxis::Row).as_df().unwrap();
+    assert_eq!(z.columns, vec!["a", "b", "c", "a", "b", "c"]);
+    assert_eq!(z.index, df.index);
+    assert_eq!(z.data.row(1).to_owned(), arr1(&[4., 5., 6., 4., 5., 6.]));
+    let z = df.maxdf(UtahAxis::Column).as_array().unwrap();
+    assert_eq!(z, arr1(&[4., 5., 6.]));
+    let z = df.mapdf(|x| x * 2., UtahAxis::Column).as_df().unwrap();
+    assert_eq!(z.columns, vec!["a", "b", "c"]);
+    assert_eq!(z.data, arr2(&[[2., 4., 6.], [8., 10., 12.]]));
+    df.data[[0, 0]] = NAN;
+    assert_eq!(df.mindf(UtahAxis::Column).as_array().unwrap(), arr1(&[8., 4., 6.]));
+}
+
+#[test]
+fn tracked_provenance() {
+    let a: DataFrame<f64> = DataFrame::new(arr2(&[[1., 10.], [2., 20.], [3., 30.]]))
+        .columns(&["a", "b"])
+        .unwrap();
+    let b: DataFrame<f64> = DataFrame::new(arr2(&[[7.], [8.]]))
+        .columns(&["c"])
+        .unwrap()
+        .index(&["2", "0"])
+        .unwrap();
+    let a = Tracked::new(a, "a.csv");
+    let b = Tracked::new(b, "b.csv");
+    let rows = a.frame.mask("a", |x| *x != 2.).unwrap();
+    let z = a.take_rows(&rows).unwrap().select_columns(&["b"]).unwrap();
+    assert_eq!(z.frame.columns, vec!["b"]);
+    assert_eq!(z.provenance_of("2")[0][0].row, 2);
+    let z = z.inner_join(&b).unwrap();
+    assert_eq!(z.frame.index, vec!["0", "2"]);
+    assert_eq!(z.provenance_of("2"),
+               vec![&[Origin { source: "a.csv".into(), row: 2 },
+                      Origin { source: "b.csv".into(), row: 0 }][..]]);
+    let z = a.concat(&a).unwrap();
+    assert_eq!(z.provenance().len(), 6);
+    assert_eq!(z.provenance()[4][0].row, 1);
+    let rows: Vec<usize> = z.provenance_of("1").iter().map(|o| o[0].row).collect();
+    assert_eq!(rows, vec![1, 1]);
+    let z = z.inner_join(&b).unwrap();
+    assert_eq!(z.frame.index, vec!["0", "2", "0", "2"]);
+    let rows: Vec<(usize, usize)> = z.provenance().iter().map(|o| (o[0].row, o[1].row)).collect();
+    assert_eq!(rows, vec![(0, 1), (2, 0), (0, 1), (2, 0)]);
+}
+
+#[test]
+fn dataframe_label_indexing() {
+    let a = arr2(&[[1., 2.], [3., 4.]]);
+    let mut df: DataFrame<f64> = DataFrame::new(a)
+        .columns(&["a", "b"])
+        .unwrap()
+        .index(&["x", "y"])
+        .unwrap();
+    assert_eq!(df[("y", "a")], 3.);
+    df[("x", "b")] = 20.;
+    assert_eq!(df.get("b").unwrap().to_owned(), arr1(&[20., 4.]));
+    assert!(df.get("c").is_none());
+    assert!(df.get_cell("z", "a").is_none());
+}
+
+#[test]
+#[should_panic(expected = "no entry at row 'x', column 'c'")]
+fn dataframe_label_indexing_missing() {
+    let a = arr2(&[[1., 2.]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["a", "b"]).unwrap().index(&["x"]).unwrap();
+    let _ = df[("x", "c")];
+}
+
+#[test]
+fn dataframe_read_csv_glob() {
+    use std::env;
+    use std::fs::{self, File};
+    use std::io::Write;
+    let dir = env::temp_dir().join("utah_read_csv_glob");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    File::create(dir.join("a.csv")).unwrap().write_all(b"x,y\n1,2\n3,4\n").unwrap();
+    File::create(dir.join("b.csv")).unwrap().write_all(b"y,z\n5,6\n").unwrap();
+    File::create(dir.join("notes.txt")).unwrap().write_all(b"x\n0\n").unwrap();
+    let pattern = dir.join("*.csv");
+    let (df, deviations) = DataFrame::<f64>::read_csv_glob(pattern.to_str().unwrap()).unwrap();
+    assert_eq!(df.columns, vec!["x", "y", "z"]);
+    assert_eq!(df.shape(), (3, 3));
+    assert_eq!(df.data.row(0).slice(s![..2]).to_owned(), arr1(&[1., 2.]));
+    assert!(df.data[[0, 2]].is_nan() && df.data[[2, 0]].is_nan());
+    assert_eq!(df.data[[2, 2]], 6.);
+    assert_eq!(deviations.len(), 2);
+    assert_eq!(deviations[0].missing, vec!["z"]);
+    assert_eq!(deviations[1].missing, vec!["x"]);
+    assert!(DataFrame::<f64>::read_csv_glob(dir.join("*.tsv").to_str().unwrap()).is_err());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn dataframe_median() {
+    let a = arr2(&[[5., 1.], [1., NAN], [3., 4.], [2., 2.]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["a", "b"]).unwrap();
+    let z = df.median(UtahAxis::Column).as_df().unwrap();
+    assert_eq!(z.columns, vec!["a", "b"]);
+    assert_eq!(z.data, arr2(&[[2.5, 2.]]));
+    let z = df.median(UtahAxis::Row).as_array().unwrap();
+    assert_eq!(z, arr1(&[3., 1., 3.5, 2.]));
+    let mut v = vec![1.; 200000];
+    v[7] = 0.;
+    v[199990] = 2.;
+    let df: DataFrame<f64> = DataFrame::new(Array::from_shape_vec((200000, 1), v).unwrap());
+    assert_eq!(df.median(UtahAxis::Column).as_array().unwrap(), arr1(&[1.]));
+}
+
+#[test]
+fn dataframe_agg_state_merge() {
+    let a = arr2(&[[1., 4.], [NAN, 2.], [3., 8.], [6., NAN], [2., 5.]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["a", "b"]).unwrap();
+    let first = df.select(&["0", "1"], UtahAxis::Row).as_df().unwrap();
+    let second = df.remove(&["0", "1"], UtahAxis::Row).as_df().unwrap();
+    let shipped = ::rustc_serialize::json::encode(&AggState::from_df(&first)).unwrap();
+    let mut state: AggState<f64> = ::rustc_serialize::json::decode(&shipped).unwrap();
+    assert_eq!(state, AggState::from_df(&first));
+    state.merge(&AggState::from_df(&second)).unwrap();
+    let z = state.finish();
+    assert_eq!(z.index, df.describe().index);
+    assert_eq!(z.data, df.describe().data);
+    let other: DataFrame<f64> = DataFrame::new(arr2(&[[1.]])).columns(&["c"]).unwrap();
+    assert!(state.merge(&AggState::from_df(&other)).is_err());
+}
+
+#[test]
+fn dataframe_apply_cdc() {
+    let a = arr2(&[[1., 10.], [2., 20.], [3., 30.]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["id", "v"]).unwrap();
+    let changes = arr2(&[[1., 2., 25.], [0., 4., 40.], [2., 1., 0.], [0., 5., 50.]]);
+    let changes: DataFrame<f64> = DataFrame::new(changes)
+        .columns(&["op", "id", "v"])
+        .unwrap()
+        .index(&["a", "b", "c", "d"])
+        .unwrap();
+    let decode = |x: &f64| match *x as i64 {
+        0 => Some(ChangeOp::Insert),
+        1 => Some(ChangeOp::Update),
+        2 => Some(ChangeOp::Delete),
+        _ => None,
+    };
+    let z = df.apply_cdc(&changes, "op", &["id"], decode).unwrap();
+    assert_eq!(z.columns, vec!["id", "v"]);
+    assert_eq!(z.index, vec!["1", "2", "b", "d"]);
+    assert_eq!(z.data, arr2(&[[2., 25.], [3., 30.], [4., 40.], [5., 50.]]));
+    assert!(df.apply_cdc(&changes, "op", &["id"], |_| None).is_err());
+    // Inserting a present key or updating a missing one is rejected, as are repeated keys.
+    assert!(df.apply_cdc(&changes, "op", &["id"], |_| Some(ChangeOp::Insert)).is_err());
+    assert!(df.apply_cdc(&changes, "op", &["id"], |_| Some(ChangeOp::Update)).is_err());
+    let a = arr2(&[[1., 10.], [1., 20.]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["id", "v"]).unwrap();
+    assert!(df.apply_cdc(&changes, "op", &["id"], decode).is_err());
+    // A deleted key can be inserted again.
+    let changes = arr2(&[[2., 1., 0.], [0., 1., 30.]]);
+    let changes: DataFrame<f64> = DataFrame::new(changes).columns(&["op", "id", "v"]).unwrap();
+    let df: DataFrame<f64> = DataFrame::new(arr2(&[[1., 10.]])).columns(&["id", "v"]).unwrap();
+    let z = df.apply_cdc(&changes, "op", &["id"], decode).unwrap();
+    assert_eq!(z.data, arr2(&[[1., 30.]]));
+}
+
+#[test]
+fn dataframe_concat_evolve() {
+    let a: DataFrame<f64> = DataFrame::new(arr2(&[[1., 2.], [3., 4.]]))
+        .columns(&["a", "b"])
+        .unwrap();
+    let b: DataFrame<f64> = DataFrame::new(arr2(&[[5., 6.]]))
+        .columns(&["c", "a"])
+        .unwrap()
+        .index(&["2"])
+        .unwrap();
+    let (z, evolution) = a.concat_evolve(&b).unwrap();
+    assert_eq!(evolution.added, vec!["c"]);
+    assert_eq!(evolution.missing, vec!["b"]);
+    assert_eq!(z.columns, vec!["a", "b", "c"]);
+    assert_eq!(z.index, vec!["0", "1", "2"]);
+    assert_eq!(z.data.row(2)[0], 6.);
+    assert!(z.data.row(2)[1].is_nan() && z.data.row(0)[2].is_nan());
+
+    let x: DataFrame<InnerType> = DataFrame::new(arr2(&[[InnerType::Float(1.)]]))
+        .columns(&["a"])
+        .unwrap();
+    let y: DataFrame<InnerType> = DataFrame::new(arr2(&[[InnerType::Str("1".to_string())]]))
+        .columns(&["a"])
+        .unwrap();
+    assert!(x.concat_evolve_checked(&x).is_ok());
+    assert!(x.concat_evolve_checked(&y).is_err());
+}
+
+#[test]
+fn dataframe_mode() {
+    let a = arr2(&[[1., 2.], [3., NAN], [3., NAN], [1., 2.], [3., 5.]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["a", "b"]).unwrap();
+    assert_eq!(df.mode(UtahAxis::Column).as_array().unwrap(), arr1(&[3., 2.]));
+    assert_eq!(df.mode(UtahAxis::Row).as_array().unwrap(), arr1(&[1., 3., 3., 1., 3.]));
+
+    let s = |x: &str| InnerType::Str(x.to_string());
+    let b = arr2(&[[s("x")], [InnerType::Empty], [s("y")], [s("y")]]);
+    let mut df: DataFrame<InnerType> = DataFrame::new(b).columns(&["c"]).unwrap();
+    assert_eq!(df.mode(UtahAxis::Column).as_array().unwrap(), arr1(&[s("y")]));
+    let z = df.impute(ImputeStrategy::Mode, UtahAxis::Column).as_df().unwrap();
+    assert_eq!(z.data, arr2(&[[s("x")], [s("y")], [s("y")], [s("y")]]));
+}
+
+#[test]
+fn dataframe_count() {
+    let a = arr2(&[[1., NAN], [NAN, NAN], [3., 4.]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["a", "b"]).unwrap();
+    let z = df.count(UtahAxis::Column).as_df().unwrap();
+    assert_eq!(z.columns, vec!["a", "b"]);
+    assert_eq!(z.data, arr2(&[[2., 1.]]));
+    assert_eq!(df.count(UtahAxis::Row).as_array().unwrap(), arr1(&[1., 0., 2.]));
+    assert_eq!(Nullable::<f64>::from_count(0), Nullable(Some(0.)));
+    assert_eq!(Nullable::<f64>::from_count(1 << 40 | 5), Nullable(Some((1u64 << 40 | 5) as f64)));
+    assert_eq!(InnerType::from_count(3), InnerType::Float(3.));
+}
+
+#[test]
+fn dataframe_corr() {
+    let a = arr2(&[[1., 2., 4.], [2., 4., 3.], [3., 7., 2.], [4., NAN, 1.], [5., 20., 0.]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["a", "b", "c"]).unwrap();
+    let z = df.corr(CorrMethod::Spearman);
+    assert_eq!(z.columns, vec!["a", "b", "c"]);
+    assert_eq!(z.index, vec!["a", "b", "c"]);
+    assert_eq!(z.data, arr2(&[[1., 1., -1.], [1., 1., -1.], [-1., -1., 1.]]));
+    let z = df.corr(CorrMethod::Kendall);
+    assert_eq!(z.data.row(0).to_owned(), arr1(&[1., 1., -1.]));
+    let z = df.corr(CorrMethod::Pearson);
+    assert!((z.data[[0, 1]] - 0.9).abs() < 0.1 && z.data[[0, 2]] == -1.);
+
+    let b = arr2(&[[InnerType::Int32(1), InnerType::Str("x".to_string()), InnerType::Float(2.)],
+                   [InnerType::Int32(2), InnerType::Str("y".to_string()), InnerType::Float(4.)]]);
+    let df: DataFrame<InnerType> = DataFrame::new(b).columns(&["a", "s", "b"]).unwrap();
+    let z = df.corr(CorrMethod::Pearson).unwrap();
+    assert_eq!(z.columns, vec!["a", "b"]);
+    assert_eq!(z.data, arr2(&[[1., 1.], [1., 1.]]));
+}
+
+#[test]
+fn dataframe_gmean_hmean() {
+    let a = arr2(&[[1., 1.], [4., NAN], [16., 3.]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["a", "b"]).unwrap();
+    let z = df.gmean(UtahAxis::Column).unwrap().as_array().unwrap();
+    assert!((z[0] - 4.).abs() < 1e-12 && (z[1] - 3f64.sqrt()).abs() < 1e-12);
+    let z = df.hmean(UtahAxis::Column).unwrap().as_df().unwrap();
+    assert_eq!(z.columns, vec!["a", "b"]);
+    assert_eq!(z.data, arr2(&[[3. / (1. + 0.25 + 0.0625), 1.5]]));
+    let bad: DataFrame<f64> = DataFrame::new(arr2(&[[1., 0.]])).columns(&["a", "b"]).unwrap();
+    assert!(bad.gmean(UtahAxis::Row).is_err());
+    assert!(bad.hmean(UtahAxis::Row).is_err());
+}
+
+#[test]
+fn dataframe_prod() {
+    let a = arr2(&[[1.1, 2.], [NAN, 3.], [1.5, NAN]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["a", "b"]).unwrap();
+    let z = df.prod(UtahAxis::Column).as_df().unwrap();
+    assert_eq!(z.columns, vec!["a", "b"]);
+    assert_eq!(z.data, arr2(&[[1.1 * 1.5, 6.]]));
+    assert_eq!(df.prod(UtahAxis::Row).as_array().unwrap(), arr1(&[2.2, 3., 1.5]));
+}
+
+#[test]
+fn dataframe_mask_any_all() {
+    let a = arr2(&[[1., NAN], [2., 3.], [NAN, NAN]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["a", "b"]).unwrap();
+    let missing = df.cell_mask(|x| x.is_nan());
+    assert_eq!(missing.any(UtahAxis::Row), arr1(&[true, false, true]));
+    assert_eq!(missing.all(UtahAxis::Row), arr1(&[false, false, true]));
+    assert_eq!(missing.any(UtahAxis::Column), arr1(&[true, true]));
+    assert_eq!(missing.all(UtahAxis::Column), arr1(&[false, false]));
+}
+
+#[test]
+fn dataframe_nunique() {
+    let a = arr2(&[[1., 2.], [1., NAN], [3., 2.], [1.05, 2.]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["a", "b"]).unwrap();
+    let z = df.nunique(UtahAxis::Column, None).as_df().unwrap();
+    assert_eq!(z.columns, vec!["a", "b"]);
+    assert_eq!(z.data, arr2(&[[3., 1.]]));
+    let z = df.nunique(UtahAxis::Column, Some(0.1)).as_array().unwrap();
+    assert_eq!(z, arr1(&[2., 1.]));
+    assert_eq!(df.nunique(UtahAxis::Row, None).as_array().unwrap(), arr1(&[2., 1., 2., 2.]));
+}
+
+#[test]
+fn dataframe_rolling_moments() {
+    let a = arr2(&[[1.], [2.], [NAN], [4.], [6.]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["a"]).unwrap();
+    let z = df.rolling(2).sum().as_array().unwrap();
+    assert!(z[0].is_nan());
+    assert_eq!(z.slice(s![1..]), arr1(&[3., 2., 4., 10.]));
+    let z = df.rolling(3).mean().as_df().unwrap();
+    assert_eq!(z.index, df.index);
+    assert!(z.data[[1, 0]].is_nan());
+    assert_eq!(z.data.slice(s![2.., ..]), arr2(&[[1.5], [3.], [5.]]));
+    let z = df.rolling(3).var().as_array().unwrap();
+    assert_eq!(z.slice(s![2..]), arr1(&[0.5, 2., 2.]));
+    let z = df.rolling(2).std().as_array().unwrap();
+    assert!(z[2].is_nan() && z[3].is_nan());
+    assert_eq!(z[4], 2f64.sqrt());
+
+    let a = arr2(&[[1e9 + 4.], [1e9 + 7.], [1e9 + 13.], [1e9 + 16.]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["a"]).unwrap();
+    let z = df.rolling(3).var().as_array().unwrap();
+    assert!((z[2] - 21.).abs() < 1e-6 && (z[3] - 21.).abs() < 1e-6);
+    let a = arr2(&[[1.], [INFINITY], [2.], [3.], [4.]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["a"]).unwrap();
+    let z = df.rolling(2).sum().as_array().unwrap();
+    assert_eq!(z.slice(s![1..]), arr1(&[INFINITY, INFINITY, 5., 7.]));
+    let z = df.rolling(2).var().as_array().unwrap();
+    assert!(z[1].is_nan() && z[2].is_nan());
+    assert_eq!(z.slice(s![3..]), arr1(&[0.5, 0.5]));
+}
+
+#[test]
+fn dataframe_expanding() {
+    let a = arr2(&[[3., 1.], [NAN, 5.], [1., 2.], [4., NAN]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["a", "b"]).unwrap();
+    let z = df.expanding(1).max().as_df().unwrap();
+    assert_eq!(z.columns, vec!["a", "b"]);
+    assert_eq!(z.data, arr2(&[[3., 1.], [3., 5.], [3., 5.], [4., 5.]]));
+    let z = df.expanding(2).sum().as_matrix().unwrap();
+    assert!(z[[0, 0]].is_nan() && z[[1, 0]].is_nan() && z[[0, 1]].is_nan());
+    assert_eq!(z.slice(s![2.., ..]), arr2(&[[4., 8.], [8., 8.]]));
+    let z = df.expanding(1).mean().as_matrix().unwrap();
+    assert_eq!(z.column(1).to_owned(), arr1(&[1., 3., 8. / 3., 8. / 3.]));
+}
+
+#[test]
+fn dataframe_ewm() {
+    let a = arr2(&[[1.], [3.], [NAN], [5.]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["a"]).unwrap();
+    let z = df.ewm(Decay::Alpha(0.5)).unwrap().mean().as_df().unwrap();
+    assert_eq!(z.columns, vec!["a"]);
+    assert_eq!(z.index, df.index);
+    assert!(z.data[[2, 0]].is_nan());
+    assert_eq!(z.data.column(0).to_owned().slice(s![..2]), arr1(&[1., 2.]));
+    assert_eq!(z.data[[3, 0]], 3.5);
+    let z = df.ewm(Decay::Span(3.)).unwrap().var().as_array().unwrap();
+    assert_eq!((z[0], z[1], z[3]), (0., 1., 0.5 * (1. + 0.5 * 9.)));
+    let z = df.ewm(Decay::Alpha(0.5)).unwrap().std().as_array().unwrap();
+    assert_eq!(z[1], 1.);
+    assert!(df.ewm(Decay::Alpha(1.)).is_ok());
+    assert!(df.ewm(Decay::Alpha(0.)).is_err());
+    assert!(df.ewm(Decay::Alpha(NAN)).is_err());
+    assert!(df.ewm(Decay::Span(0.)).is_err());
+}
+
+#[test]
+fn dataframe_agg_with() {
+    let a = arr2(&[[1., 5.], [4., 2.], [3., 9.]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["a", "b"]).unwrap();
+    let range = |v: ArrayView1<f64>| {
+        v.iter().cloned().fold(NAN, f64::max) - v.iter().cloned().fold(NAN, f64::min)
+    };
+    let z = df.agg(range, UtahAxis::Column).as_df().unwrap();
+    assert_eq!(z.columns, vec!["a", "b"]);
+    assert_eq!(z.data, arr2(&[[3., 7.]]));
+    let z = df.agg(|v| v[0] * 10., UtahAxis::Row).as_array().unwrap();
+    assert_eq!(z, arr1(&[10., 40., 30.]));
+}
+
+#[test]
+fn dataframe_groupby_reductions() {
+    let a = arr2(&[[1., 2., 10.], [2., 4., NAN], [1., 6., 30.], [2., 8., 40.]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["g", "x", "y"]).unwrap();
+    let g = df.groupby("g").unwrap();
+    let z = g.sum();
+    assert_eq!(z.index, vec!["1.0", "2.0"]);
+    assert_eq!(z.columns, vec!["x", "y"]);
+    assert_eq!(z.data, arr2(&[[8., 40.], [12., 40.]]));
+    assert_eq!(g.mean().data, arr2(&[[4., 20.], [6., 40.]]));
+    assert_eq!(g.count().data, arr2(&[[2., 2.], [2., 1.]]));
+    assert_eq!(g.max().data, arr2(&[[6., 30.], [8., 40.]]));
+    assert_eq!(g.min().data, arr2(&[[2., 10.], [4., 40.]]));
+    assert_eq!(g.agg_with(|v| v.len() as f64).data, arr2(&[[2., 2.], [2., 2.]]));
+    let a = arr2(&[[0., 1.], [NAN, 2.], [-0., 3.], [0., 4.], [NAN, 5.]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["g", "x"]).unwrap();
+    let g = df.groupby("g").unwrap();
+    assert_eq!(g.groups, vec![vec![0, 3], vec![1, 4], vec![2]]);
+    let s = |x: &str| InnerType::Str(x.to_string());
+    let long = "a".repeat(100);
+    let a = arr2(&[[s(&long)], [s(&long[1..])], [s(&long)]]);
+    let df: DataFrame<InnerType> = DataFrame::new(a).columns(&["g"]).unwrap();
+    assert_eq!(df.groupby("g").unwrap().groups, vec![vec![0, 2], vec![1]]);
+}
+
+#[test]
+fn dataframe_groupby_multi() {
+    let a = arr2(&[[1., 1., 10.], [2., 1., 20.], [1., 2., 30.], [1., 1., 40.], [2., 1., NAN]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["g", "h", "x"]).unwrap();
+    let g = df.groupby_multi(&["g", "h"]).unwrap();
+    assert_eq!(g.ngroups(), 3);
+    assert_eq!(g.groups, vec![vec![0, 3], vec![1, 4], vec![2]]);
+    let z = g.sum();
+    assert_eq!(z.columns, vec!["x"]);
+    assert_eq!(z.index, vec!["1.0|1.0", "2.0|1.0", "1.0|2.0"]);
+    assert_eq!(z.data, arr2(&[[50.], [20.], [30.]]));
+    assert_eq!(z.xs(0, "1.0").unwrap().index, vec!["1.0", "2.0"]);
+    assert!(df.groupby_multi(&["g", "y"]).is_err());
+    let a = arr2(&[[0., 1.], [NAN, 1.], [-0., 1.], [0., 1.], [NAN, 1.]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["g", "h"]).unwrap();
+    let g = df.groupby_multi(&["g", "h"]).unwrap();
+    assert_eq!(g.groups, df.groupby("g").unwrap().groups);
+    assert_eq!(g.ngroups(), 3);
+}
+
+#[test]
+fn dataframe_groupby_apply() {
+    let a = arr2(&[[1., 2.], [2., 4.], [1., 6.], [2., 10.]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["g", "x"]).unwrap();
+    let g = df.groupby("g").unwrap();
+    let z = g.apply(|sub| {
+            let x = sub.column("x").unwrap();
+            let mean = x.mean();
+            Series { data: x.data.mapv(|v| v - mean), ..x }
+        })
+        .unwrap();
+    assert_eq!(z.columns, vec!["x"]);
+    assert_eq!(z.index, vec!["1.0|0", "1.0|2", "2.0|1", "2.0|3"]);
+    assert_eq!(z.data, arr2(&[[-2.], [2.], [-3.], [3.]]));
+    let z = g.apply(|sub| sub.clone()).unwrap();
+    assert_eq!(z.columns, vec!["g", "x"]);
+    // Labels returned by the function are escaped like the group keys.
+    let z = g.apply(|sub| sub.clone().index(&vec!["a|b"; sub.index.len()]).unwrap()).unwrap();
+    assert_eq!(z.index[0], "1.0|a\\|b");
+    assert_eq!(split_levels(&z.index[0]), vec!["1.0", "a|b"]);
+    assert!(g.apply(|sub| if sub.index.len() > 0 && sub.index[0] == "0" {
+            sub.clone()
+        } else {
+            sub.remove(&["g"], UtahAxis::Column).as_df().unwrap()
+        })
+        .is_err());
+}
+
+#[test]
+fn dataframe_pivot_table() {
+    let a = arr2(&[[1., 10., 1.], [1., 20., 2.], [2., 10., 3.], [1., 10., 4.]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["day", "store", "sales"]).unwrap();
+    let z = df.pivot_table("day", "store", "sales", Agg::Sum).unwrap();
+    assert_eq!(z.index, vec!["1.0", "2.0"]);
+    assert_eq!(z.columns, vec!["10.0", "20.0"]);
+    assert_eq!(z.data.row(0).to_owned(), arr1(&[5., 2.]));
+    assert_eq!(z.data[[1, 0]], 3.);
+    assert!(z.data[[1, 1]].is_nan());
+    assert!(df.pivot_table("day", "store", "price", Agg::Sum).is_err());
+}
+
+#[test]
+fn dataframe_melt() {
+    let s = |x: &str| InnerType::Str(x.to_string());
+    let a = arr2(&[[s("x"), InnerType::Float(1.), InnerType::Float(2.)],
+                   [s("y"), InnerType::Float(3.), InnerType::Empty]]);
+    let df: DataFrame<InnerType> = DataFrame::new(a).columns(&["id", "a", "b"]).unwrap();
+    let z = df.melt(&["id"], &["a", "b"]).unwrap();
+    assert_eq!(z.columns, vec!["id", "variable", "value"]);
+    assert_eq!(z.index, vec!["0", "1", "2", "3"]);
+    assert_eq!(z.data,
+               arr2(&[[s("x"), s("a"), InnerType::Float(1.)],
+                      [s("y"), s("a"), InnerType::Float(3.)],
+                      [s("x"), s("b"), InnerType::Float(2.)],
+                      [s("y"), s("b"), InnerType::Empty]]));
+    assert!(df.melt(&["id"], &["c"]).is_err());
+}
+
+#[test]
+fn dataframe_crosstab() {
+    let a = arr2(&[[1., 0.], [1., 1.], [2., 1.], [1., 1.], [2., 1.]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["a", "b"]).unwrap();
+    let z = df.crosstab("a", "b", None).unwrap();
+    assert_eq!(z.index, vec!["1.0", "2.0"]);
+    assert_eq!(z.columns, vec!["0.0", "1.0"]);
+    assert_eq!(z.data, arr2(&[[1., 2.], [0., 2.]]));
+    let z = df.crosstab("a", "b", Some(UtahAxis::Row)).unwrap();
+    assert_eq!(z.data, arr2(&[[1. / 3., 2. / 3.], [0., 1.]]));
+    let z = df.crosstab("a", "b", Some(UtahAxis::Column)).unwrap();
+    assert_eq!(z.data, arr2(&[[1., 0.5], [0., 0.5]]));
+    let a = arr2(&[[1, 2], [1, 3], [1, 3]]);
+    let df: DataFrame<i32> = DataFrame::new(a).columns(&["a", "b"]).unwrap();
+    let z = df.crosstab("a", "b", Some(UtahAxis::Row)).unwrap();
+    assert_eq!(z.data, arr2(&[[1. / 3., 2. / 3.]]));
+}
+
+#[test]
+fn dataframe_resample() {
+    let a = arr2(&[[1.], [2.], [NAN], [4.], [5.]]);
+    let df: DataFrame<f64> = DataFrame::new(a)
+        .columns(&["p"])
+        .unwrap()
+        .index(&["2017-01-01T09:00:00",
+                 "2017-01-01 15:30:00",
+                 "2017-01-02T10:00:00",
+                 "2017-01-02T11:00:00",
+                 "2016-12-31"])
+        .unwrap();
+    let z = df.resample(frequency("1D").unwrap()).unwrap().sum();
+    assert_eq!(z.index, vec!["2016-12-31", "2017-01-01", "2017-01-02"]);
+    assert_eq!(z.data, arr2(&[[5.], [3.], [4.]]));
+    let z = df.resample(frequency("12H").unwrap()).unwrap().last();
+    assert_eq!(z.index,
+               vec!["2016-12-31", "2017-01-01", "2017-01-01T12:00:00", "2017-01-02"]);
+    let z = df.resample(Duration::from_secs(86400)).unwrap().ohlc();
+    assert_eq!(z.columns, vec!["p|open", "p|high", "p|low", "p|close"]);
+    assert_eq!(z.data.row(1).to_owned(), arr1(&[1., 2., 1., 2.]));
+    let piped = df.clone().columns(&["p|q"]).unwrap();
+    let z = piped.resample(Duration::from_secs(86400)).unwrap().ohlc();
+    assert_eq!(split_levels(&z.columns[0]), vec!["p|q", "open"]);
+    assert!(df.resample(frequency("1Q").unwrap_or(Duration::from_secs(0))).is_err());
+    let bad: DataFrame<f64> = DataFrame::new(arr2(&[[1.]])).columns(&["p"]).unwrap();
+    assert!(bad.resample(Duration::from_secs(60)).is_err());
+}
+
+#[test]
+fn dataframe_qcut() {
+    let a = arr2(&[[1.], [7.], [3.], [NAN], [5.], [9.]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["x"]).unwrap();
+    let z = df.qcut("x", 2, &["low", "high"]).unwrap();
+    let s = |x: &str| InnerType::Str(x.to_string());
+    assert_eq!(z.data.column(0).to_owned(),
+               arr1(&[s("low"), s("high"), s("low"), InnerType::Empty, s("low"), s("high")]));
+    let z = df.qcut("x", 4, &[]).unwrap();
+    assert_eq!(z.data[[0, 0]], s("(1.0, 3.0]"));
+    let ties: DataFrame<f64> = DataFrame::new(arr2(&[[1.], [1.], [1.], [2.]]))
+        .columns(&["x"])
+        .unwrap();
+    assert!(ties.qcut("x", 4, &[]).is_err());
+}
+
+#[test]
+fn dataframe_hist() {
+    let a = arr2(&[[0., 5.], [1., NAN], [4., 8.], [2., 3.]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["a", "b"]).unwrap();
+    let z = df.hist("a", 2).unwrap();
+    assert_eq!(z.columns, vec!["lo", "hi", "count"]);
+    assert_eq!(z.data, arr2(&[[0., 2., 3.], [2., 4., 1.]]));
+    let z = df.hist_all(4);
+    assert_eq!(z.columns, vec!["lo", "hi", "a", "b"]);
+    assert_eq!(z.data.column(0).to_owned(), arr1(&[0., 2., 4., 6.]));
+    assert_eq!(z.data.column(2).to_owned(), arr1(&[3., 1., 0., 0.]));
+    assert_eq!(z.data.column(3).to_owned(), arr1(&[0., 1., 1., 1.]));
+    assert!(df.hist("c", 2).is_err());
+}
+
+#[test]
+fn dataframe_standardize() {
+    let a = arr2(&[[1., 2.], [2., 2.], [3., 2.], [NAN, 2.]]);
+    let mut df: DataFrame<f64> = DataFrame::new(a).columns(&["a", "b"]).unwrap();
+    {
+        let z = df.standardize(UtahAxis::Column).as_df().unwrap();
+        assert_eq!(z.columns, vec!["a", "b"]);
+        assert_eq!(z.data.column(0).iter().take(3).cloned().collect::<Vec<_>>(),
+                   vec![-1., 0., 1.]);
+        assert_eq!(z.data.column(1).to_owned(), arr1(&[0., 0., 0., 0.]));
+    }
+    assert!(df.data[[3, 0]].is_nan());
+    assert_eq!(df.data[[0, 0]], -1.);
+    let mut df: DataFrame<f64> = DataFrame::new(arr2(&[[1., 5., 3.]]))
+        .columns(&["a", "b", "c"])
+        .unwrap();
+    let z = df.standardize(UtahAxis::Row).as_df().unwrap();
+    assert_eq!(z.data.row(0).to_owned(), arr1(&[-1., 1., 0.]));
+}
+
+#[test]
+fn dataframe_minmax_scale() {
+    let a = arr2(&[[1., 2.], [3., 2.], [5., 2.], [NAN, 2.]]);
+    let mut df: DataFrame<f64> = DataFrame::new(a).columns(&["a", "b"]).unwrap();
+    {
+        let z = df.minmax_scale((0., 1.), UtahAxis::Column).as_df().unwrap();
+        assert_eq!(z.data.column(0).iter().take(3).cloned().collect::<Vec<_>>(),
+                   vec![0., 0.5, 1.]);
+        assert_eq!(z.data.column(1).to_owned(), arr1(&[0., 0., 0., 0.]));
+    }
+    assert!(df.data[[3, 0]].is_nan());
+    let z = df.minmax_scale((-1., 1.), UtahAxis::Row).as_df().unwrap();
+    assert_eq!(z.data.row(0).to_owned(), arr1(&[-1., -1.]));
+    assert_eq!(z.data.row(1).to_owned(), arr1(&[1., -1.]));
+
+    let mut df: DataFrame<i32> = DataFrame::new(arr2(&[[2], [4], [6]]));
+    let z = df.minmax_scale((10, 110), UtahAxis::Column).as_df().unwrap();
+    assert_eq!(z.data, arr2(&[[10], [60], [110]]));
+}
+
+#[test]
+fn dataframe_join_on() {
+    let a = arr2(&[[1., 10.], [2., 20.], [3., 30.]]);
+    let left: DataFrame<f64> = DataFrame::new(a).columns(&["user_id", "x"]).unwrap();
+    let b = arr2(&[[3., 0.3], [1., 0.1]]);
+    let right: DataFrame<f64> = DataFrame::new(b).columns(&["user_id", "y"]).unwrap();
+    let z = left.inner_join_on(&right, "user_id").unwrap().as_df().unwrap();
+    assert_eq!(z.columns, vec!["user_id", "x", "user_id", "y"]);
+    assert_eq!(z.index, vec!["0", "2"]);
+    assert_eq!(z.data, arr2(&[[1., 10., 1., 0.1], [3., 30., 3., 0.3]]));
+    let z = left.outer_join_on(&right, "user_id").unwrap().as_df().unwrap();
+    assert_eq!(z.index, vec!["0", "1", "2"]);
+    assert!(z.data[[1, 3]].is_nan());
+    assert!(left.inner_join_on(&right, "x").is_err());
+    let b = arr2(&[[1., 100.], [4., 400.], [1., 200.]]);
+    let right: DataFrame<f64> = DataFrame::new(b).columns(&["user_id", "y"]).unwrap();
+    let z = left.inner_join_on(&right, "user_id").unwrap().as_df().unwrap();
+    assert_eq!(z.index, vec!["0", "0"]);
+    assert_eq!(z.data.column(3).to_owned(), arr1(&[100., 200.]));
+    let z = left.outer_join_on(&right, "user_id").unwrap().as_df().unwrap();
+    assert_eq!(z.index, vec!["0", "0", "1", "2"]);
+    assert_eq!(z.data.column(3).slice(s![..2]).to_owned(), arr1(&[100., 200.]));
+}
+
+#[test]
+fn dataframe_join_on_keys() {
+    let s = |x: &str| InnerType::Str(x.to_string());
+    let a = arr2(&[[s("d1"), s("A"), InnerType::Float(1.)],
+                   [s("d1"), s("B"), InnerType::Float(2.)],
+                   [s("d2"), s("A"), InnerType::Float(3.)],
+                   [InnerType::Empty, s("A"), InnerType::Float(4.)]]);
+    let left: DataFrame<InnerType> = DataFrame::new(a).columns(&["date", "ticker", "px"]).unwrap();
+    let b = arr2(&[[s("A"), s("d2"), InnerType::Int32(30)],
+                   [s("B"), s("d1"), InnerType::Int32(20)],
+                   [s("A"), InnerType::Empty, InnerType::Int32(40)],
+                   [s("A"), s("d1|A"), InnerType::Int32(50)]]);
+    let right: DataFrame<InnerType> =
+        DataFrame::new(b).columns(&["ticker", "date", "vol"]).unwrap();
+    let z = left.inner_join_on_keys(&right, &["date", "ticker"]).unwrap().as_df().unwrap();
+    assert_eq!(z.index, vec!["1", "2"]);
+    assert_eq!(z.data.column(5).to_owned(),
+               arr1(&[InnerType::Int32(20), InnerType::Int32(30)]));
+    let z = left.outer_join_on_keys(&right, &["date", "ticker"]).unwrap().as_df().unwrap();
+    assert_eq!(z.index, vec!["0", "1", "2", "3"]);
+    assert_eq!(z.data.column(5).to_owned(),
+               arr1(&[InnerType::Empty,
+                      InnerType::Int32(20),
+                      InnerType::Int32(30),
+                      InnerType::Empty]));
+    let c = arr2(&[[InnerType::Int32(1), InnerType::Float(1.)]]);
+    let ints: DataFrame<InnerType> = DataFrame::new(c).columns(&["k", "v"]).unwrap();
+    let c = arr2(&[[InnerType::Float(1.), InnerType::Float(2.)]]);
+    let floats: DataFrame<InnerType> = DataFrame::new(c).columns(&["k", "w"]).unwrap();
+    assert_eq!(ints.inner_join_on_keys(&floats, &["k"]).unwrap().count(), 0);
+    assert!(left.inner_join_on_keys(&right, &["date", "px"]).is_err());
+}
+
+#[test]
+fn dataframe_anti_join() {
+    let a = arr2(&[[1.], [2.], [3.]]);
+    let left: DataFrame<f64> = DataFrame::new(a).index(&["x", "y", "z"]).unwrap();
+    let right: DataFrame<f64> = DataFrame::new(arr2(&[[5., 6.], [7., 8.]]))
+        .index(&["z", "w"])
+        .unwrap();
+    let z = left.anti_join(&right);
+    assert_eq!(z.index, vec!["x", "y"]);
+    assert_eq!(z.data, arr2(&[[1.], [2.]]));
+    assert_eq!(left.anti_join(&left).index.len(), 0);
+}
+
+#[test]
+fn dataframe_semi_join() {
+    let a = arr2(&[[1.], [2.], [3.]]);
+    let left: DataFrame<f64> = DataFrame::new(a).index(&["x", "y", "z"]).unwrap();
+    let right: DataFrame<f64> = DataFrame::new(arr2(&[[5., 6.], [7., 8.], [9., 0.]]))
+        .index(&["z", "x", "z"])
+        .unwrap();
+    let z = left.semi_join(&right);
+    assert_eq!(z.columns, left.columns);
+    assert_eq!(z.index, vec!["x", "z"]);
+    assert_eq!(z.data, arr2(&[[1.], [3.]]));
+}
+
+#[test]
+fn join_builds_smaller_side() {
+    let a = arr2(&[[1.], [2.]]);
+    let small: DataFrame<f64> = DataFrame::new(a).index(&["b", "a"]).unwrap();
+    let b = arr2(&[[10.], [20.], [30.], [40.]]);
+    let large: DataFrame<f64> = DataFrame::new(b).index(&["a", "c", "a", "d"]).unwrap();
+    let z = small.inner_left_join(&large).as_df().unwrap();
+    assert_eq!(z.index, vec!["a", "a"]);
+    assert_eq!(z.data, arr2(&[[2., 10.], [2., 30.]]));
+    let z = small.outer_left_join(&large).as_df().unwrap();
+    assert_eq!(z.index, vec!["b", "a", "a"]);
+    assert!(z.data[[0, 1]].is_nan());
+    assert_eq!(z.data.column(1).slice(s![1..]).to_owned(), arr1(&[10., 30.]));
+    let z = large.inner_left_join(&small).as_df().unwrap();
+    assert_eq!(z.index, vec!["a", "a"]);
+    assert_eq!(z.data, arr2(&[[10., 2.], [30., 2.]]));
+}
+
+#[test]
+fn join_merges_sorted_indices() {
+    let a = arr2(&[[1.], [2.], [3.], [4.]]);
+    let left: DataFrame<f64> = DataFrame::new(a).index(&["2", "9", "10", "10"]).unwrap();
+    let left = left.sort_index(true);
+    let b = arr2(&[[20.], [90.], [91.], [100.]]);
+    let right: DataFrame<f64> = DataFrame::new(b).index(&["1", "9", "9.0", "10"]).unwrap();
+    let right = right.sort_index(true);
+    let z = left.inner_left_join(&right).as_df().unwrap();
+    assert_eq!(z.index, vec!["9", "10", "10"]);
+    assert_eq!(z.data, arr2(&[[2., 90.], [3., 100.], [4., 100.]]));
+    let b = arr2(&[[10.], [20.]]);
+    let right: DataFrame<f64> = DataFrame::new(b).index(&["1a", "2"]).unwrap();
+    let z = left.outer_left_join(&right.sort_index(true)).as_df().unwrap();
+    assert_eq!(z.data[[0, 1]], 20.);
+    assert!(z.data.column(1).iter().skip(1).all(|x| x.is_nan()));
+}
+
+#[test]
+#[allow(deprecated)]
+fn join_concat_suffixes() {
+    let a = arr2(&[[1., 2.]]);
+    let left: DataFrame<f64> = DataFrame::new(a).columns(&["k", "v"]).unwrap();
+    let b = arr2(&[[1., 3., 4.]]);
+    let right: DataFrame<f64> = DataFrame::new(b).columns(&["k", "v", "w"]).unwrap();
+    let z = left.inner_join_on(&right, "k").unwrap().suffixes("_left", "_right").as_df().unwrap();
+    assert_eq!(z.columns, vec!["k_left", "v_left", "k_right", "v_right", "w"]);
+    let z = left.outer_left_join(&right).suffixes("_l", "_r").as_df().unwrap();
+    assert_eq!(z.columns, vec!["k_l", "v_l", "k_r", "v_r", "w"]);
+    let z = left.concat(&right, UtahAxis::Row).suffixes("_l", "_r").as_df().unwrap();
+    assert_eq!(z.columns, vec!["k_l", "v_l", "k_r", "v_r", "w"]);
+    assert_eq!(z.data, arr2(&[[1., 2., 1., 3., 4.]]));
+    let z = left.concat(&right, UtahAxis::Row).as_df().unwrap();
+    assert_eq!(z.columns, vec!["k", "v", "k", "v", "w"]);
+}
+
+#[test]
+fn join_indicator() {
+    let a = arr2(&[[1.], [2.]]);
+    let left: DataFrame<InnerType> = DataFrame::new(a).index(&["x", "y"]).unwrap();
+    let b = arr2(&[[3.]]);
+    let right: DataFrame<InnerType> =
+        DataFrame::new(b).index(&["y"]).unwrap().columns(&["b"]).unwrap();
+    let s = |x: &str| InnerType::Str(x.to_string());
+    let z = left.outer_left_join(&right).as_df_with_indicator("_merge").unwrap();
+    assert_eq!(z.columns, vec!["0", "b", "_merge"]);
+    assert_eq!(z.data.column(2).to_owned(), arr1(&[s("left_only"), s("both")]));
+    let z = right.outer_right_join(&left).as_df_with_indicator("_merge").unwrap();
+    assert_eq!(z.data.column(2).to_owned(), arr1(&[s("right_only"), s("both")]));
+    let z = left.inner_left_join(&right).as_df_with_indicator("_merge").unwrap();
+    assert_eq!(z.data, arr2(&[[InnerType::Float(2.), InnerType::Float(3.), s("both")]]));
+}
+
+#[test]
+fn dataframe_join_columns() {
+    let a = arr2(&[[1.], [2.]]);
+    let left: DataFrame<f64> =
+        DataFrame::new(a).columns(&["a"]).unwrap().index(&["x", "y"]).unwrap();
+    let b = arr2(&[[3., 4.], [5., 6.]]);
+    let right: DataFrame<f64> =
+        DataFrame::new(b).columns(&["b", "c"]).unwrap().index(&["z", "x"]).unwrap();
+    let z = left.join_columns(&right);
+    assert_eq!(z.columns, vec!["a", "b", "c"]);
+    assert_eq!(z.index, vec!["x", "y", "z"]);
+    assert_eq!(z.data.row(0).to_owned(), arr1(&[1., 5., 6.]));
+    assert!(z.data[[1, 1]].is_nan() && z.data[[1, 2]].is_nan());
+    assert!(z.data[[2, 0]].is_nan());
+    assert_eq!(z.data[[2, 2]], 4.);
+    let b = arr2(&[[7., 8.], [9., 10.]]);
+    let right: DataFrame<f64> =
+        DataFrame::new(b).columns(&["b", "c"]).unwrap().index(&["y", "y"]).unwrap();
+    let z = left.join_columns(&right);
+    assert_eq!(z.index, vec!["x", "y", "y"]);
+    assert_eq!(z.data.column(1).slice(s![1..]).to_owned(), arr1(&[7., 9.]));
+}
+
+#[test]
+fn dataframe_concat_rows() {
+    let a = arr2(&[[1., 2.]]);
+    let top: DataFrame<f64> = DataFrame::new(a).columns(&["a", "b"]).unwrap();
+    let b = arr2(&[[3., 4.], [5., 6.]]);
+    let bottom: DataFrame<f64> = DataFrame::new(b).columns(&["c", "a"]).unwrap();
+    let z = top.concat_rows(&bottom, ConcatJoin::Outer, None).unwrap();
+    assert_eq!(z.columns, vec!["a", "b", "c"]);
+    assert_eq!(z.index, vec!["0", "0", "1"]);
+    assert_eq!(z.data.column(0).to_owned(), arr1(&[1., 4., 6.]));
+    assert!(z.data[[1, 1]].is_nan() && z.data[[0, 2]].is_nan());
+    let z = top.concat_rows(&bottom, ConcatJoin::Inner, Some(("top", "bottom"))).unwrap();
+    assert_eq!(z.columns, vec!["a"]);
+    assert_eq!(z.index, vec!["top|0", "bottom|0", "bottom|1"]);
+    assert_eq!(MultiIndex::from_labels(&z.index[..]).level(0).unwrap(),
+               vec!["top", "bottom", "bottom"]);
+    let c: DataFrame<f64> = DataFrame::new(arr2(&[[1., 2., 3.]]))
+        .columns(&["c", "a", "c"])
+        .unwrap();
+    assert!(top.concat_rows(&c, ConcatJoin::Outer, None).is_err());
+    assert!(c.concat_rows(&top, ConcatJoin::Inner, None).is_err());
+}
+
+#[test]
+fn dataframe_push_extend_rows() {
+    let a = arr2(&[[1., 2.]]);
+    let mut df: DataFrame<f64> = DataFrame::new(a).columns(&["a", "b"]).unwrap();
+    for i in 1..100 {
+        df.push_row(&i.to_string(), &[i as f64, 0.]).unwrap();
+    }
+    assert_eq!(df.data.dim(), (100, 2));
+    assert_eq!(df.index[99], "99");
+    assert_eq!(df.data.row(99).to_owned(), arr1(&[99., 0.]));
+    assert!(df.push_row("x", &[1.]).is_err());
+    let mut t: DataFrame<f64> = DataFrame::new(arr2(&[[1., 2.], [3., 4.]]).reversed_axes())
+        .columns(&["a", "b"])
+        .unwrap();
+    let head: DataFrame<f64> = DataFrame::new(arr2(&[[1., 2.], [1., 0.]]))
+        .columns(&["a", "b"])
+        .unwrap();
+    t.extend_rows(&head).unwrap();
+    assert_eq!(t.data, arr2(&[[1., 3.], [2., 4.], [1., 2.], [1., 0.]]));
+    assert_eq!(t.index, vec!["0", "1", "0", "1"]);
+    let other: DataFrame<f64> = DataFrame::new(arr2(&[[1., 2.]])).columns(&["b", "a"]).unwrap();
+    assert!(t.extend_rows(&other).is_err());
+}
+
+#[test]
+fn dataframe_impute_fill() {
+    let a = arr2(&[[NAN, 1.], [2., NAN], [NAN, NAN], [4., 5.], [NAN, NAN]]);
+    let mut df: DataFrame<f64> = DataFrame::new(a).columns(&["a", "b"]).unwrap();
+    {
+        let z = df.clone().impute(ImputeStrategy::BackFill, UtahAxis::Column).as_df().unwrap();
+        assert_eq!(z.data.column(0).iter().take(4).cloned().collect::<Vec<_>>(),
+                   vec![2., 2., 4., 4.]);
+        assert!(z.data[[4, 0]].is_nan());
+    }
+    let z = df.impute(ImputeStrategy::ForwardFill, UtahAxis::Column).as_df().unwrap();
+    assert!(z.data[[0, 0]].is_nan());
+    assert_eq!(z.data.column(0).iter().skip(1).cloned().collect::<Vec<_>>(),
+               vec![2., 2., 4., 4.]);
+    assert_eq!(z.data.column(1).to_owned(), arr1(&[1., 1., 1., 5., 5.]));
+}
+
+#[test]
+fn dataframe_impute_interpolate() {
+    let a = arr2(&[[NAN, 1., NAN, NAN, 7., NAN], [0., NAN, NAN, NAN, NAN, 10.]]);
+    let mut df: DataFrame<f64> =
+        DataFrame::new(a).columns(&["a", "b", "c", "d", "e", "f"]).unwrap();
+    {
+        let z = df.clone()
+            .impute(ImputeStrategy::Interpolate(InterpolateEdge::Empty), UtahAxis::Row)
+            .as_df()
+            .unwrap();
+        assert!(z.data[[0, 0]].is_nan() && z.data[[0, 5]].is_nan());
+        assert_eq!(z.data.row(0).iter().skip(1).take(4).cloned().collect::<Vec<_>>(),
+                   vec![1., 3., 5., 7.]);
+        assert_eq!(z.data.row(1).to_owned(), arr1(&[0., 2., 4., 6., 8., 10.]));
+    }
+    let z = df.impute(ImputeStrategy::Interpolate(InterpolateEdge::Nearest), UtahAxis::Row)
+        .as_df()
+        .unwrap();
+    assert_eq!(z.data.row(0).to_owned(), arr1(&[1., 1., 3., 5., 7., 7.]));
+}
+
+#[test]
+fn dataframe_impute_constant() {
+    let a = arr2(&[[1., NAN], [NAN, 4.]]);
+    let mut df: DataFrame<f64> = DataFrame::new(a).columns(&["a", "b"]).unwrap();
+    let z = df.impute(ImputeStrategy::Constant(0.), UtahAxis::Column).as_df().unwrap();
+    assert_eq!(z.data, arr2(&[[1., 0.], [0., 4.]]));
+    let s = |x: &str| InnerType::Str(x.to_string());
+    let b = arr2(&[[s("x")], [InnerType::Empty]]);
+    let mut df: DataFrame<InnerType> = DataFrame::new(b).columns(&["c"]).unwrap();
+    let z = df.impute(ImputeStrategy::Constant(s("unknown")), UtahAxis::Row).as_df().unwrap();
+    assert_eq!(z.data, arr2(&[[s("x")], [s("unknown")]]));
+}
+
+#[test]
+fn dataframe_dropna() {
+    let a = arr2(&[[1., NAN, 3.], [NAN, NAN, NAN], [4., NAN, 6.], [7., 8., 9.]]);
+    let df: DataFrame<f64> = DataFrame::new(a).columns(&["a", "b", "c"]).unwrap();
+    let z = df.dropna(UtahAxis::Row, DropHow::Any, None);
+    assert_eq!(z.index, vec!["3"]);
+    assert_eq!(z.data, arr2(&[[7., 8., 9.]]));
+    let z = df.dropna(UtahAxis::Row, DropHow::All, None);
+    assert_eq!(z.index, vec!["0", "2", "3"]);
+    let z = df.dropna(UtahAxis::Row, DropHow::Any, Some(2));
+    assert_eq!(z.index, vec!["0", "2", "3"]);
+    let z = df.dropna(UtahAxis::Row, DropHow::Any, Some(3));
+    assert_eq!(z.index, vec!["3"]);
+    let z = df.dropna(UtahAxis::Column, DropHow::Any, Some(2));
+    assert_eq!(z.columns, vec!["a", "c"]);
+    assert_eq!(z.data.column(1).iter().filter(|x| !x.is_nan()).count(), 3);
+    assert_eq!(df.dropna(UtahAxis::Column, DropHow::Any, None).columns.len(), 0);
+}
@@ -0,0 +1,97 @@
+//! Utah dataframe transactions
+//!
+//! A `Transaction` groups several in-place changes to a dataframe so that they can be undone
+//! together. Columns are copied the first time they are changed, so a transaction that only
+//! touches a few columns only pays for those.
+
+use dataframe::DataFrame;
+use ndarray::ArrayViewMut1;
+use util::error::*;
+use util::helpers::position;
+use util::traits::UtahNum;
+use util::types::*;
+
+pub struct Transaction<'a, T: 'a>
+    where T: UtahNum
+{
+    df: &'a mut DataFrame<T>,
+    columns: Vec<(usize, Row<T>)>,
+    frame: Option<DataFrame<T>>,
+    done: bool,
+}
+
+impl<'a, T> Transaction<'a, T>
+    where T: UtahNum
+{
+    pub fn new(df: &'a mut DataFrame<T>) -> Transaction<'a, T> {
+        Transaction {
+            df: df,
+            columns: Vec::new(),
+            frame: None,
+            done: false,
+        }
+    }
+
+    /// The dataframe, with the changes made so far.
+    pub fn df(&self) -> &DataFrame<T> {
+        self.df
+    }
+
+    /// Mutable access to a single column. Its values are saved before the first change.
+    pub fn column_mut<'c, U: ?Sized>(&'c mut self, name: &U) -> Result<ArrayViewMut1<'c, T>>
+        where for<'b> String: From<&'b U>
+    {
+        let name: String = name.into();
+        let i = position(&self.df.columns[..], &name)?;
+        if self.frame.is_none() && !self.columns.iter().any(|&(j, _)| j == i) {
+            self.columns.push((i, self.df.data.column(i).to_owned()));
+        }
+        Ok(self.df.data.column_mut(i))
+    }
+
+    /// Mutable access to the whole dataframe, for changes that can touch any cell or its
+    /// shape. The whole dataframe is saved before the first change.
+    pub fn frame_mut(&mut self) -> &mut DataFrame<T> {
+        if self.frame.is_none() {
+            let mut saved = self.df.clone();
+            for &(i, ref values) in &self.columns {
+                saved.data.column_mut(i).assign(values);
+            }
+            self.frame = Some(saved);
+            self.columns.clear();
+        }
+        self.df
+    }
+
+    /// Keep the changes.
+    pub fn commit(mut self) {
+        self.done = true;
+    }
+
+    /// Undo the changes.
+    pub fn rollback(mut self) {
+        self.restore();
+        self.done = true;
+    }
+
+    fn restore(&mut self) {
+        if let Some(saved) = self.frame.take() {
+            *self.df = saved;
+        }
+        for (i, values) in self.columns.drain(..) {
+            self.df.data.column_mut(i).assign(&values);
+        }
+    }
+}
+
+/// A transaction dropped without being committed is rolled back, so that returning early on an
+/// error leaves the dataframe untouched.
+impl<'a, T> Drop for Transaction<'a, T>
+    where T: UtahNum
+{
+    fn drop(&mut self) {
+        if !self.done {
+            self.restore();
+        }
+    }
+}
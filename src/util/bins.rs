@@ -0,0 +1,96 @@
+//! Utah binning functions.
+
+use util::error::*;
+use util::traits::UtahNum;
+use util::helpers::count;
+
+/// Check that bin edges are strictly increasing and that there is one label per bin, if labels
+/// are given.
+pub fn check_edges<T>(edges: &[T], labels: usize) -> Result<()>
+    where T: UtahNum + PartialOrd
+{
+    if edges.len() < 2 {
+        return Err(ErrorKind::InvalidBins("at least two edges are required".into()).into());
+    }
+    if edges.iter().any(|x| x.is_empty()) || edges.windows(2).any(|w| !(w[0] < w[1])) {
+        return Err(ErrorKind::InvalidBins("edges must be strictly increasing".into()).into());
+    }
+    if labels != 0 && labels != edges.len() - 1 {
+        return Err(ErrorKind::InvalidBins(format!("expected {} labels, got {}",
+                                                  edges.len() - 1,
+                                                  labels))
+            .into());
+    }
+    Ok(())
+}
+
+/// Default label of each bin, in interval notation.
+pub fn default_labels<T>(edges: &[T]) -> Vec<String>
+    where T: UtahNum
+{
+    edges.windows(2).map(|w| format!("({:?}, {:?}]", w[0], w[1])).collect()
+}
+
+/// Find the bin containing `value`. Bins are closed on the right, `(e[i], e[i + 1]]`, except for
+/// the first bin which also includes its lowest edge. Empty values and values outside the edges
+/// have no bin.
+pub fn bin_of<T>(value: &T, edges: &[T]) -> Option<usize>
+    where T: UtahNum + PartialOrd
+{
+    if value.is_empty() || *value < edges[0] || *value > edges[edges.len() - 1] {
+        return None;
+    }
+    let (mut lo, mut hi) = (0, edges.len() - 1);
+    while hi - lo > 1 {
+        let mid = (lo + hi) / 2;
+        if *value <= edges[mid] {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
+    }
+    Some(lo)
+}
+
+/// `bins + 1` equally spaced edges spanning the non-empty values, or `None` if there are none.
+/// If all the values are equal, the edges span one unit above them.
+pub fn even_edges<'a, I, T>(values: I, bins: usize) -> Option<Vec<T>>
+    where I: Iterator<Item = &'a T>,
+          T: UtahNum + PartialOrd + 'a
+{
+    let mut range: Option<(T, T)> = None;
+    for x in values.filter(|x| !x.is_empty()) {
+        range = Some(match range {
+            None => (x.clone(), x.clone()),
+            Some((lo, hi)) => {
+                (if *x < lo { x.clone() } else { lo }, if *x > hi { x.clone() } else { hi })
+            }
+        });
+    }
+    let (lo, hi) = match range {
+        Some(r) => r,
+        None => return None,
+    };
+    let hi = if hi == lo { lo.clone() + T::one() } else { hi };
+    let bins = bins.max(1);
+    let width = hi.clone() - lo.clone();
+    let mut edges: Vec<T> = (0..bins)
+        .map(|k| lo.clone() + width.clone() * count(k) / count(bins))
+        .collect();
+    edges.push(hi);
+    Some(edges)
+}
+
+/// The number of values in each bin.
+pub fn bin_counts<'a, I, T>(values: I, edges: &[T]) -> Vec<usize>
+    where I: Iterator<Item = &'a T>,
+          T: UtahNum + PartialOrd + 'a
+{
+    let mut counts = vec![0; edges.len() - 1];
+    for x in values {
+        if let Some(b) = bin_of(x, edges) {
+            counts[b] += 1;
+        }
+    }
+    counts
+}
@@ -0,0 +1,57 @@
+//! Utah block statistics.
+//!
+//! A `BlockIndex` records the smallest and largest value of a column within each block of
+//! consecutive rows. Range filters consult it to skip blocks that cannot hold a match, which pays
+//! off on sorted or time-ordered columns sliced repeatedly.
+
+use util::traits::UtahNum;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BlockIndex<T>
+    where T: UtahNum
+{
+    pub column: String,
+    pub block_size: usize,
+    pub nrows: usize,
+    /// The smallest and largest non-empty value of each block, or `None` for an all-empty block.
+    pub bounds: Vec<Option<(T, T)>>,
+}
+
+impl<T> BlockIndex<T>
+    where T: UtahNum + PartialOrd
+{
+    /// Compute the statistics of `values` in blocks of `block_size` rows.
+    pub fn new(column: String, values: &[T], block_size: usize) -> BlockIndex<T> {
+        let block_size = block_size.max(1);
+        let bounds = values.chunks(block_size)
+            .map(|block| {
+                block.iter().filter(|x| !x.is_empty()).fold(None, |acc, x| match acc {
+                    None => Some((x.clone(), x.clone())),
+                    Some((lo, hi)) => {
+                        Some((if *x < lo { x.clone() } else { lo },
+                              if *x > hi { x.clone() } else { hi }))
+                    }
+                })
+            })
+            .collect();
+        BlockIndex {
+            column: column,
+            block_size: block_size,
+            nrows: values.len(),
+            bounds: bounds,
+        }
+    }
+
+    /// The row ranges of the blocks that may hold values within `[lo, hi]`.
+    pub fn candidates(&self, lo: &T, hi: &T) -> Vec<(usize, usize)> {
+        self.bounds
+            .iter()
+            .enumerate()
+            .filter(|&(_, b)| match *b {
+                Some((ref min, ref max)) => !(max < lo || min > hi),
+                None => false,
+            })
+            .map(|(i, _)| (i * self.block_size, ((i + 1) * self.block_size).min(self.nrows)))
+            .collect()
+    }
+}
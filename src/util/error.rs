@@ -42,6 +42,78 @@ error_chain! {
             description("Parsing Error.")
             display("Read failed. Parsing Error. {}", t)
         }
+        InvalidBins(t: String) {
+            description("invalid bins")
+            display("invalid bins: {}", t)
+        }
+        ConstraintViolation(column: String, labels: Vec<String>) {
+            description("constraint violation")
+            display("column '{}' violates the constraint at {:?}", column, labels)
+        }
+        MisalignedLabels(t: String) {
+            description("misaligned labels")
+            display("misaligned labels: {}", t)
+        }
+        StaleIndex(t: String) {
+            description("stale block index")
+            display("block index does not match the dataframe: {}", t)
+        }
+        InvalidExpression(t: String) {
+            description("invalid expression")
+            display("invalid expression: {}", t)
+        }
+        InvalidLevel(t: String) {
+            description("invalid index level")
+            display("invalid index level: {}", t)
+        }
+        InvalidCast(value: String, dtype: String) {
+            description("invalid cast")
+            display("cannot cast {} to {}", value, dtype)
+        }
+        PrivacyBudgetExceeded(parameter: String, requested: String, remaining: String) {
+            description("privacy budget exceeded")
+            display("privacy budget exceeded: requested {} {}, remaining {}", parameter, requested, remaining)
+        }
+        InvalidPrivacyParameter(parameter: String, value: String) {
+            description("invalid privacy parameter")
+            display("invalid privacy parameter: {} = {}", parameter, value)
+        }
+        NoMatchingFiles(pattern: String) {
+            description("no matching files")
+            display("no files match '{}'", pattern)
+        }
+        InvalidChangeOp(t: String) {
+            description("invalid change operation")
+            display("invalid change operation: {}", t)
+        }
+        DTypeConflict(column: String, left: String, right: String) {
+            description("dtype conflict")
+            display("column '{}' holds {} values on one side and {} on the other", column, left, right)
+        }
+        NonPositiveValue(t: String) {
+            description("non-positive value")
+            display("expected positive values, found {}", t)
+        }
+        InvalidTimestamp(t: String) {
+            description("invalid timestamp")
+            display("invalid timestamp or frequency: '{}'", t)
+        }
+        InvalidSampleSize(requested: String, available: String) {
+            description("invalid sample size")
+            display("cannot sample {} rows without replacement from {} rows", requested, available)
+        }
+        PoisonedLock(t: String) {
+            description("poisoned lock")
+            display("a thread panicked while writing column '{}'", t)
+        }
+        InvalidDecay(t: String) {
+            description("invalid decay")
+            display("invalid decay {}: the smoothing factor must lie in (0, 1]", t)
+        }
+        DuplicateColumn(t: String) {
+            description("duplicate column")
+            display("duplicate column name: '{}'", t)
+        }
     }
 
 
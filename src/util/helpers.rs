@@ -0,0 +1,279 @@
+//! Utah helper functions shared by the dataframe operations.
+
+use util::error::*;
+use util::traits::UtahNum;
+use util::types::*;
+use ndarray::{Array, Axis};
+use rand::Rng;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet, VecDeque};
+use dataframe::DataFrame;
+use util::keys::ReprKey;
+
+/// Find the position of a label in a list of labels.
+pub fn position(labels: &[String], name: &str) -> Result<usize> {
+    match labels.iter().position(|x| x == name) {
+        Some(i) => Ok(i),
+        None => Err(ErrorKind::InvalidColumnName(name.to_string()).into()),
+    }
+}
+
+/// Express a count as a `UtahNum`.
+pub fn count<T>(n: usize) -> T
+    where T: UtahNum
+{
+    T::from_count(n)
+}
+
+/// Positions of the values in each group of equal values, in order of first appearance.
+/// Values are equal when they are the same key (see `util::keys`), so empty values form a single
+/// group.
+pub fn group_positions<T>(values: &[T]) -> Vec<(T, Vec<usize>)>
+    where T: UtahNum
+{
+    let mut groups: Vec<(T, Vec<usize>)> = Vec::new();
+    let mut lookup: HashMap<ReprKey<T>, usize> = HashMap::new();
+    for (i, v) in values.iter().enumerate() {
+        match lookup.get(&ReprKey(v)).cloned() {
+            Some(g) => groups[g].1.push(i),
+            None => {
+                lookup.insert(ReprKey(v), groups.len());
+                groups.push((v.clone(), vec![i]));
+            }
+        }
+    }
+    groups
+}
+
+/// The most frequent non-empty value, or empty if there is none. Ties go to the value seen
+/// first.
+pub fn mode<'a, I, T>(values: I) -> T
+    where I: Iterator<Item = &'a T>,
+          T: UtahNum + 'a
+{
+    let values: Vec<T> = values.filter(|x| !x.is_empty()).cloned().collect();
+    group_positions(&values[..])
+        .into_iter()
+        .fold((T::empty(), 0), |(m, n), (v, p)| if p.len() > n { (v, p.len()) } else { (m, n) })
+        .0
+}
+
+/// The number of distinct non-empty values. Values are told apart by hashing their debug
+/// representation or, given a `tolerance`, by sorting them and counting the gaps wider than it.
+pub fn distinct<'a, I, T>(values: I, tolerance: Option<&T>) -> usize
+    where I: Iterator<Item = &'a T>,
+          T: UtahNum + PartialOrd + 'a
+{
+    let values = values.filter(|x| !x.is_empty());
+    match tolerance {
+        None => values.map(ReprKey).collect::<HashSet<_>>().len(),
+        Some(tol) => {
+            let mut v: Vec<&T> = values.collect();
+            v.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+            let gaps = v.windows(2).filter(|w| w[1].clone() - w[0].clone() > *tol).count();
+            if v.is_empty() { 0 } else { gaps + 1 }
+        }
+    }
+}
+
+/// Gather the rows or columns at `positions` into a new matrix, in the given order.
+pub fn take<T>(data: &Matrix<T>, axis: UtahAxis, positions: &[usize]) -> Matrix<T>
+    where T: Clone
+{
+    let (nrows, ncols) = data.dim();
+    let mut v = Vec::with_capacity(positions.len() * match axis {
+        UtahAxis::Row => ncols,
+        UtahAxis::Column => nrows,
+    });
+    match axis {
+        UtahAxis::Row => {
+            for &p in positions {
+                v.extend(data.subview(Axis(0), p).iter().cloned());
+            }
+            Array::from_shape_vec((positions.len(), ncols), v).unwrap()
+        }
+        UtahAxis::Column => {
+            for row in data.axis_iter(Axis(0)) {
+                v.extend(positions.iter().map(|&p| row[p].clone()));
+            }
+            Array::from_shape_vec((nrows, positions.len()), v).unwrap()
+        }
+    }
+}
+
+/// Gather the labels at `positions`, in the given order.
+pub fn take_labels(labels: &[String], positions: &[usize]) -> Vec<String> {
+    positions.iter().map(|&p| labels[p].clone()).collect()
+}
+
+/// Draw `n` positions out of `population`, with or without replacement.
+pub fn sample_positions<R>(rng: &mut R,
+                           population: usize,
+                           n: usize,
+                           replace: bool)
+                           -> Result<Vec<usize>>
+    where R: Rng
+{
+    if replace {
+        if population == 0 && n > 0 {
+            return Err(ErrorKind::InvalidSampleSize(n.to_string(), population.to_string())
+                .into());
+        }
+        return Ok((0..n).map(|_| rng.gen_range(0, population)).collect());
+    }
+    if n > population {
+        return Err(ErrorKind::InvalidSampleSize(n.to_string(), population.to_string()).into());
+    }
+    // Partial Fisher-Yates shuffle.
+    let mut positions: Vec<usize> = (0..population).collect();
+    for i in 0..n {
+        let j = rng.gen_range(i, population);
+        positions.swap(i, j);
+    }
+    positions.truncate(n);
+    Ok(positions)
+}
+
+/// The number of positions in a `frac` share of `population`. The share must be finite and not
+/// negative.
+pub fn sample_size(frac: f64, population: usize) -> Result<usize> {
+    if !frac.is_finite() || frac < 0. {
+        return Err(ErrorKind::InvalidSampleSize(frac.to_string(), population.to_string()).into());
+    }
+    Ok((frac * population as f64).round() as usize)
+}
+
+/// Align two lists of labels: the labels of `a` followed by those of `b` left unpaired, with the
+/// position of each label in `a` and in `b`, if any. Repeated labels pair up in order of
+/// occurrence: the first `x` of `a` with the first `x` of `b`, the second with the second, and so
+/// on, so no label of either side is dropped.
+pub fn align_labels(a: &[String], b: &[String])
+                    -> Vec<(String, Option<usize>, Option<usize>)> {
+    let mut positions: HashMap<&str, VecDeque<usize>> = HashMap::with_capacity(b.len());
+    for (j, y) in b.iter().enumerate() {
+        positions.entry(&y[..]).or_insert_with(VecDeque::new).push_back(j);
+    }
+    let mut paired = vec![false; b.len()];
+    let mut aligned: Vec<_> = a.iter()
+        .enumerate()
+        .map(|(i, x)| {
+            let j = positions.get_mut(&x[..]).and_then(|p| p.pop_front());
+            if let Some(j) = j {
+                paired[j] = true;
+            }
+            (x.clone(), Some(i), j)
+        })
+        .collect();
+    aligned.extend(b.iter()
+        .enumerate()
+        .filter(|&(j, _)| !paired[j])
+        .map(|(j, y)| (y.clone(), None, Some(j))));
+    aligned
+}
+
+/// Reduce values to a single one. Empty values are skipped, except by `Agg::EmptyCount`, which
+/// counts them.
+pub fn reduce<'a, I, T>(values: I, agg: Agg) -> T
+    where I: Iterator<Item = &'a T>,
+          T: UtahNum + PartialOrd + 'a
+{
+    let (values, empties): (Vec<&T>, Vec<&T>) = values.partition(|x| !x.is_empty());
+    let values = values.into_iter();
+    match agg {
+        Agg::Sum => values.fold(T::zero(), |acc, x| acc + x.clone()),
+        Agg::Prod => values.fold(T::one(), |acc, x| acc * x.clone()),
+        Agg::Mean => {
+            let (sum, n) = values.fold((T::zero(), 0), |(acc, n), x| (acc + x.clone(), n + 1));
+            if n == 0 { T::empty() } else { sum / count(n) }
+        }
+        Agg::Max => {
+            values.fold(T::empty(), |acc, x| {
+                if acc.is_empty() || *x > acc { x.clone() } else { acc }
+            })
+        }
+        Agg::Min => {
+            values.fold(T::empty(), |acc, x| {
+                if acc.is_empty() || *x < acc { x.clone() } else { acc }
+            })
+        }
+        Agg::Count => count(values.count()),
+        Agg::EmptyCount => count(empties.len()),
+        Agg::FirstValid => values.cloned().next().unwrap_or_else(T::empty),
+        Agg::LastValid => values.cloned().last().unwrap_or_else(T::empty),
+    }
+}
+
+/// The labels of two sides laid end to end, with `suffixes` appended to the labels that appear on
+/// both sides.
+pub fn suffix_labels(left: &[String],
+                     right: &[String],
+                     suffixes: &(String, String))
+                     -> Vec<String> {
+    let (in_left, in_right): (HashSet<&String>, HashSet<&String>) =
+        (left.iter().collect(), right.iter().collect());
+    let tag = |x: &String, other: &HashSet<&String>, suffix: &String| if other.contains(x) {
+        format!("{}{}", x, suffix)
+    } else {
+        x.clone()
+    };
+    left.iter()
+        .map(|x| tag(x, &in_right, &suffixes.0))
+        .chain(right.iter().map(|x| tag(x, &in_left, &suffixes.1)))
+        .collect()
+}
+
+/// Lay out owned windows as the rows or columns of a dataframe, along the specified `UtahAxis`.
+pub fn windows_df<I, T>(windows: I, other: Vec<String>, axis: UtahAxis) -> DataFrame<T>
+    where I: Iterator<Item = (String, Row<T>)>,
+          T: UtahNum
+{
+    let mut c = Vec::new();
+    let mut n = Vec::new();
+    for (i, j) in windows {
+        c.extend(j.iter().cloned());
+        n.push(i);
+    }
+    let d = Array::from_shape_vec((n.len(), other.len()), c).unwrap();
+    match axis {
+        UtahAxis::Row => {
+            DataFrame {
+                columns: other,
+                data: d,
+                index: n,
+                index_sorted: false,
+            }
+        }
+        UtahAxis::Column => {
+            DataFrame {
+                columns: n,
+                data: d.reversed_axes(),
+                index: other,
+                index_sorted: false,
+            }
+        }
+    }
+}
+
+/// The statistics reported by `describe`, one row each.
+pub const DESCRIBE_STATS: [(&'static str, Agg); 5] = [("count", Agg::Count),
+                                                      ("mean", Agg::Mean),
+                                                      ("min", Agg::Min),
+                                                      ("max", Agg::Max),
+                                                      ("empty", Agg::EmptyCount)];
+
+/// Summarize the columns of `df` with `columns`, which holds one value per entry of
+/// `DESCRIBE_STATS` for each column.
+pub fn describe<T>(df: &DataFrame<T>, columns: Vec<Vec<T>>) -> DataFrame<T>
+    where T: UtahNum
+{
+    let mut d = Vec::with_capacity(DESCRIBE_STATS.len() * columns.len());
+    for r in 0..DESCRIBE_STATS.len() {
+        d.extend(columns.iter().map(|c| c[r].clone()));
+    }
+    DataFrame {
+        columns: df.columns.clone(),
+        data: Array::from_shape_vec((DESCRIBE_STATS.len(), columns.len()), d).unwrap(),
+        index: DESCRIBE_STATS.iter().map(|&(name, _)| name.to_string()).collect(),
+        index_sorted: false,
+    }
+}
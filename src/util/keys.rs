@@ -0,0 +1,137 @@
+//! Utah hash keys.
+//!
+//! Values are grouped and matched by their debug representation, which is defined for every
+//! `UtahNum` and tells apart the variants of mixed values. So `-0.0` and `0.0` are different keys,
+//! and all empty values are the same one. The keys below hash and compare that representation as
+//! it is written out, without building a string for it.
+
+use std::fmt::{self, Debug, Write};
+use std::hash::{Hash, Hasher};
+use ndarray::ArrayView1;
+use util::traits::UtahNum;
+
+/// Passes formatted text on to a hasher.
+struct HashWriter<'h, H: Hasher + 'h>(&'h mut H);
+
+impl<'h, H: Hasher> Write for HashWriter<'h, H> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.write(s.as_bytes());
+        Ok(())
+    }
+}
+
+/// Holds short formatted text, failing once it runs out of room.
+struct ShortBuf {
+    bytes: [u8; 64],
+    len: usize,
+}
+
+impl Write for ShortBuf {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        if end > self.bytes.len() {
+            return Err(fmt::Error);
+        }
+        self.bytes[self.len..end].copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+/// Checks formatted text against the text expected, failing at the first difference.
+struct Expect<'s>(&'s [u8]);
+
+impl<'s> Write for Expect<'s> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if !self.0.starts_with(s.as_bytes()) {
+            return Err(fmt::Error);
+        }
+        self.0 = &self.0[s.len()..];
+        Ok(())
+    }
+}
+
+/// Feed the debug representation of `x` to a hasher.
+pub fn hash_repr<T: Debug, H: Hasher>(x: &T, state: &mut H) {
+    write!(HashWriter(state), "{:?}", x).unwrap();
+    // Ends the representation, since no UTF-8 text holds this byte.
+    state.write_u8(0xff);
+}
+
+/// Whether two values have the same debug representation.
+pub fn same_repr<T: Debug>(a: &T, b: &T) -> bool {
+    let mut buf = ShortBuf {
+        bytes: [0; 64],
+        len: 0,
+    };
+    let same = |expected: &[u8]| {
+        let mut expect = Expect(expected);
+        write!(expect, "{:?}", b).is_ok() && expect.0.is_empty()
+    };
+    match write!(buf, "{:?}", a) {
+        Ok(()) => same(&buf.bytes[..buf.len]),
+        Err(_) => same(format!("{:?}", a).as_bytes()),
+    }
+}
+
+/// Feed the key of a value to a hasher.
+fn hash_value<T: UtahNum, H: Hasher>(x: &T, state: &mut H) {
+    if x.is_empty() {
+        state.write_u8(0xfe);
+    } else {
+        hash_repr(x, state);
+    }
+}
+
+/// Whether two values are the same key.
+fn same_value<T: UtahNum>(a: &T, b: &T) -> bool {
+    match (a.is_empty(), b.is_empty()) {
+        (false, false) => same_repr(a, b),
+        (x, y) => x && y,
+    }
+}
+
+/// A value as a hash key.
+#[derive(Clone, Copy, Debug)]
+pub struct ReprKey<'a, T: 'a>(pub &'a T);
+
+impl<'a, T: UtahNum> Hash for ReprKey<'a, T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_value(self.0, state);
+    }
+}
+
+impl<'a, T: UtahNum> PartialEq for ReprKey<'a, T> {
+    fn eq(&self, other: &Self) -> bool {
+        same_value(self.0, other.0)
+    }
+}
+
+impl<'a, T: UtahNum> Eq for ReprKey<'a, T> {}
+
+/// The values of a row at some positions, as a hash key.
+#[derive(Clone, Debug)]
+pub struct RowKey<'a, 'k, T: 'a> {
+    pub row: ArrayView1<'a, T>,
+    pub keys: &'k [usize],
+}
+
+impl<'a, 'k, T: UtahNum> Hash for RowKey<'a, 'k, T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for &k in self.keys {
+            hash_value(&self.row[k], state);
+        }
+    }
+}
+
+impl<'a, 'k, T: UtahNum> PartialEq for RowKey<'a, 'k, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.keys.len() == other.keys.len() &&
+        self.keys
+            .iter()
+            .zip(other.keys.iter())
+            .all(|(&i, &j)| same_value(&self.row[i], &other.row[j]))
+    }
+}
+
+impl<'a, 'k, T: UtahNum> Eq for RowKey<'a, 'k, T> {}
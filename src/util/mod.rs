@@ -4,6 +4,15 @@
 pub mod error;
 #[macro_use]
 pub mod macros;
+pub mod bins;
+pub mod blocks;
+pub mod helpers;
+pub mod keys;
+pub mod multiindex;
+pub mod rank;
 pub mod readcsv;
+pub mod rowset;
+pub mod stats;
+pub mod time;
 pub mod traits;
 pub mod types;
@@ -0,0 +1,142 @@
+//! Utah hierarchical index labels.
+//!
+//! A `MultiIndex` holds one tuple of labels per row. It is stored in a dataframe's `index` (or
+//! `columns`) as flat labels, with the levels joined by `LEVEL_SEPARATOR`, so every existing
+//! operation keeps working on it. A separator inside a level is escaped with `LEVEL_ESCAPE`, so
+//! any label survives the round-trip.
+
+use std::fmt;
+use util::error::*;
+
+/// The separator between levels in a flattened label.
+pub const LEVEL_SEPARATOR: char = '|';
+
+/// Escapes a `LEVEL_SEPARATOR` (or itself) inside a level.
+pub const LEVEL_ESCAPE: char = '\\';
+
+/// Escape a raw level value, so that it can be joined with other levels.
+pub fn escape_level(level: &str) -> String {
+    let mut s = String::with_capacity(level.len());
+    for c in level.chars() {
+        if c == LEVEL_SEPARATOR || c == LEVEL_ESCAPE {
+            s.push(LEVEL_ESCAPE);
+        }
+        s.push(c);
+    }
+    s
+}
+
+/// Join raw level values into a flattened label.
+pub fn join_levels<S: AsRef<str>>(levels: &[S]) -> String {
+    let sep = LEVEL_SEPARATOR.to_string();
+    levels.iter().map(|x| escape_level(x.as_ref())).collect::<Vec<_>>().join(&sep)
+}
+
+/// Split a flattened label on its unescaped separators, and unescape each level.
+pub fn split_levels(label: &str) -> Vec<String> {
+    let mut levels = vec![String::new()];
+    let mut chars = label.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            LEVEL_ESCAPE => levels.last_mut().unwrap().push(chars.next().unwrap_or(LEVEL_ESCAPE)),
+            LEVEL_SEPARATOR => levels.push(String::new()),
+            _ => levels.last_mut().unwrap().push(c),
+        }
+    }
+    levels
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MultiIndex {
+    pub tuples: Vec<Vec<String>>,
+}
+
+impl MultiIndex {
+    /// Build an index from one array of labels per level. All levels must be the same length.
+    pub fn from_arrays<U: Clone>(levels: &[&[U]]) -> Result<MultiIndex>
+        where String: From<U>
+    {
+        let n = levels.first().map_or(0, |l| l.len());
+        if let Some(l) = levels.iter().find(|l| l.len() != n) {
+            return Err(ErrorKind::IndexShapeMismatch(n.to_string(), l.len().to_string()).into());
+        }
+        let tuples = (0..n)
+            .map(|i| levels.iter().map(|l| String::from(l[i].clone())).collect())
+            .collect();
+        Ok(MultiIndex { tuples: tuples })
+    }
+
+    /// Split flattened labels back into their levels.
+    pub fn from_labels(labels: &[String]) -> MultiIndex {
+        MultiIndex {
+            tuples: labels.iter().map(|x| split_levels(x)).collect(),
+        }
+    }
+
+    /// The flattened labels, for use as a dataframe's index or columns.
+    pub fn labels(&self) -> Vec<String> {
+        self.tuples.iter().map(|t| join_levels(&t[..])).collect()
+    }
+
+    /// The number of levels, taken from the shortest tuple.
+    pub fn nlevels(&self) -> usize {
+        self.tuples.iter().map(|t| t.len()).min().unwrap_or(0)
+    }
+
+    /// The labels of a single level.
+    pub fn level(&self, level: usize) -> Result<Vec<String>> {
+        if level >= self.nlevels() {
+            return Err(ErrorKind::InvalidLevel(level.to_string()).into());
+        }
+        Ok(self.tuples.iter().map(|t| t[level].clone()).collect())
+    }
+
+    /// Drop a level, keeping the others in order.
+    pub fn droplevel(&self, level: usize) -> Result<MultiIndex> {
+        if level >= self.nlevels() {
+            return Err(ErrorKind::InvalidLevel(level.to_string()).into());
+        }
+        let tuples = self.tuples
+            .iter()
+            .map(|t| {
+                t.iter()
+                    .enumerate()
+                    .filter(|&(i, _)| i != level)
+                    .map(|(_, x)| x.clone())
+                    .collect()
+            })
+            .collect();
+        Ok(MultiIndex { tuples: tuples })
+    }
+}
+
+impl fmt::Display for MultiIndex {
+    /// One tuple per line, with the levels aligned. Outer levels that repeat the line above are
+    /// left blank.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut widths: Vec<usize> = Vec::new();
+        for t in &self.tuples {
+            for (l, x) in t.iter().enumerate() {
+                if l == widths.len() {
+                    widths.push(0);
+                }
+                widths[l] = widths[l].max(x.chars().count());
+            }
+        }
+        let mut previous: &[String] = &[];
+        for t in &self.tuples {
+            let mut repeated = true;
+            for (l, x) in t.iter().enumerate() {
+                if l + 1 == t.len() {
+                    write!(f, "{}", x)?;
+                } else {
+                    repeated = repeated && previous.get(l) == Some(x);
+                    write!(f, "{:1$} ", if repeated { "" } else { &x[..] }, widths[l])?;
+                }
+            }
+            writeln!(f, "")?;
+            previous = &t[..];
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,227 @@
+//! Utah ranking and ordering functions.
+
+use std::cmp::Ordering;
+use std::f64::NAN;
+use util::traits::UtahNum;
+use util::helpers::count;
+use util::types::{EmptyPosition, RankMethod};
+
+/// Positions of the non-empty values, in ascending order of value. The sort is stable.
+pub fn sorted_positions<T>(values: &[T]) -> Vec<usize>
+    where T: UtahNum + PartialOrd
+{
+    let mut order: Vec<usize> = (0..values.len()).filter(|&i| !values[i].is_empty()).collect();
+    order.sort_by(|&a, &b| values[a].partial_cmp(&values[b]).unwrap_or(Ordering::Equal));
+    order
+}
+
+/// Percent rank of each value, `(rank - 1) / (n - 1)`, where tied values share the lowest rank.
+/// Empty values become `NaN` and are not counted in `n`. The ranks are fractions whatever the
+/// type of the values, so they are always `f64`.
+pub fn pct_rank<T>(values: &[T]) -> Vec<f64>
+    where T: UtahNum + PartialOrd
+{
+    let mut out = vec![NAN; values.len()];
+    let order = sorted_positions(values);
+    if order.len() == 1 {
+        out[order[0]] = 0.;
+        return out;
+    }
+    let denom = order.len().saturating_sub(1) as f64;
+    let mut rank = 0;
+    for (i, &p) in order.iter().enumerate() {
+        if i > 0 && values[p] != values[order[i - 1]] {
+            rank = i;
+        }
+        out[p] = rank as f64 / denom;
+    }
+    out
+}
+
+/// Cumulative distribution of each value, i.e. the fraction of values less than or equal to it.
+/// Empty values become `NaN` and are not counted.
+pub fn cume_dist<T>(values: &[T]) -> Vec<f64>
+    where T: UtahNum + PartialOrd
+{
+    let mut out = vec![NAN; values.len()];
+    let order = sorted_positions(values);
+    let n = order.len() as f64;
+    let mut start = 0;
+    while start < order.len() {
+        let mut end = start;
+        while end < order.len() && values[order[end]] == values[order[start]] {
+            end += 1;
+        }
+        for &p in &order[start..end] {
+            out[p] = end as f64 / n;
+        }
+        start = end;
+    }
+    out
+}
+
+/// Compare two values for sorting. Empty values are placed according to `empties`, regardless of
+/// the sort direction.
+pub fn compare<T>(a: &T, b: &T, ascending: bool, empties: EmptyPosition) -> Ordering
+    where T: UtahNum + PartialOrd
+{
+    match (a.is_empty(), b.is_empty()) {
+        (true, true) => Ordering::Equal,
+        (true, false) => {
+            match empties {
+                EmptyPosition::First => Ordering::Less,
+                EmptyPosition::Last => Ordering::Greater,
+            }
+        }
+        (false, true) => {
+            match empties {
+                EmptyPosition::First => Ordering::Greater,
+                EmptyPosition::Last => Ordering::Less,
+            }
+        }
+        (false, false) => {
+            let o = a.partial_cmp(b).unwrap_or(Ordering::Equal);
+            if ascending { o } else { o.reverse() }
+        }
+    }
+}
+
+/// Compare two labels for sorting. Labels that parse as numbers (other than NaN) come first, in
+/// numeric order, and the others follow lexicographically, so ISO 8601 dates sort chronologically.
+/// This is a total order, unlike comparing mixed pairs one way or the other.
+pub fn compare_labels(a: &str, b: &str) -> Ordering {
+    let number = |x: &str| match x.parse::<f64>() {
+        Ok(x) if !x.is_nan() => Some(x),
+        _ => None,
+    };
+    match (number(a), number(b)) {
+        (Some(x), Some(y)) => x.partial_cmp(&y).unwrap(),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => a.cmp(b),
+    }
+}
+
+/// Positions of the labels, in sorted order. The sort is stable.
+pub fn sorted_label_positions(labels: &[String], ascending: bool) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..labels.len()).collect();
+    order.sort_by(|&a, &b| {
+        let o = compare_labels(&labels[a], &labels[b]);
+        if ascending { o } else { o.reverse() }
+    });
+    order
+}
+
+/// Rank of each value, starting at one, with ties broken by `method`. Empty values stay empty if
+/// `empties` is `None`, and otherwise are ranked together before or after all other values.
+pub fn rank<T>(values: &[T], method: RankMethod, empties: Option<EmptyPosition>) -> Vec<T>
+    where T: UtahNum + PartialOrd
+{
+    let mut out = vec![T::empty(); values.len()];
+    let order = sorted_positions(values);
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    let mut start = 0;
+    while start < order.len() {
+        let end = (start..order.len())
+            .find(|&e| values[order[e]] != values[order[start]])
+            .unwrap_or(order.len());
+        groups.push(order[start..end].to_vec());
+        start = end;
+    }
+    let missing: Vec<usize> = (0..values.len()).filter(|&i| values[i].is_empty()).collect();
+    if !missing.is_empty() {
+        match empties {
+            Some(EmptyPosition::First) => groups.insert(0, missing),
+            Some(EmptyPosition::Last) => groups.push(missing),
+            None => {}
+        }
+    }
+    let mut seen = 0;
+    for (dense, group) in groups.iter().enumerate() {
+        let (low, high) = (seen + 1, seen + group.len());
+        let r: T = match method {
+            RankMethod::Average => count::<T>(low + high) / count(2),
+            RankMethod::Min => count(low),
+            RankMethod::Max => count(high),
+            RankMethod::Dense => count(dense + 1),
+        };
+        for &p in group {
+            out[p] = r.clone();
+        }
+        seen = high;
+    }
+    out
+}
+
+/// Move the `k`-th smallest value into position `k` of `values`, with no larger values before it
+/// and no smaller values after it, in linear expected time. Each pass splits the values into
+/// those below, equal to and above the pivot, so repeated values do not slow it down.
+pub fn select_nth<T>(values: &mut [T], k: usize)
+    where T: PartialOrd
+{
+    let (mut lo, mut hi) = (0, values.len());
+    while hi - lo > 1 {
+        let mid = lo + (hi - lo) / 2;
+        values.swap(lo, mid);
+        // The pivot stays at `lt`: `lo..lt` is below it, `lt..i` equal and `gt..hi` above.
+        let (mut lt, mut i, mut gt) = (lo, lo + 1, hi);
+        while i < gt {
+            if values[i] < values[lt] {
+                values.swap(lt, i);
+                lt += 1;
+                i += 1;
+            } else if values[i] > values[lt] {
+                gt -= 1;
+                values.swap(i, gt);
+            } else {
+                i += 1;
+            }
+        }
+        if k < lt {
+            hi = lt;
+        } else if k >= gt {
+            lo = gt;
+        } else {
+            return;
+        }
+    }
+}
+
+/// The `q`-th quantile of the non-empty values, interpolating linearly between the closest ranks.
+/// Empty if there are no values.
+pub fn quantile<T>(values: &[T], q: f64) -> T
+    where T: UtahNum + PartialOrd + From<f64>
+{
+    let mut v: Vec<T> = values.iter().filter(|x| !x.is_empty()).cloned().collect();
+    if v.is_empty() {
+        return T::empty();
+    }
+    let h = q.max(0.).min(1.) * (v.len() - 1) as f64;
+    let k = h.floor() as usize;
+    select_nth(&mut v[..], k);
+    let a = v[k].clone();
+    if k + 1 == v.len() || h == k as f64 {
+        return a;
+    }
+    let b = v[k + 1..].iter().fold(v[k + 1].clone(), |m, x| if *x < m { x.clone() } else { m });
+    a.clone() + (b - a) * T::from(h - k as f64)
+}
+
+/// The median of the non-empty values, the mean of the two middle ones for an even count. Empty
+/// if there are no values.
+pub fn median<T>(values: &[T]) -> T
+    where T: UtahNum + PartialOrd
+{
+    let mut v: Vec<T> = values.iter().filter(|x| !x.is_empty()).cloned().collect();
+    let n = v.len();
+    if n == 0 {
+        return T::empty();
+    }
+    let k = n / 2;
+    select_nth(&mut v[..], k);
+    if n % 2 == 1 {
+        return v[k].clone();
+    }
+    let a = v[..k].iter().fold(v[0].clone(), |m, x| if *x > m { x.clone() } else { m });
+    (a + v[k].clone()) / count(2)
+}
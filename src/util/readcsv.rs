@@ -12,6 +12,8 @@ use util::traits::UtahNum;
 use util::error::*;
 use util::traits::Constructor;
 use rustc_serialize::Decodable;
+use std::fs;
+use std::path::{Path, PathBuf};
 
 use csv;
 
@@ -21,6 +23,58 @@ pub trait ReadCSV<T>
     where T: UtahNum + Decodable
 {
     fn read_csv(file: &'static str) -> Result<DataFrame<T>>;
+    fn read_csv_glob(pattern: &str) -> Result<(DataFrame<T>, Vec<SchemaDeviation>)>;
+}
+
+/// How the columns of one file differ from the unified schema of a multi-file read.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SchemaDeviation {
+    pub path: PathBuf,
+    /// Columns of the unified schema the file lacks. They are filled with empty values.
+    pub missing: Vec<String>,
+    /// Whether the file's own columns come in a different order than in the unified schema.
+    pub reordered: bool,
+}
+
+/// Whether `name` matches `pattern`, where `*` matches any run of characters and `?` any single
+/// character.
+fn wildcard_match(pattern: &[char], name: &[char]) -> bool {
+    match (pattern.first(), name.first()) {
+        (None, None) => true,
+        (Some(&'*'), _) => {
+            wildcard_match(&pattern[1..], name) ||
+            (!name.is_empty() && wildcard_match(pattern, &name[1..]))
+        }
+        (Some(&'?'), Some(_)) => wildcard_match(&pattern[1..], &name[1..]),
+        (Some(p), Some(n)) if p == n => wildcard_match(&pattern[1..], &name[1..]),
+        _ => false,
+    }
+}
+
+/// The files matching `pattern`, in sorted order. Wildcards may only appear in the file name.
+fn glob_files(pattern: &str) -> Result<Vec<PathBuf>> {
+    let path = Path::new(pattern);
+    let dir = match path.parent() {
+        Some(d) if !d.as_os_str().is_empty() => d.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+    let name: Vec<char> = path.file_name()
+        .and_then(|x| x.to_str())
+        .unwrap_or("")
+        .chars()
+        .collect();
+    let entries = fs::read_dir(&dir).map_err(|e| ErrorKind::ParseError(e.to_string()))?;
+    let mut files: Vec<PathBuf> = entries.filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_file())
+        .filter(|p| {
+            p.file_name()
+                .and_then(|x| x.to_str())
+                .map_or(false, |x| wildcard_match(&name[..], &x.chars().collect::<Vec<_>>()[..]))
+        })
+        .collect();
+    files.sort();
+    Ok(files)
 }
 
 impl<T> ReadCSV<T> for DataFrame<T>
@@ -40,4 +94,58 @@ impl<T> ReadCSV<T> for DataFrame<T>
         let matrix = Array::from_shape_vec((nrow, ncol), v).unwrap();
         DataFrame::new(matrix).columns(&columns[..])
     }
+
+    /// Read every file matching `pattern` (e.g. `"data/*.csv"`) and stack their rows. The
+    /// columns are unified by name, in order of first appearance, and columns a file lacks are
+    /// filled with empty values. The files that deviate from the unified schema are reported.
+    fn read_csv_glob(pattern: &str) -> Result<(DataFrame<T>, Vec<SchemaDeviation>)> {
+        let files = glob_files(pattern)?;
+        if files.is_empty() {
+            return Err(ErrorKind::NoMatchingFiles(pattern.to_string()).into());
+        }
+        let mut columns: Vec<String> = Vec::new();
+        let mut tables = Vec::new();
+        for path in &files {
+            let mut rdr = csv::Reader::from_file(path)
+                .map_err(|e| ErrorKind::ParseError(e.to_string()))?;
+            let headers = rdr.headers().map_err(|e| ErrorKind::ParseError(e.to_string()))?;
+            let mut rows: Vec<Vec<T>> = Vec::new();
+            for record in rdr.decode() {
+                rows.push(record.map_err(|e| ErrorKind::ParseError(e.to_string()))?);
+            }
+            for h in &headers {
+                if !columns.contains(h) {
+                    columns.push(h.clone());
+                }
+            }
+            tables.push((headers, rows));
+        }
+        let mut deviations = Vec::new();
+        let mut v = Vec::new();
+        let mut nrow = 0;
+        for (path, &(ref headers, ref rows)) in files.iter().zip(tables.iter()) {
+            let positions: Vec<Option<usize>> =
+                columns.iter().map(|c| headers.iter().position(|h| h == c)).collect();
+            let missing: Vec<String> = columns.iter()
+                .zip(positions.iter())
+                .filter(|&(_, p)| p.is_none())
+                .map(|(c, _)| c.clone())
+                .collect();
+            let order: Vec<usize> = positions.iter().filter_map(|&p| p).collect();
+            let reordered = order.windows(2).any(|w| w[0] > w[1]);
+            if !missing.is_empty() || reordered {
+                deviations.push(SchemaDeviation {
+                    path: path.clone(),
+                    missing: missing,
+                    reordered: reordered,
+                });
+            }
+            for row in rows {
+                v.extend(positions.iter().map(|&p| p.map_or_else(T::empty, |p| row[p].clone())));
+                nrow += 1;
+            }
+        }
+        let matrix = Array::from_shape_vec((nrow, columns.len()), v).unwrap();
+        Ok((DataFrame::new(matrix).columns(&columns[..])?, deviations))
+    }
 }
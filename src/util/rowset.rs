@@ -0,0 +1,117 @@
+//! Utah row sets.
+//!
+//! A `RowSet` records which rows of a dataframe satisfy a predicate as one bit per row. Sets for
+//! several predicates can be combined with `&`, `|` and `!` before gathering the matching rows
+//! once, instead of materializing a dataframe after each condition.
+
+use std::ops::{BitAnd, BitOr, Not};
+
+const WORD_BITS: usize = 64;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RowSet {
+    len: usize,
+    words: Vec<u64>,
+}
+
+impl RowSet {
+    /// An empty set over `len` rows.
+    pub fn new(len: usize) -> RowSet {
+        RowSet {
+            len: len,
+            words: vec![0; (len + WORD_BITS - 1) / WORD_BITS],
+        }
+    }
+
+    /// A set holding every one of `len` rows.
+    pub fn full(len: usize) -> RowSet {
+        !RowSet::new(len)
+    }
+
+    /// The set of rows for which `f` holds.
+    pub fn from_fn<F>(len: usize, f: F) -> RowSet
+        where F: Fn(usize) -> bool
+    {
+        let mut set = RowSet::new(len);
+        for row in (0..len).filter(|&r| f(r)) {
+            set.insert(row);
+        }
+        set
+    }
+
+    /// The number of rows the set ranges over.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count() == 0
+    }
+
+    pub fn insert(&mut self, row: usize) {
+        assert!(row < self.len, "row {} out of range for {} rows", row, self.len);
+        self.words[row / WORD_BITS] |= 1 << (row % WORD_BITS);
+    }
+
+    pub fn contains(&self, row: usize) -> bool {
+        row < self.len && self.words[row / WORD_BITS] & (1 << (row % WORD_BITS)) != 0
+    }
+
+    /// The number of rows in the set.
+    pub fn count(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// The rows in the set, in ascending order.
+    pub fn positions(&self) -> Vec<usize> {
+        (0..self.len).filter(|&r| self.contains(r)).collect()
+    }
+
+    fn zip_words<F>(&self, other: &RowSet, f: F) -> RowSet
+        where F: Fn(u64, u64) -> u64
+    {
+        assert_eq!(self.len, other.len, "row sets range over different numbers of rows");
+        RowSet {
+            len: self.len,
+            words: self.words.iter().zip(other.words.iter()).map(|(&a, &b)| f(a, b)).collect(),
+        }
+    }
+}
+
+impl<'a> BitAnd for &'a RowSet {
+    type Output = RowSet;
+    fn bitand(self, other: &RowSet) -> RowSet {
+        self.zip_words(other, |a, b| a & b)
+    }
+}
+
+impl<'a> BitOr for &'a RowSet {
+    type Output = RowSet;
+    fn bitor(self, other: &RowSet) -> RowSet {
+        self.zip_words(other, |a, b| a | b)
+    }
+}
+
+impl Not for RowSet {
+    type Output = RowSet;
+    fn not(mut self) -> RowSet {
+        for w in self.words.iter_mut() {
+            *w = !*w;
+        }
+        // Clear the bits past the last row.
+        let tail = self.len % WORD_BITS;
+        if tail != 0 {
+            if let Some(last) = self.words.last_mut() {
+                *last &= (1 << tail) - 1;
+            }
+        }
+        self
+    }
+}
+
+impl<'a> Not for &'a RowSet {
+    type Output = RowSet;
+    fn not(self) -> RowSet {
+        !self.clone()
+    }
+}
@@ -0,0 +1,51 @@
+//! Utah statistics over pairs of samples.
+
+use util::rank::rank;
+use util::types::{CorrMethod, RankMethod};
+
+/// The correlation of two samples of the same length, or NaN if it is undefined.
+pub fn corr(x: &[f64], y: &[f64], method: CorrMethod) -> f64 {
+    match method {
+        CorrMethod::Pearson => pearson(x, y),
+        CorrMethod::Spearman => {
+            pearson(&rank(x, RankMethod::Average, None)[..],
+                    &rank(y, RankMethod::Average, None)[..])
+        }
+        CorrMethod::Kendall => kendall(x, y),
+    }
+}
+
+fn pearson(x: &[f64], y: &[f64]) -> f64 {
+    let n = x.len() as f64;
+    let (mx, my) = (x.iter().sum::<f64>() / n, y.iter().sum::<f64>() / n);
+    let (mut sxy, mut sxx, mut syy) = (0., 0., 0.);
+    for (a, b) in x.iter().zip(y.iter()) {
+        sxy += (a - mx) * (b - my);
+        sxx += (a - mx) * (a - mx);
+        syy += (b - my) * (b - my);
+    }
+    sxy / (sxx * syy).sqrt()
+}
+
+/// Kendall's tau-b, which accounts for ties.
+fn kendall(x: &[f64], y: &[f64]) -> f64 {
+    let (mut concordant, mut discordant, mut ties_x, mut ties_y) = (0f64, 0., 0., 0.);
+    for i in 0..x.len() {
+        for j in i + 1..x.len() {
+            let s = (x[i] - x[j]) * (y[i] - y[j]);
+            if x[i] == x[j] && y[i] == y[j] {
+                continue;
+            } else if x[i] == x[j] {
+                ties_x += 1.;
+            } else if y[i] == y[j] {
+                ties_y += 1.;
+            } else if s > 0. {
+                concordant += 1.;
+            } else {
+                discordant += 1.;
+            }
+        }
+    }
+    let pairs = concordant + discordant;
+    (concordant - discordant) / ((pairs + ties_x) * (pairs + ties_y)).sqrt()
+}
@@ -0,0 +1,99 @@
+//! Utah timestamps
+//!
+//! Index labels hold timestamps as ISO 8601 strings, either dates (`2017-01-31`) or date-times
+//! (`2017-01-31T09:30:00`, or with a space instead of the `T`). They are handled here as whole
+//! seconds since the Unix epoch, in UTC.
+
+use std::time::Duration;
+use util::error::*;
+
+const DAY: i64 = 86400;
+
+/// Days since the epoch of a civil date.
+fn days_from_civil(y: i64, m: i64, d: i64) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = y - era * 400;
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+/// The civil date of a number of days since the epoch.
+fn civil_from_days(z: i64) -> (i64, i64, i64) {
+    let z = z + 719468;
+    let era = if z >= 0 { z } else { z - 146096 } / 146097;
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    (if m <= 2 { yoe + era * 400 + 1 } else { yoe + era * 400 }, m, d)
+}
+
+/// Parse a timestamp label into seconds since the epoch.
+pub fn parse_timestamp(label: &str) -> Result<i64> {
+    let fail = || -> Error { ErrorKind::InvalidTimestamp(label.to_string()).into() };
+    let field = |s: Option<&str>, lo: i64, hi: i64| -> Result<i64> {
+        match s.and_then(|s| s.parse::<i64>().ok()) {
+            Some(x) if x >= lo && x <= hi => Ok(x),
+            _ => Err(fail()),
+        }
+    };
+    let label = label.trim();
+    let label = if label.ends_with('Z') { &label[..label.len() - 1] } else { label };
+    let (date, time) = match label.find(|c| c == 'T' || c == ' ') {
+        Some(i) => (&label[..i], Some(&label[i + 1..])),
+        None => (label, None),
+    };
+    let mut date = date.splitn(3, '-');
+    let y = field(date.next(), 0, 9999)?;
+    let m = field(date.next(), 1, 12)?;
+    let d = field(date.next(), 1, 31)?;
+    let mut seconds = days_from_civil(y, m, d) * DAY;
+    if let Some(time) = time {
+        let mut time = time.splitn(3, ':');
+        seconds += field(time.next(), 0, 23)? * 3600;
+        seconds += field(time.next(), 0, 59)? * 60;
+        if let Some(s) = time.next() {
+            seconds += field(Some(s), 0, 60)?;
+        }
+    }
+    Ok(seconds)
+}
+
+/// Format seconds since the epoch as a date-time label, or as a date label if it falls on
+/// midnight.
+pub fn format_timestamp(seconds: i64) -> String {
+    let days = if seconds >= 0 { seconds / DAY } else { (seconds + 1) / DAY - 1 };
+    let rest = seconds - days * DAY;
+    let (y, m, d) = civil_from_days(days);
+    if rest == 0 {
+        format!("{:04}-{:02}-{:02}", y, m, d)
+    } else {
+        format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+                y,
+                m,
+                d,
+                rest / 3600,
+                rest % 3600 / 60,
+                rest % 60)
+    }
+}
+
+/// Parse a frequency such as `30S`, `15min`, `6H`, `1D` or `1W`.
+pub fn frequency(freq: &str) -> Result<Duration> {
+    let fail = || -> Error { ErrorKind::InvalidTimestamp(freq.to_string()).into() };
+    let split = freq.find(|c: char| !c.is_digit(10)).unwrap_or(freq.len());
+    let n: u64 = if split == 0 { 1 } else { freq[..split].parse().map_err(|_| fail())? };
+    let unit = match &freq[split..] {
+        "S" | "s" => 1,
+        "T" | "min" => 60,
+        "H" | "h" => 3600,
+        "D" | "d" => 86400,
+        "W" | "w" => 7 * 86400,
+        _ => return Err(fail()),
+    };
+    Ok(Duration::from_secs(n * unit))
+}
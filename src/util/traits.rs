@@ -4,12 +4,23 @@ use std::iter::Iterator;
 use combinators::aggregate::*;
 use combinators::transform::*;
 use combinators::process::*;
+use combinators::window::*;
+use combinators::groupby::*;
 use dataframe::{DataFrame, DataFrameMut, DataFrameIterator, DataFrameMutIterator};
 use std::fmt::Debug;
 use util::error::*;
 use std::ops::{Add, Sub, Mul, Div};
 use num::traits::{One, Zero};
 use ndarray::ArrayView1;
+use mixedtypes::InnerType;
+use rand::Rng;
+use std::time::Duration;
+use transaction::Transaction;
+use expr::{Expr, VirtualFrame};
+use series::Series;
+use util::blocks::BlockIndex;
+use util::rowset::RowSet;
+use cache::StatsCache;
 
 pub trait UtahNum
     : Add<Output = Self> +
@@ -44,6 +55,18 @@ impl<T> UtahNum for T
 pub trait Empty<T> {
     fn empty() -> T;
     fn is_empty(&self) -> bool;
+
+    /// The count `n` as a value. By default it is built up from ones by doubling, in `O(log n)`
+    /// additions; the numeric types convert it directly.
+    fn from_count(n: usize) -> T
+        where T: Zero + One + Add<Output = T> + Clone
+    {
+        let bits = (0usize.count_zeros() - n.leading_zeros()) as usize;
+        (0..bits).rev().fold(T::zero(), |acc, b| {
+            let twice = acc.clone() + acc;
+            if n >> b & 1 == 1 { twice + T::one() } else { twice }
+        })
+    }
 }
 
 pub trait Constructor<'a, T>
@@ -62,7 +85,10 @@ pub trait Constructor<'a, T>
 pub trait Operations<'a, T>
     where T: 'a + UtahNum
 {
-    fn shape(self) -> (usize, usize);
+    fn shape(&self) -> (usize, usize);
+    fn nrows(&self) -> usize;
+    fn ncols(&self) -> usize;
+    fn is_empty(&self) -> bool;
     fn select<U: ?Sized>(&'a self, names: &'a [&'a U], axis: UtahAxis) -> SelectIter<'a, T>
         where String: From<&'a U>;
     fn remove<U: ?Sized>(&'a self, names: &'a [&'a U], axis: UtahAxis) -> RemoveIter<'a, T>
@@ -77,6 +103,8 @@ pub trait Operations<'a, T>
     fn outer_left_join(&'a self, other: &'a DataFrame<T>) -> OuterJoinIter<'a, T>;
     fn inner_right_join(&'a self, other: &'a DataFrame<T>) -> InnerJoinIter<'a, T>;
     fn outer_right_join(&'a self, other: &'a DataFrame<T>) -> OuterJoinIter<'a, T>;
+    #[deprecated(note = "the axis names the lanes that are chained, so `UtahAxis::Column` stacks \
+                         rows; use `concat_rows` to stack rows aligned on column names")]
     fn concat(&'a self, other: &'a DataFrame<T>, axis: UtahAxis) -> ConcatIter<'a, T>;
     fn sumdf(&'a mut self, axis: UtahAxis) -> SumIter<'a, T>;
     fn mean(&'a mut self, axis: UtahAxis) -> MeanIter<'a, T>;
@@ -85,7 +113,194 @@ pub trait Operations<'a, T>
     fn mapdf<F>(&'a mut self, f: F, axis: UtahAxis) -> MapDFIter<'a, T, F>
         where F: Fn(T) -> T,
               for<'r> F: Fn(T) -> T;
-    fn impute(&'a mut self, strategy: ImputeStrategy, axis: UtahAxis) -> ImputeIter<'a, T>;
+    fn impute(&'a mut self, strategy: ImputeStrategy<T>, axis: UtahAxis) -> ImputeIter<'a, T>;
+    fn pct_rank<U: ?Sized>(&'a self, column: &'a U) -> Result<DataFrame<f64>>
+        where String: From<&'a U>,
+              T: PartialOrd;
+    fn pct_rank_by<U: ?Sized>(&'a self, column: &'a U, by: &'a U) -> Result<DataFrame<f64>>
+        where String: From<&'a U>,
+              T: PartialOrd;
+    fn cume_dist<U: ?Sized>(&'a self, column: &'a U) -> Result<DataFrame<f64>>
+        where String: From<&'a U>,
+              T: PartialOrd;
+    fn cume_dist_by<U: ?Sized>(&'a self, column: &'a U, by: &'a U) -> Result<DataFrame<f64>>
+        where String: From<&'a U>,
+              T: PartialOrd;
+    fn sort_values<U: ?Sized>(&'a self,
+                              columns: &'a [&'a U],
+                              ascending: bool,
+                              empties: EmptyPosition)
+                              -> Result<DataFrame<T>>
+        where String: From<&'a U>,
+              T: PartialOrd;
+    fn cut<U: ?Sized>(&'a self,
+                      column: &'a U,
+                      edges: &[T],
+                      labels: &[&str])
+                      -> Result<DataFrame<InnerType>>
+        where String: From<&'a U>,
+              T: PartialOrd;
+    fn sort_index(&'a self, ascending: bool) -> DataFrame<T>;
+    fn rolling(&'a self, window: usize) -> Rolling<'a, T>;
+    fn groupby<U: ?Sized>(&'a self, key: &'a U) -> Result<GroupBy<'a, T>>
+        where String: From<&'a U>;
+    fn sample<R: Rng>(&'a self, n: usize, replace: bool, rng: &mut R) -> Result<DataFrame<T>>;
+    fn sample_frac<R: Rng>(&'a self,
+                           frac: f64,
+                           replace: bool,
+                           rng: &mut R)
+                           -> Result<DataFrame<T>>;
+    fn sample_stratified<U: ?Sized, R: Rng>(&'a self,
+                                            by: &'a U,
+                                            frac: f64,
+                                            replace: bool,
+                                            rng: &mut R)
+                                            -> Result<DataFrame<T>>
+        where String: From<&'a U>;
+    fn rank_rows(&'a mut self) -> RankIter<'a, T>;
+    fn demean_rows(&'a mut self) -> DemeanIter<'a, T>;
+    fn shuffle<R: Rng>(&mut self, rng: &mut R);
+    fn trimmed_mean(&'a self, frac: f64, axis: UtahAxis) -> TrimmedMeanIter<'a, T>;
+    fn winsorized_mean(&'a self, frac: f64, axis: UtahAxis) -> WinsorizedMeanIter<'a, T>;
+    fn groupby_binned<U: ?Sized>(&'a self, column: &'a U, edges: &[T]) -> Result<GroupBy<'a, T>>
+        where String: From<&'a U>,
+              T: PartialOrd;
+    fn unique<U: ?Sized>(&'a self, column: &'a U) -> Result<Row<T>> where String: From<&'a U>;
+    fn reduce(&'a self, agg: Agg) -> T where T: PartialOrd;
+    fn value_counts<U: ?Sized>(&'a self, column: &'a U) -> Result<DataFrame<T>>
+        where String: From<&'a U>;
+    fn xs(&'a self, level: usize, key: &str) -> Result<DataFrame<T>>;
+    fn groupby_level(&'a self, level: usize) -> Result<GroupBy<'a, T>>;
+    fn insert_column_at<U: ?Sized>(&'a self,
+                                   pos: usize,
+                                   name: &'a U,
+                                   data: ArrayView1<'a, T>)
+                                   -> InsertIter<'a, T>
+        where String: From<&'a U>;
+    fn pop_column<U: ?Sized>(&mut self, name: &U) -> Result<Row<T>>
+        where for<'b> String: From<&'b U>;
+    fn with_column<U: ?Sized, F>(&'a self, name: &'a U, f: F) -> WithColumnIter<'a, T, F>
+        where String: From<&'a U>,
+              F: Fn(&RowView<'a, T>) -> T;
+    fn begin(&'a mut self) -> Transaction<'a, T>;
+    fn apply<F>(&'a self, f: F, axis: UtahAxis) -> ApplyIter<'a, T, F>
+        where F: Fn(ArrayView1<'a, T>) -> Vec<T>;
+    fn define<U: ?Sized>(&'a self, name: &U, expr: Expr<T>) -> VirtualFrame<'a, T>
+        where for<'b> String: From<&'b U>;
+    fn column<U: ?Sized>(&'a self, name: &U) -> Result<Series<T>>
+        where for<'b> String: From<&'b U>;
+    fn insert_series_at(&mut self, pos: usize, series: &Series<T>) -> Result<()>;
+    fn append_series(&mut self, series: &Series<T>) -> Result<()>;
+    fn block_index<U: ?Sized>(&'a self, column: &U, block_size: usize) -> Result<BlockIndex<T>>
+        where for<'b> String: From<&'b U>,
+              T: PartialOrd;
+    fn filter_range(&'a self, index: &BlockIndex<T>, lo: &T, hi: &T) -> Result<DataFrame<T>>
+        where T: PartialOrd;
+    fn scalar_op(&'a mut self, op: ArithOp, value: T, axis: UtahAxis) -> ScalarOpIter<'a, T>;
+    fn dot(&'a self, other: &DataFrame<T>) -> Result<DataFrame<T>>;
+    fn cumsum(&'a self, axis: UtahAxis) -> CumulativeIter<'a, T>;
+    fn cumprod(&'a self, axis: UtahAxis) -> CumulativeIter<'a, T>;
+    fn assert_monotonic<U: ?Sized>(&'a self, column: &U, direction: Monotonic) -> Result<()>
+        where for<'b> String: From<&'b U>,
+              T: PartialOrd;
+    fn assert_in_range<U: ?Sized>(&'a self, column: &U, lo: &T, hi: &T) -> Result<()>
+        where for<'b> String: From<&'b U>,
+              T: PartialOrd;
+    fn shift(&'a self, periods: isize, fill: T, axis: UtahAxis) -> Shift<'a, T>;
+    fn diff(&'a self, periods: isize, axis: UtahAxis) -> Shift<'a, T>;
+    fn first_valid(&'a self, axis: UtahAxis) -> ReduceIter<'a, T>;
+    fn last_valid(&'a self, axis: UtahAxis) -> ReduceIter<'a, T>;
+    fn rank(&'a mut self,
+            axis: UtahAxis,
+            method: RankMethod,
+            empties: Option<EmptyPosition>)
+            -> RankIter<'a, T>;
+    fn mask<U: ?Sized, F>(&'a self, column: &U, f: F) -> Result<RowSet>
+        where for<'b> String: From<&'b U>,
+              F: Fn(&T) -> bool;
+    fn take_rows(&'a self, rows: &RowSet) -> Result<DataFrame<T>>;
+    fn clip(&'a mut self, lo: T, hi: T) -> ClipIter<'a, T> where T: PartialOrd;
+    fn clip_lower(&'a mut self, lo: T) -> ClipIter<'a, T> where T: PartialOrd;
+    fn clip_upper(&'a mut self, hi: T) -> ClipIter<'a, T> where T: PartialOrd;
+    fn describe(&'a self) -> DataFrame<T> where T: PartialOrd;
+    fn describe_cached(&'a self, cache: &StatsCache<T>) -> Result<DataFrame<T>>
+        where T: PartialOrd;
+    fn replace(&'a mut self, pairs: &[(T, T)], columns: Option<&[&str]>) -> ReplaceIter<'a, T>;
+    fn replace_with<F>(&'a mut self, f: F, columns: Option<&[&str]>) -> ReplaceWithIter<'a, T, F>
+        where F: Fn(&T) -> Option<T>;
+    fn cell_mask<F>(&'a self, f: F) -> Matrix<bool> where F: Fn(&T) -> bool;
+    fn where_mask(&'a self, mask: &Matrix<bool>, other: T) -> Result<DataFrame<T>>;
+    fn where_mask_df(&'a self, mask: &Matrix<bool>, other: &DataFrame<T>) -> Result<DataFrame<T>>;
+    fn get_mut(&'a mut self, column: &str) -> Option<RowViewMut<'a, T>>;
+    fn row_mut(&'a mut self, label: &str) -> Option<RowViewMut<'a, T>>;
+    fn median(&'a self, axis: UtahAxis) -> MedianIter<'a, T>;
+    fn apply_cdc<F>(&'a self,
+                    changes: &DataFrame<T>,
+                    op: &str,
+                    keys: &[&str],
+                    decode: F)
+                    -> Result<DataFrame<T>>
+        where F: Fn(&T) -> Option<ChangeOp>;
+    fn concat_evolve(&'a self, other: &DataFrame<T>) -> Result<(DataFrame<T>, SchemaEvolution)>;
+    fn mode(&'a self, axis: UtahAxis) -> ModeIter<'a, T>;
+    fn count(&'a self, axis: UtahAxis) -> ReduceIter<'a, T>;
+    fn corr(&'a self, method: CorrMethod) -> DataFrame<f64> where T: Into<f64>;
+    fn gmean(&'a self, axis: UtahAxis) -> Result<GMeanIter<'a, T>>
+        where T: PartialOrd + Into<f64> + From<f64>;
+    fn hmean(&'a self, axis: UtahAxis) -> Result<HMeanIter<'a, T>>
+        where T: PartialOrd + Into<f64> + From<f64>;
+    fn prod(&'a self, axis: UtahAxis) -> ReduceIter<'a, T>;
+    fn nunique(&'a self, axis: UtahAxis, tolerance: Option<T>) -> NUniqueIter<'a, T>;
+    fn expanding(&'a self, min_periods: usize) -> Rolling<'a, T>;
+    fn ewm(&'a self, decay: Decay) -> Result<Ewm<'a, T>>;
+    fn agg<F>(&'a self, f: F, axis: UtahAxis) -> AggWithIter<'a, T, F>
+        where F: Fn(ArrayView1<'a, T>) -> T;
+    fn groupby_multi(&'a self, by: &[&str]) -> Result<GroupBy<'a, T>>;
+    fn pivot_table(&'a self,
+                   index: &str,
+                   columns: &str,
+                   values: &str,
+                   agg: Agg)
+                   -> Result<DataFrame<T>>
+        where T: PartialOrd;
+    fn melt(&'a self, id_vars: &[&str], value_vars: &[&str]) -> Result<DataFrame<T>>
+        where T: From<String>;
+    fn crosstab(&'a self, a: &str, b: &str, normalize: Option<UtahAxis>) -> Result<DataFrame<f64>>;
+    fn resample(&'a self, every: Duration) -> Result<GroupBy<'a, T>>;
+    fn qcut<U: ?Sized>(&'a self,
+                       column: &'a U,
+                       q: usize,
+                       labels: &[&str])
+                       -> Result<DataFrame<InnerType>>
+        where String: From<&'a U>,
+              T: PartialOrd + From<f64>;
+    fn hist<U: ?Sized>(&'a self, column: &'a U, bins: usize) -> Result<DataFrame<T>>
+        where String: From<&'a U>,
+              T: PartialOrd;
+    fn hist_all(&'a self, bins: usize) -> DataFrame<T> where T: PartialOrd;
+    fn standardize(&'a mut self, axis: UtahAxis) -> StandardizeIter<'a, T>
+        where T: From<f64> + Into<f64>;
+    fn minmax_scale(&'a mut self, feature_range: (T, T), axis: UtahAxis) -> MinMaxScaleIter<'a, T>
+        where T: PartialOrd;
+    fn inner_join_on(&'a self, other: &'a DataFrame<T>, on: &str) -> Result<InnerJoinIter<'a, T>>;
+    fn outer_join_on(&'a self, other: &'a DataFrame<T>, on: &str) -> Result<OuterJoinIter<'a, T>>;
+    fn inner_join_on_keys(&'a self,
+                          other: &'a DataFrame<T>,
+                          on: &[&str])
+                          -> Result<InnerJoinIter<'a, T>>;
+    fn outer_join_on_keys(&'a self,
+                          other: &'a DataFrame<T>,
+                          on: &[&str])
+                          -> Result<OuterJoinIter<'a, T>>;
+    fn anti_join(&'a self, other: &DataFrame<T>) -> DataFrame<T>;
+    fn semi_join(&'a self, other: &DataFrame<T>) -> DataFrame<T>;
+    fn join_columns(&'a self, other: &DataFrame<T>) -> DataFrame<T>;
+    fn concat_rows(&'a self,
+                   other: &DataFrame<T>,
+                   join: ConcatJoin,
+                   keys: Option<(&str, &str)>)
+                   -> Result<DataFrame<T>>;
+    fn dropna(&'a self, axis: UtahAxis, how: DropHow, thresh: Option<usize>) -> DataFrame<T>;
 }
 
 pub trait Aggregate<'a, T>
@@ -104,7 +319,7 @@ pub trait Process<'a, T, F>
     where T: UtahNum,
           F: Fn(T) -> T
 {
-    fn impute(self, strategy: ImputeStrategy) -> Impute<'a, Self, T>
+    fn impute(self, strategy: ImputeStrategy<T>) -> Impute<'a, Self, T>
         where Self: Sized + Iterator<Item = WindowMut<'a, T>>;
     fn to_mut_df(self) -> DataFrameMut<'a, T> where Self: Sized + Iterator<Item = WindowMut<'a, T>>;
     fn mapdf(self, f: F) -> MapDF<'a, T, Self, F>
@@ -130,6 +345,14 @@ pub trait Transform<'a, T>
 
 
 
+/// Reductions of boolean masks, such as those built by `cell_mask`.
+pub trait MaskOps {
+    /// Whether any entry is true, per row or per column along the specified `UtahAxis`.
+    fn any(&self, axis: UtahAxis) -> Row<bool>;
+    /// Whether every entry is true, per row or per column along the specified `UtahAxis`.
+    fn all(&self, axis: UtahAxis) -> Row<bool>;
+}
+
 pub trait ToDataFrame<'a, I, T>
     where T: UtahNum + 'a
 {
@@ -4,7 +4,9 @@ use combinators::interact::*;
 use combinators::aggregate::*;
 use combinators::process::*;
 use std::iter::Chain;
+use std::ops::{Add, Sub, Mul, Div};
 use dataframe::{DataFrameIterator, DataFrameMutIterator};
+use rustc_serialize::{Decodable, Decoder, Encodable, Encoder};
 
 
 #[derive( Clone, Debug, Copy)]
@@ -14,8 +16,177 @@ pub enum UtahAxis {
 }
 
 #[derive( Clone, Debug)]
-pub enum ImputeStrategy {
+pub enum ImputeStrategy<T> {
     Mean,
+    /// The most frequent value, which also suits categorical data.
+    Mode,
+    /// The closest non-empty value before the gap. Leading gaps stay empty.
+    ForwardFill,
+    /// The closest non-empty value after the gap. Trailing gaps stay empty.
+    BackFill,
+    /// A straight line between the non-empty values around the gap, treating entries as evenly
+    /// spaced. Leading and trailing gaps are handled by the `InterpolateEdge`.
+    Interpolate(InterpolateEdge),
+    /// A fixed value, such as zero or a domain-specific default.
+    Constant(T),
+}
+
+/// Which rows or columns `dropna` removes.
+#[derive(Clone, Debug, Copy, PartialEq)]
+pub enum DropHow {
+    /// Those with any empty value.
+    Any,
+    /// Those with only empty values.
+    All,
+}
+
+/// How interpolation fills the gaps before the first or after the last non-empty value.
+#[derive(Clone, Debug, Copy, PartialEq)]
+pub enum InterpolateEdge {
+    /// Leave them empty.
+    Empty,
+    /// Repeat the nearest non-empty value.
+    Nearest,
+}
+
+/// An arithmetic operation between two values.
+#[derive(Clone, Debug, Copy, PartialEq)]
+pub enum ArithOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+impl ArithOp {
+    /// Apply the operation to `a` and `b`.
+    pub fn apply<T>(&self, a: T, b: T) -> T
+        where T: Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T>
+    {
+        match *self {
+            ArithOp::Add => a + b,
+            ArithOp::Sub => a - b,
+            ArithOp::Mul => a * b,
+            ArithOp::Div => a / b,
+        }
+    }
+}
+
+/// A direction in which a sequence of values can be monotonic.
+#[derive(Clone, Debug, Copy, PartialEq)]
+pub enum Monotonic {
+    Increasing,
+    Decreasing,
+    StrictlyIncreasing,
+    StrictlyDecreasing,
+}
+
+/// A reduction of many values to one.
+#[derive(Clone, Debug, Copy, PartialEq)]
+pub enum Agg {
+    Sum,
+    Prod,
+    Mean,
+    Max,
+    Min,
+    Count,
+    EmptyCount,
+    FirstValid,
+    LastValid,
+}
+
+const AGGS: [(&'static str, Agg); 9] = [("Sum", Agg::Sum),
+                                        ("Prod", Agg::Prod),
+                                        ("Mean", Agg::Mean),
+                                        ("Max", Agg::Max),
+                                        ("Min", Agg::Min),
+                                        ("Count", Agg::Count),
+                                        ("EmptyCount", Agg::EmptyCount),
+                                        ("FirstValid", Agg::FirstValid),
+                                        ("LastValid", Agg::LastValid)];
+
+impl Encodable for Agg {
+    fn encode<S: Encoder>(&self, s: &mut S) -> Result<(), S::Error> {
+        let i = AGGS.iter().position(|&(_, a)| a == *self).unwrap();
+        s.emit_enum("Agg", |s| s.emit_enum_variant(AGGS[i].0, i, 0, |_| Ok(())))
+    }
+}
+
+impl Decodable for Agg {
+    fn decode<D: Decoder>(d: &mut D) -> Result<Agg, D::Error> {
+        let names: Vec<&str> = AGGS.iter().map(|&(name, _)| name).collect();
+        d.read_enum("Agg", |d| d.read_enum_variant(&names[..], |_, i| Ok(AGGS[i].1)))
+    }
+}
+
+/// How fast the weights of an exponentially weighted window decay.
+#[derive(Clone, Debug, Copy, PartialEq)]
+pub enum Decay {
+    /// The smoothing factor, in `(0, 1]`.
+    Alpha(f64),
+    /// The span `s`, at least one, for a smoothing factor of `2 / (s + 1)`.
+    Span(f64),
+}
+
+impl Decay {
+    pub fn alpha(&self) -> f64 {
+        match *self {
+            Decay::Alpha(a) => a,
+            Decay::Span(s) => 2. / (s + 1.),
+        }
+    }
+}
+
+/// A measure of correlation between two columns.
+#[derive(Clone, Debug, Copy, PartialEq)]
+pub enum CorrMethod {
+    Pearson,
+    /// Pearson correlation of the ranks.
+    Spearman,
+    Kendall,
+}
+
+/// How tied values are ranked.
+#[derive(Clone, Debug, Copy, PartialEq)]
+pub enum RankMethod {
+    Average,
+    Min,
+    Max,
+    Dense,
+}
+
+/// How the columns changed when stacking two dataframes with different schemas.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct SchemaEvolution {
+    /// Columns of the second dataframe the first lacks. They are added after the first
+    /// dataframe's columns and filled with empty values in its rows.
+    pub added: Vec<String>,
+    /// Columns of the first dataframe the second lacks. They are filled with empty values in the
+    /// second dataframe's rows.
+    pub missing: Vec<String>,
+}
+
+/// Which columns to keep when stacking dataframes with different columns.
+#[derive(Clone, Debug, Copy, PartialEq)]
+pub enum ConcatJoin {
+    /// The columns of either dataframe, filled with empty values where missing.
+    Outer,
+    /// Only the columns both dataframes have.
+    Inner,
+}
+
+/// The kind of a change in a change-data-capture stream.
+#[derive(Clone, Debug, Copy, PartialEq)]
+pub enum ChangeOp {
+    Insert,
+    Update,
+    Delete,
+}
+
+#[derive( Clone, Debug, Copy, PartialEq)]
+pub enum EmptyPosition {
+    First,
+    Last,
 }
 
 
@@ -32,18 +203,39 @@ pub type MatrixView<'a, T> = ArrayView2<'a, T>;
 
 pub type DFIter<'a, T> = DataFrameIterator<'a, T>;
 pub type AppendIter<'a, T> = Append<'a, DFIter<'a, T>, T>;
+pub type InsertIter<'a, T> = Insert<'a, DFIter<'a, T>, T>;
+pub type WithColumnIter<'a, T, F> = WithColumn<'a, DFIter<'a, T>, T, F>;
+pub type ApplyIter<'a, T, F> = Apply<'a, DFIter<'a, T>, T, F>;
 pub type SelectIter<'a, T> = Select<'a, DFIter<'a, T>, T>;
 pub type RemoveIter<'a, T> = Remove<'a, DFIter<'a, T>, T>;
-pub type InnerJoinIter<'a, T> = InnerJoin<'a, DFIter<'a, T>, T>;
-pub type OuterJoinIter<'a, T> = OuterJoin<'a, DFIter<'a, T>, T>;
+pub type InnerJoinIter<'a, T> = InnerJoin<'a, T>;
+pub type OuterJoinIter<'a, T> = OuterJoin<'a, T>;
 pub type ConcatIter<'a, T> = Concat<'a, Chain<DFIter<'a, T>, DFIter<'a, T>>, T>;
 pub type SumIter<'a, T> = Sum<'a, DFIter<'a, T>, T>;
 pub type MaxIter<'a, T> = Max<'a, DFIter<'a, T>, T>;
 pub type MinIter<'a, T> = Min<'a, DFIter<'a, T>, T>;
 pub type StdevIter<'a, T> = Stdev<'a, DFIter<'a, T>, T>;
 pub type MeanIter<'a, T> = Mean<'a, DFIter<'a, T>, T>;
+pub type CumulativeIter<'a, T> = Cumulative<'a, DFIter<'a, T>, T>;
+pub type ReduceIter<'a, T> = Reduce<'a, DFIter<'a, T>, T>;
+pub type ModeIter<'a, T> = Mode<'a, DFIter<'a, T>, T>;
+pub type NUniqueIter<'a, T> = NUnique<'a, DFIter<'a, T>, T>;
+pub type AggWithIter<'a, T, F> = AggWith<'a, DFIter<'a, T>, T, F>;
+pub type MedianIter<'a, T> = Median<'a, DFIter<'a, T>, T>;
+pub type GMeanIter<'a, T> = GMean<'a, DFIter<'a, T>, T>;
+pub type HMeanIter<'a, T> = HMean<'a, DFIter<'a, T>, T>;
+pub type TrimmedMeanIter<'a, T> = TrimmedMean<'a, DFIter<'a, T>, T>;
+pub type WinsorizedMeanIter<'a, T> = WinsorizedMean<'a, DFIter<'a, T>, T>;
 pub type MapDFIter<'a, T, F> = MapDF<'a, T, DataFrameMutIterator<'a, T>, F>;
 pub type ImputeIter<'a, T> = Impute<'a, DataFrameMutIterator<'a, T>, T>;
+pub type RankIter<'a, T> = Rank<'a, DataFrameMutIterator<'a, T>, T>;
+pub type ScalarOpIter<'a, T> = ScalarOp<'a, DataFrameMutIterator<'a, T>, T>;
+pub type ClipIter<'a, T> = Clip<'a, DataFrameMutIterator<'a, T>, T>;
+pub type ReplaceIter<'a, T> = Replace<'a, DataFrameMutIterator<'a, T>, T>;
+pub type ReplaceWithIter<'a, T, F> = ReplaceWith<'a, DataFrameMutIterator<'a, T>, T, F>;
+pub type StandardizeIter<'a, T> = Standardize<'a, DataFrameMutIterator<'a, T>, T>;
+pub type MinMaxScaleIter<'a, T> = MinMaxScale<'a, DataFrameMutIterator<'a, T>, T>;
+pub type DemeanIter<'a, T> = Demean<'a, DataFrameMutIterator<'a, T>, T>;
 
 pub type WindowMut<'a, T> = (String, ArrayViewMut1<'a, T>);
 pub type Window<'a, T> = (String, ArrayView1<'a, T>);